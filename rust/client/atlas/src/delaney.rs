@@ -0,0 +1,770 @@
+use crate::{validate, Atlas, NotationError, NotationPolygon};
+use crate::periodic::{
+    angle_distance, cross, direction, interior_angle, normalize_angle, periodic_radius, periodic_vertex_posts, regular_tile, PointMap,
+    MAX_GENERATED, POINT_MARGIN,
+};
+use geometry::{Point, ORIGIN};
+use models::{FullAtlasPost, FullAtlasVertexPost, FullSubTilingPost};
+use std::{collections::VecDeque, f64::consts::{PI, TAU}};
+use tile::Tile;
+
+// upper bound on the radius a symbol's tiling is developed within while looking for its translations
+const MAX_DEVELOP_RADIUS: f64 = 64.;
+
+// canonical keys record angles in degrees and lengths rounded to this many parts per unit
+const KEY_PRECISION: f64 = 1000.;
+
+// StarEdge is an edge of an atlas vertex: (sides of the tile anticlockwise of it, neighbor index, neighbor edge index, parity)
+type StarEdge = (usize, usize, usize, bool);
+// Development is a patch of a symbol's tiling: (tiles, shortest two independent translations)
+type Development = (Vec<Tile>, Option<(Point, Point)>);
+
+// DelaneySymbol is the Delaney-Dress symbol of a tiling, e.g. <1.1:1:1,1,1:4,4> for the square tiling. Its chambers are
+// the triangles of the tiling's barycentric subdivision up to the tiling's symmetries, each with its 0 corner at a vertex,
+// its 1 corner at the midpoint of one of the vertex's edges and its 2 corner at the center of one of the edge's tiles.
+// It's written in the format used by Gavrog and 2dtiling: the size, then for each operation the chamber it maps each
+// chamber to unless that's a lesser chamber, then the tile degree of each orbit of operations 0 and 1 followed by the
+// vertex degree of each orbit of operations 1 and 2, with chambers numbered from 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DelaneySymbol {
+    // operations[i][d] is the chamber sharing chamber d's side opposite its i corner, which is d itself along a mirror
+    pub operations: [Vec<usize>; 3],
+    // tile_degrees[d] is the number of sides of chamber d's tile
+    pub tile_degrees: Vec<usize>,
+    // vertex_degrees[d] is the number of edges at chamber d's vertex
+    pub vertex_degrees: Vec<usize>,
+}
+
+impl DelaneySymbol {
+    pub fn new(operations: [Vec<usize>; 3], tile_degrees: Vec<usize>, vertex_degrees: Vec<usize>) -> Result<DelaneySymbol, NotationError> {
+        let size = tile_degrees.len();
+        if size == 0 {
+            return Err(NotationError::Empty)
+        }
+        for (operation, images) in operations.iter().enumerate() {
+            for (chamber, image) in images.iter().enumerate() {
+                if images.len() != size || images.get(*image) != Some(&chamber) {
+                    return Err(NotationError::InvalidOperation { operation, chamber })
+                }
+            }
+        }
+        if vertex_degrees.len() != size {
+            return Err(NotationError::InvalidDegree { chamber: size.min(vertex_degrees.len()) })
+        }
+
+        let symbol = DelaneySymbol { operations, tile_degrees, vertex_degrees };
+        if symbol.orbits(&[0, 1, 2]).len() != 1 {
+            return Err(NotationError::DisconnectedSymbol)
+        }
+        for chamber in 0..size {
+            let op = |i: usize| symbol.operations[i][chamber];
+            if symbol.tile_degrees[op(0)] != symbol.tile_degrees[chamber] || symbol.tile_degrees[op(1)] != symbol.tile_degrees[chamber]
+                || symbol.vertex_degrees[op(1)] != symbol.vertex_degrees[chamber] || symbol.vertex_degrees[op(2)] != symbol.vertex_degrees[chamber]
+                || [(0, 1), (0, 2), (1, 2)].iter().any(|(i, j)| symbol.degree(*i, *j, chamber) % symbol.period(*i, *j, chamber) != 0)
+            {
+                return Err(NotationError::InvalidDegree { chamber })
+            }
+        }
+        Ok(symbol)
+    }
+
+    pub fn parse(symbol: &str) -> Result<DelaneySymbol, NotationError> {
+        let invalid = || NotationError::InvalidSymbol { symbol: symbol.to_string() };
+        let parts = symbol
+            .trim()
+            .strip_prefix('<')
+            .and_then(|symbol| symbol.strip_suffix('>'))
+            .ok_or_else(invalid)?
+            .split(':')
+            .collect::<Vec<&str>>();
+        if parts.len() != 4 {
+            return Err(invalid())
+        }
+        let numbers = |list: &str| list.split_whitespace().map(|number| number.parse::<usize>().map_err(|_| invalid())).collect::<Result<Vec<usize>, NotationError>>();
+
+        let header = numbers(parts[1])?;
+        let size = match header.as_slice() { [size] | [size, 2] => *size, _ => return Err(invalid()) };
+
+        let lists = parts[2].split(',').map(numbers).collect::<Result<Vec<Vec<usize>>, NotationError>>()?;
+        if lists.len() != 3 {
+            return Err(invalid())
+        }
+        let mut operations = [vec![size; size], vec![size; size], vec![size; size]];
+        for (operation, list) in lists.iter().enumerate() {
+            let mut images = list.iter();
+            for chamber in 0..size {
+                if operations[operation][chamber] != size {
+                    continue
+                }
+                let image = images.next().ok_or_else(invalid)?.wrapping_sub(1);
+                if image >= size || operations[operation][image] != size {
+                    return Err(NotationError::InvalidOperation { operation, chamber })
+                }
+                operations[operation][chamber] = image;
+                operations[operation][image] = chamber;
+            }
+            if images.next().is_some() {
+                return Err(invalid())
+            }
+        }
+
+        let lists = parts[3].split(',').map(numbers).collect::<Result<Vec<Vec<usize>>, NotationError>>()?;
+        if lists.len() != 2 {
+            return Err(invalid())
+        }
+        let partial = DelaneySymbol { operations, tile_degrees: vec![0; size], vertex_degrees: vec![0; size] };
+        let mut degrees = [vec![0; size], vec![0; size]];
+        for (i, list) in lists.iter().enumerate() {
+            let orbits = partial.orbits(&[i, i + 1]);
+            if orbits.len() != list.len() {
+                return Err(invalid())
+            }
+            for (orbit, degree) in orbits.iter().zip(list.iter()) {
+                for chamber in orbit.iter() {
+                    degrees[i][*chamber] = *degree;
+                }
+            }
+        }
+        let [tile_degrees, vertex_degrees] = degrees;
+
+        DelaneySymbol::new(partial.operations, tile_degrees, vertex_degrees)
+    }
+
+    // from_vertex_posts derives the symbol of an atlas, with one chamber per side of each of its vertices' edges
+    pub fn from_vertex_posts(polygons: &[Tile], vertices: &[FullAtlasVertexPost]) -> Result<DelaneySymbol, NotationError> {
+        let validation = validate(polygons, vertices);
+        if !validation.valid {
+            return Err(NotationError::InvalidAtlas { issues: validation.issues })
+        }
+        let stars = vertices
+            .iter()
+            .map(|vertex| {
+                vertex.edges
+                    .iter()
+                    .map(|edge| (polygons[edge.polygon_index].size(), edge.neighbor_index, edge.neighbor_edge_index, edge.parity))
+                    .collect()
+            })
+            .collect::<Vec<Vec<StarEdge>>>();
+        DelaneySymbol::from_stars(&stars)
+    }
+
+    // from_atlas derives the symbol of an atlas' proto vertex stars, see from_vertex_posts
+    pub fn from_atlas(atlas: &Atlas) -> Result<DelaneySymbol, NotationError> {
+        let stars = atlas.proto_vertex_stars
+            .iter()
+            .map(|star| {
+                star.tiles
+                    .iter()
+                    .zip(star.proto_neighbors.iter())
+                    .map(|(tile, neighbor)| (tile.size(), neighbor.proto_vertex_star_index, neighbor.neighbor_index, neighbor.transform.parity))
+                    .collect()
+            })
+            .collect::<Vec<Vec<StarEdge>>>();
+        DelaneySymbol::from_stars(&stars)
+    }
+
+    // from_stars derives a symbol from the edges of an atlas' vertices, each given by the number of sides of the tile
+    // anticlockwise of it and its neighbor's vertex index, edge index and parity. Edge j of vertex i has chamber (i, j, 0)
+    // on its anticlockwise side, the side of tile j, and chamber (i, j - 1, 1) on its clockwise side, the side of tile j - 1.
+    fn from_stars(stars: &[Vec<StarEdge>]) -> Result<DelaneySymbol, NotationError> {
+        let offsets = stars
+            .iter()
+            .scan(0, |offset, star| {
+                let chamber = *offset;
+                *offset += 2 * star.len();
+                Some(chamber)
+            })
+            .collect::<Vec<usize>>();
+        let size: usize = stars.iter().map(|star| 2 * star.len()).sum();
+        let chamber = |i: usize, j: usize, side: usize| offsets[i] + 2 * (j % stars[i].len()) + side;
+
+        let mut operations = [vec![0; size], vec![0; size], vec![0; size]];
+        let mut tile_degrees = vec![0; size];
+        let mut vertex_degrees = vec![0; size];
+        for (i, star) in stars.iter().enumerate() {
+            let n = star.len();
+            for (j, (sides, _, _, _)) in star.iter().enumerate() {
+                for side in 0..2 {
+                    let d = chamber(i, j, side);
+                    tile_degrees[d] = *sides;
+                    vertex_degrees[d] = n;
+                    operations[1][d] = chamber(i, j, 1 - side);
+                    operations[2][d] = if side == 0 { chamber(i, j + n - 1, 1) } else { chamber(i, j + 1, 0) };
+
+                    // a chamber's edge is the vertex's edge j on side 0 and edge j + 1 on side 1
+                    let (_, k, m, parity) = star[(j + side) % n];
+                    let neighbor_size = match stars.get(k) { Some(neighbor) if m < neighbor.len() => neighbor.len(), _ => return Err(NotationError::InvalidOperation { operation: 0, chamber: d }) };
+                    operations[0][d] = match (side == 0) == parity {
+                        true => chamber(k, m, 0),
+                        false => chamber(k, m + neighbor_size - 1, 1),
+                    };
+                }
+            }
+        }
+
+        DelaneySymbol::new(operations, tile_degrees, vertex_degrees)
+    }
+
+    pub fn size(&self) -> usize {
+        self.tile_degrees.len()
+    }
+
+    // degree returns the degree of operations i and j at chamber d: the number of sides of its tile, the number of
+    // edges at its vertex or, for operations 0 and 2, 2 as every edge has two vertices and two tiles
+    pub fn degree(&self, i: usize, j: usize, d: usize) -> usize {
+        match (i.min(j), i.max(j)) {
+            (0, 1) => self.tile_degrees[d],
+            (1, 2) => self.vertex_degrees[d],
+            _ => 2,
+        }
+    }
+
+    // period returns the least number of times operations i then j are applied to return to chamber d
+    pub fn period(&self, i: usize, j: usize, d: usize) -> usize {
+        let mut period = 1;
+        let mut chamber = self.operations[j][self.operations[i][d]];
+        while chamber != d {
+            chamber = self.operations[j][self.operations[i][chamber]];
+            period += 1;
+        }
+        period
+    }
+
+    // curvature is 0 for symbols of tilings of the plane, positive for the sphere and negative for the hyperbolic plane
+    pub fn curvature(&self) -> f64 {
+        (0..self.size()).map(|d| 1. / self.tile_degrees[d] as f64 + 1. / self.vertex_degrees[d] as f64 - 0.5).sum()
+    }
+
+    // dual returns the symbol of the dual tiling, whose vertices are the tiling's tiles and whose tiles are its vertices
+    pub fn dual(&self) -> DelaneySymbol {
+        DelaneySymbol {
+            operations: [self.operations[2].clone(), self.operations[1].clone(), self.operations[0].clone()],
+            tile_degrees: self.vertex_degrees.clone(),
+            vertex_degrees: self.tile_degrees.clone(),
+        }
+    }
+
+    // minimal merges the chambers which are indistinguishable by their operations and degrees, giving the symbol of the
+    // tiling up to all its symmetries
+    pub fn minimal(&self) -> DelaneySymbol {
+        self.labelled_minimal(&vec![(); self.size()]).0
+    }
+
+    // canonical returns the minimal symbol with its chambers numbered in the order they're reached from the chamber
+    // which gives the least numbering, so that two tilings are the same exactly when their canonical symbols are
+    pub fn canonical(&self) -> DelaneySymbol {
+        self.labelled_canonical(&vec![(); self.size()]).0
+    }
+
+    // labelled_minimal is minimal where chambers are also only merged when they have equal labels,
+    // returning the label of each of the minimal symbol's chambers
+    pub(crate) fn labelled_minimal<L: Clone + PartialEq>(&self, labels: &[L]) -> (DelaneySymbol, Vec<L>) {
+        let classes = self.labelled_classes(labels);
+        let count = classes.iter().max().unwrap() + 1;
+        let representatives = (0..count).map(|class| classes.iter().position(|other| *other == class).unwrap()).collect::<Vec<usize>>();
+        let symbol = DelaneySymbol {
+            operations: [0, 1, 2].map(|i| representatives.iter().map(|d| classes[self.operations[i][*d]]).collect()),
+            tile_degrees: representatives.iter().map(|d| self.tile_degrees[*d]).collect(),
+            vertex_degrees: representatives.iter().map(|d| self.vertex_degrees[*d]).collect(),
+        };
+        (symbol, representatives.iter().map(|d| labels[*d].clone()).collect())
+    }
+
+    // labelled_classes returns the chamber of labelled_minimal's symbol each chamber is merged into, so two chambers have
+    // the same class exactly when some symmetry of the tiling maps one onto the other
+    pub(crate) fn labelled_classes<L: PartialEq>(&self, labels: &[L]) -> Vec<usize> {
+        let size = self.size();
+        let number = |keys: Vec<Vec<usize>>| {
+            let mut distinct: Vec<Vec<usize>> = vec![];
+            keys
+                .into_iter()
+                .map(|key| match distinct.iter().position(|other| *other == key) {
+                    Some(class) => class,
+                    None => { distinct.push(key); distinct.len() - 1 },
+                })
+                .collect::<Vec<usize>>()
+        };
+
+        let mut classes = number((0..size).map(|d| {
+            let label = labels.iter().position(|label| *label == labels[d]).unwrap();
+            vec![self.tile_degrees[d], self.vertex_degrees[d], label]
+        }).collect());
+        loop {
+            let refined = number((0..size).map(|d| {
+                let mut key = vec![classes[d]];
+                key.extend(self.operations.iter().map(|images| classes[images[d]]));
+                key
+            }).collect());
+            let count = refined.iter().max().unwrap() + 1;
+            let done = count == classes.iter().max().unwrap() + 1;
+            classes = refined;
+            if done {
+                break
+            }
+        }
+        classes
+    }
+
+    // labelled_canonical is canonical where the numbering is also the least by the chambers' labels,
+    // returning the label of each of the canonical symbol's chambers
+    fn labelled_canonical<L: Clone + Ord>(&self, labels: &[L]) -> (DelaneySymbol, Vec<L>) {
+        let (minimal, labels) = self.labelled_minimal(labels);
+        let size = minimal.size();
+        let (_, order) = (0..size)
+            .map(|start| {
+                let mut order = vec![start];
+                let mut numbers = vec![size; size];
+                numbers[start] = 0;
+                let mut index = 0;
+                while index < order.len() {
+                    for images in minimal.operations.iter() {
+                        let image = images[order[index]];
+                        if numbers[image] == size {
+                            numbers[image] = order.len();
+                            order.push(image);
+                        }
+                    }
+                    index += 1;
+                }
+                let code = order
+                    .iter()
+                    .flat_map(|d| {
+                        let mut entry = minimal.operations.iter().map(|images| numbers[images[*d]]).collect::<Vec<usize>>();
+                        entry.extend(vec![minimal.tile_degrees[*d], minimal.vertex_degrees[*d]]);
+                        entry
+                    })
+                    .collect::<Vec<usize>>();
+                ((code, order.iter().map(|d| labels[*d].clone()).collect::<Vec<L>>()), order)
+            })
+            .min()
+            .unwrap();
+
+        let mut numbers = vec![0; size];
+        for (number, d) in order.iter().enumerate() {
+            numbers[*d] = number;
+        }
+        let symbol = DelaneySymbol {
+            operations: [0, 1, 2].map(|i| order.iter().map(|d| numbers[minimal.operations[i][*d]]).collect()),
+            tile_degrees: order.iter().map(|d| minimal.tile_degrees[*d]).collect(),
+            vertex_degrees: order.iter().map(|d| minimal.vertex_degrees[*d]).collect(),
+        };
+        (symbol, order.iter().map(|d| labels[*d].clone()).collect())
+    }
+
+    // orbifold returns the Conway orbifold symbol of the tiling's symmetries, e.g. *632 or 4*2, with x for crosscaps
+    pub fn orbifold(&self) -> String {
+        let size = self.size();
+        let op = |i: usize, d: usize| self.operations[i][d];
+        let order = |i: usize, j: usize, d: usize| self.degree(i, j, d) / self.period(i, j, d);
+
+        // cone points are rotation centers which no mirror passes through
+        let mut cones: Vec<usize> = vec![];
+        let mut point_count = 0;
+        for (i, j) in [(0, 1), (0, 2), (1, 2)].iter() {
+            for orbit in self.orbits(&[*i, *j]) {
+                point_count += 1;
+                if order(*i, *j, orbit[0]) > 1 && orbit.iter().all(|d| op(*i, *d) != *d && op(*j, *d) != *d) {
+                    cones.push(order(*i, *j, orbit[0]));
+                }
+            }
+        }
+        cones.sort_unstable_by(|a, b| b.cmp(a));
+
+        // each mirror boundary is walked along the chamber sides fixed by an operation, collecting the corners it turns at
+        let mut boundaries: Vec<Vec<usize>> = vec![];
+        let mut walked = vec![[false; 3]; size];
+        for start in 0..size {
+            for side in 0..3 {
+                if op(side, start) != start || walked[start][side] {
+                    continue
+                }
+                let mut corners: Vec<usize> = vec![];
+                let (mut d, mut side, mut toward) = (start, side, (side + 1) % 3);
+                loop {
+                    walked[d][side] = true;
+                    let other = 3 - side - toward;
+                    let corner = order(side, other, d);
+                    if corner > 1 {
+                        corners.push(corner);
+                    }
+                    let (mut next, mut crossing) = (d, other);
+                    while op(crossing, next) != next {
+                        next = op(crossing, next);
+                        crossing = if crossing == other { side } else { other };
+                    }
+                    toward = 3 - crossing - toward;
+                    d = next;
+                    side = crossing;
+                    if walked[d][side] {
+                        break
+                    }
+                }
+                let n = corners.len();
+                let greatest = (0..n.max(1))
+                    .flat_map(|offset| vec![
+                        (0..n).map(|k| corners[(offset + k) % n]).collect::<Vec<usize>>(),
+                        (0..n).map(|k| corners[(offset + n - k) % n]).collect::<Vec<usize>>(),
+                    ])
+                    .max()
+                    .unwrap();
+                boundaries.push(greatest);
+            }
+        }
+        boundaries.sort_unstable_by(|a, b| b.cmp(a));
+
+        let edge_count: usize = (0..3).map(|i| (size + (0..size).filter(|d| op(i, *d) == *d).count()) / 2).sum();
+        let euler_characteristic = point_count as isize - edge_count as isize + size as isize;
+
+        let mut colors: Vec<Option<bool>> = vec![None; size];
+        let mut is_orientable = true;
+        colors[0] = Some(true);
+        let mut queue = VecDeque::from(vec![0]);
+        while let Some(d) = queue.pop_front() {
+            for i in 0..3 {
+                let e = op(i, d);
+                match colors[e] {
+                    _ if e == d => {},
+                    None => { colors[e] = colors[d].map(|color| !color); queue.push_back(e); },
+                    Some(color) => is_orientable &= Some(color) != colors[d],
+                }
+            }
+        }
+
+        let genus = 2 - euler_characteristic - boundaries.len() as isize;
+        let (handles, crosscaps) = if is_orientable { (genus / 2, 0) } else { (0, genus) };
+        let mut symbol = "o".repeat(handles.max(0) as usize);
+        symbol.extend(cones.iter().map(usize::to_string));
+        for corners in boundaries.iter() {
+            symbol.push('*');
+            symbol.extend(corners.iter().map(usize::to_string));
+        }
+        symbol.push_str(&"x".repeat(crosscaps.max(0) as usize));
+        symbol
+    }
+
+    // polygons returns a regular polygon for each distinct tile degree in order of first appearance
+    pub fn polygons(&self) -> Vec<NotationPolygon> {
+        let mut polygons: Vec<NotationPolygon> = vec![];
+        for sides in self.tile_degrees.iter() {
+            let polygon = NotationPolygon::Regular { sides: *sides };
+            if !polygons.contains(&polygon) {
+                polygons.push(polygon);
+            }
+        }
+        polygons
+    }
+
+    // tiles returns the tile of each of the symbol's polygons, in order of polygon index
+    pub fn tiles(&self) -> Vec<Tile> {
+        self.polygons().iter().map(NotationPolygon::tile).collect()
+    }
+
+    // to_vertex_posts derives an atlas of the symbol's tiling with regular polygons with unit sides for its tiles,
+    // with one vertex per vertex of the tiling's unit cell
+    pub fn to_vertex_posts(&self) -> Result<Vec<FullAtlasVertexPost>, NotationError> {
+        let curvature = self.curvature();
+        if curvature.abs() > POINT_MARGIN {
+            return Err(NotationError::NonEuclidean { curvature })
+        }
+        for (vertex_index, orbit) in self.orbits(&[1, 2]).iter().enumerate() {
+            let mut d = orbit[0];
+            let mut angle = 0.;
+            for _ in 0..self.vertex_degrees[d] {
+                angle += interior_angle(self.tile_degrees[d]);
+                d = self.operations[2][self.operations[1][d]];
+            }
+            if (angle - TAU).abs() > POINT_MARGIN {
+                return Err(NotationError::AngleSumMismatch { vertex_index, degrees: angle * 360. / TAU })
+            }
+        }
+
+        let mut radius = 8.;
+        loop {
+            let (tiles, translations) = self.develop(radius)?;
+            match translations {
+                Some(translations) if periodic_radius(translations) <= radius => return periodic_vertex_posts(&self.polygons(), &tiles, translations),
+                Some(translations) => radius = periodic_radius(translations),
+                None if radius < MAX_DEVELOP_RADIUS => radius *= 2.,
+                None => return Err(NotationError::NoTiling),
+            }
+        }
+    }
+
+    // to_full_atlas_post builds an atlas of this symbol's tiling, where polygon_ids are the ids of
+    // this symbol's polygons, in order of polygon index
+    pub fn to_full_atlas_post(&self, tiling: FullSubTilingPost, polygon_ids: Vec<i32>) -> Result<FullAtlasPost, NotationError> {
+        let polygons = self.polygons();
+        if polygon_ids.len() != polygons.len() {
+            return Err(NotationError::PolygonIdCount { expected: polygons.len(), received: polygon_ids.len() })
+        }
+        Ok(FullAtlasPost {
+            tiling,
+            polygon_ids,
+            vertices: self.to_vertex_posts()?,
+            owner_id: None,
+        })
+    }
+
+    // develop lays the chambers out in the plane as flags of a vertex, an edge's angle from it and the side of the edge
+    // its tile is on, until no more are found within radius of the origin. It returns the tiles found along with the
+    // shortest two independent translations between flags of chamber 0, if there are any.
+    fn develop(&self, radius: f64) -> Result<Development, NotationError> {
+        // flags are keyed by a point just off their edge on their tile's side
+        let key = |point: &Point, angle: f64, orientation: f64| {
+            &(point + &direction(angle).mul(0.25)) + &direction(angle + PI / 2.).mul(0.05 * orientation)
+        };
+
+        let mut flags: PointMap<usize> = PointMap::new();
+        let mut centroids: PointMap<()> = PointMap::new();
+        let mut tiles: Vec<Tile> = vec![];
+        let mut translations: Vec<Point> = vec![];
+        let mut queue: VecDeque<(Point, f64, f64, usize)> = VecDeque::new(); // (vertex, angle, orientation, chamber)
+        flags.insert(key(&ORIGIN, 0., 1.), 0);
+        queue.push_back((ORIGIN, 0., 1., 0));
+
+        while let Some((point, angle, orientation, d)) = queue.pop_front() {
+            let sides = self.tile_degrees[d];
+            let tile = regular_tile(&point, angle, orientation, sides);
+            if centroids.insert(tile.centroid, ()) {
+                tiles.push(tile);
+                if tiles.len() > MAX_GENERATED {
+                    return Err(NotationError::NoTiling)
+                }
+            }
+            if d == 0 && orientation > 0. && angle_distance(angle, 0.) < POINT_MARGIN && point.norm() > POINT_MARGIN {
+                translations.push(point);
+            }
+
+            let neighbors = vec![
+                (&point + &direction(angle), angle + PI, -orientation, self.operations[0][d]),
+                (point, angle + orientation * interior_angle(sides), -orientation, self.operations[1][d]),
+                (point, angle, -orientation, self.operations[2][d]),
+            ];
+            for (point, angle, orientation, chamber) in neighbors {
+                let angle = normalize_angle(angle);
+                if point.norm() > radius {
+                    continue
+                }
+                let flag = key(&point, angle, orientation);
+                match flags.get(&flag) {
+                    Some(other) if *other != chamber => return Err(NotationError::NoTiling),
+                    Some(_) => {},
+                    None => {
+                        flags.insert(flag, chamber);
+                        queue.push_back((point, angle, orientation, chamber));
+                    },
+                }
+            }
+        }
+
+        translations.sort_by(|a, b| a.norm().partial_cmp(&b.norm()).unwrap());
+        let translations = translations.first().and_then(|translation0| {
+            translations
+                .iter()
+                .find(|translation1| cross(translation0, translation1).abs() > POINT_MARGIN)
+                .map(|translation1| (*translation0, *translation1))
+        });
+        Ok((tiles, translations))
+    }
+
+    // orbits partitions the chambers into the orbits of operations, each in the order its chambers are reached
+    // from its least chamber
+    fn orbits(&self, operations: &[usize]) -> Vec<Vec<usize>> {
+        let mut reached = vec![false; self.size()];
+        let mut orbits: Vec<Vec<usize>> = vec![];
+        for start in 0..self.size() {
+            if reached[start] {
+                continue
+            }
+            reached[start] = true;
+            let mut orbit = vec![start];
+            let mut index = 0;
+            while index < orbit.len() {
+                for i in operations.iter() {
+                    let image = self.operations[*i][orbit[index]];
+                    if !reached[image] {
+                        reached[image] = true;
+                        orbit.push(image);
+                    }
+                }
+                index += 1;
+            }
+            orbits.push(orbit);
+        }
+        orbits
+    }
+}
+
+impl std::fmt::Display for DelaneySymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let operations = self.operations
+            .iter()
+            .map(|images| {
+                images
+                    .iter()
+                    .enumerate()
+                    .filter(|(d, image)| *image >= d)
+                    .map(|(_, image)| (image + 1).to_string())
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        let degrees = [0, 1]
+            .iter()
+            .map(|i| {
+                self.orbits(&[*i, i + 1])
+                    .iter()
+                    .map(|orbit| self.degree(*i, i + 1, orbit[0]).to_string())
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        write!(f, "<1.1:{}:{}:{}>", self.size(), operations, degrees)
+    }
+}
+
+// canonical_key identifies an atlas' tiling up to similarity: it's the same for any two atlases of the tiling whatever
+// the order of their polygons, vertices and edges and whichever of the tiling's mirror images they describe. It's the
+// atlas' canonical symbol with each chamber labelled by its tile's angle at its vertex and its edge's length.
+pub fn canonical_key(atlas: &Atlas) -> Result<String, NotationError> {
+    let (symbol, labels) = labelled_symbol(atlas)?;
+    let (symbol, labels) = symbol.labelled_canonical(&labels);
+    Ok(format!(
+        "{} {}",
+        symbol,
+        labels.iter().map(|(angle, length)| format!("{}/{}", angle, length)).collect::<Vec<String>>().join(" "),
+    ))
+}
+
+// uniformity returns k for an atlas of a k-uniform tiling, i.e. the number of orbits of its vertices under its symmetries
+pub fn uniformity(atlas: &Atlas) -> Result<usize, NotationError> {
+    let (symbol, labels) = labelled_symbol(atlas)?;
+    Ok(symbol.labelled_minimal(&labels).0.orbits(&[1, 2]).len())
+}
+
+// labelled_symbol returns an atlas' symbol along with the angle of each chamber's tile at its vertex and its edge's length
+pub(crate) fn labelled_symbol(atlas: &Atlas) -> Result<(DelaneySymbol, Vec<(i64, i64)>), NotationError> {
+    let symbol = DelaneySymbol::from_atlas(atlas)?;
+    let round = |value: f64| (value * KEY_PRECISION).round() as i64;
+    let labels = atlas.proto_vertex_stars
+        .iter()
+        .flat_map(|star| star.tiles.iter().flat_map(|tile| vec![
+            // the star's tiles have their point at the vertex first, followed by the end of the tile's first edge
+            (round(tile.angle(0).to_degrees()), round(tile.points[1].norm())),
+            (round(tile.angle(0).to_degrees()), round(tile.points[tile.size() - 1].norm())),
+        ]))
+        .collect::<Vec<(i64, i64)>>();
+    Ok((symbol, labels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GomJauHogg, VertexConfiguration};
+    use models::FullAtlasEdgePost;
+    use tile::regular_polygon;
+
+    fn symbol(notation: &str) -> DelaneySymbol {
+        let configuration = VertexConfiguration::parse(notation).unwrap();
+        let vertices = configuration.to_vertex_posts().expect(notation);
+        DelaneySymbol::from_vertex_posts(&configuration.tiles(), &vertices).expect(notation)
+    }
+
+    fn key(notation: &str) -> String {
+        let configuration = VertexConfiguration::parse(notation).unwrap();
+        canonical_key(&Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().unwrap()).unwrap()).expect(notation)
+    }
+
+    #[test]
+    fn test_delaney_symbol_parse() {
+        let notation = "<1.1:10:2 4 7 8 10,2 5 6 8 10,3 4 7 9 10:3 3 6,5>";
+        let symbol = DelaneySymbol::parse(notation).unwrap();
+        assert_eq!(10, symbol.size());
+        assert_eq!(vec![3, 3, 3, 3, 3, 3, 3, 3, 6, 6], symbol.tile_degrees);
+        assert_eq!(notation, symbol.to_string());
+        assert_eq!(Ok(symbol), DelaneySymbol::parse("<1.1:10 2:2 4 7 8 10,2 5 6 8 10,3 4 7 9 10:3 3 6,5>"));
+
+        assert_eq!(Err(NotationError::InvalidSymbol { symbol: String::from("<1.1:1:1,1:4,4>") }), DelaneySymbol::parse("<1.1:1:1,1:4,4>"));
+        assert_eq!(Err(NotationError::InvalidSymbol { symbol: String::from("<1.1:1:1,1,1:4>") }), DelaneySymbol::parse("<1.1:1:1,1,1:4>"));
+        assert_eq!(Err(NotationError::InvalidOperation { operation: 0, chamber: 0 }), DelaneySymbol::parse("<1.1:2:3,1 2,2:3 6,4>"));
+        assert_eq!(Err(NotationError::DisconnectedSymbol), DelaneySymbol::parse("<1.1:2:1 2,1 2,1 2:4 4,4 4>"));
+        assert_eq!(Err(NotationError::InvalidDegree { chamber: 0 }), DelaneySymbol::parse("<1.1:2:2,2,1 2:3,3>"));
+    }
+
+    #[test]
+    fn test_delaney_symbol_from_vertex_posts() {
+        for (notation, canonical, orbifold) in [
+            ("3.3.3.3.3.3", "<1.1:1:1,1,1:3,6>", "*632"),
+            ("4.4.4.4", "<1.1:1:1,1,1:4,4>", "*442"),
+            ("6.6.6", "<1.1:1:1,1,1:6,3>", "*632"),
+            ("3.3.3.3.6", "<1.1:10:2 4 7 8 10,2 5 6 8 10,3 4 7 9 10:3 3 6,5>", "632"),
+            ("3.3.3.4.4", "<1.1:5:1 2 3 5,2 4 5,1 3 5:4 3,5>", "2*22"),
+            ("3.3.4.3.4", "<1.1:5:1 3 5,2 3 5,1 4 5:3 4,5>", "4*2"),
+            ("3.4.6.4", "<1.1:4:1 2 3 4,1 3 4,2 4:3 4 6,4>", "*632"),
+            ("3.6.3.6", "<1.1:2:1 2,1 2,2:3 6,4>", "*632"),
+            ("3.12.12", "<1.1:3:1 2 3,1 3,2 3:3 12,3>", "*632"),
+            ("4.6.12", "<1.1:6:1 2 3 4 5 6,2 5 6,3 4 6:4 6 12,3>", "*632"),
+            ("4.8.8", "<1.1:3:1 2 3,1 3,2 3:4 8,3>", "*442"),
+            ("[3^3.4^2; 4^4]", "<1.1:9:1 2 3 5 6 7 9,2 4 6 8 9,1 3 4 5 7 9:4 4 3,4 5>", "2*22"),
+            ("[3.4^2.6; 3.6.3.6]", "<1.1:16:1 4 3 6 8 11 10 12 13 16 15,2 5 4 7 10 9 13 14 16,3 5 6 9 11 12 15 16:3 6 3 4,4 4 4>", "22*"),
+        ].iter() {
+            let symbol = symbol(notation).canonical();
+            assert_eq!(*canonical, symbol.to_string(), "{}", notation);
+            assert_eq!(*orbifold, symbol.orbifold(), "{}", notation);
+        }
+
+        // a tiling's canonical symbol doesn't depend on the atlas it's derived from
+        let vertices = GomJauHogg::parse("6-3-3,3-3,3,6/r60/r(h43)").unwrap().to_vertex_posts().unwrap();
+        assert_eq!(symbol("3.3.3.3.6").canonical(), DelaneySymbol::from_vertex_posts(&[regular_polygon(1., 6), regular_polygon(1., 3)], &vertices).unwrap().canonical());
+        assert_ne!(symbol("3.3.3.3.6").canonical(), symbol("3.3.3.3.3.3").canonical());
+    }
+
+    #[test]
+    fn test_delaney_symbol_to_vertex_posts() {
+        for notation in ["3.3.3.3.6", "3.3.4.3.4", "4.6.12", "[3^6; 3^2.4.3.4]", "[3.4.6.4; 4.6.12]", "[3.4^2.6; 3.6.3.6]"].iter() {
+            let symbol = symbol(notation).canonical();
+            let vertices = symbol.to_vertex_posts().expect(notation);
+            let validation = validate(&symbol.tiles(), &vertices);
+            assert!(validation.valid, "{}: {}", notation, validation);
+            assert_eq!(symbol, DelaneySymbol::from_vertex_posts(&symbol.tiles(), &vertices).unwrap().canonical(), "{}", notation);
+        }
+
+        let result = DelaneySymbol::parse("<1.1:1:1,1,1:5,3>").unwrap().to_vertex_posts().map(|_| ());
+        assert!(matches!(result, Err(NotationError::NonEuclidean { curvature }) if (curvature - 1. / 30.).abs() < POINT_MARGIN), "{:?}", result);
+    }
+
+    #[test]
+    fn test_canonical_key() {
+        assert_eq!("<1.1:1:1,1,1:4,4> 90000/1000", key("4.4.4.4"));
+
+        // GomJau-Hogg's atlas of 3.3.3.3.6 lists its polygons in another order and has more vertices
+        let notation = GomJauHogg::parse("6-3-3,3-3,3,6/r60/r(h43)").unwrap();
+        let vertices = notation.to_vertex_posts().unwrap();
+        assert!(vertices.len() > 1);
+        assert_eq!(key("3.3.3.3.6"), canonical_key(&Atlas::from_vertex_posts(&notation.tiles(), &vertices).unwrap()).unwrap());
+
+        assert_ne!(key("3.3.4.3.4"), key("3.3.3.4.4"));
+        assert_ne!(key("[3^6; 3^2.4.3.4]"), key("3.3.4.3.4"));
+
+        // tiles with the same combinatorics but another shape give another key
+        let rectangle = Tile::new(vec![Point(0., 0.), Point(2., 0.), Point(2., 1.), Point(0., 1.)]);
+        let vertices = vec![FullAtlasVertexPost {
+            edges: (0..4)
+                .map(|j| FullAtlasEdgePost { polygon_index: 0, point_index: j, neighbor_index: 0, neighbor_edge_index: (j + 2) % 4, parity: false })
+                .collect(),
+        }];
+        assert!(validate(&[rectangle.clone()], &vertices).valid);
+        assert_eq!("<1.1:2:1 2,2,1 2:4,4> 90000/1000 90000/2000", canonical_key(&Atlas::from_vertex_posts(&[rectangle], &vertices).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_uniformity() {
+        for (notation, expected) in [("4.4.4.4", 1), ("3.4.6.4", 1), ("[3^6; 3^2.4.3.4]", 2)].iter() {
+            let configuration = VertexConfiguration::parse(notation).unwrap();
+            let atlas = Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().unwrap()).unwrap();
+            assert_eq!(Ok(*expected), uniformity(&atlas), "{}", notation);
+        }
+    }
+}
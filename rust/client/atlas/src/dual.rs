@@ -0,0 +1,223 @@
+use crate::{unit_cell, Atlas, NotationError};
+use crate::periodic::{cross, periodic_corner_vertex_posts, periodic_radius, POINT_MARGIN};
+use crate::symmetry::PlacedStar;
+use geometry::{Euclid, Point, Transformable};
+use models::{FullAtlasPost, FullAtlasVertexPost, FullSubTilingPost};
+use tile::Tile;
+
+// Dual is an atlas of the dual of a tiling, e.g. of a Laves tiling for an Archimedean one. The dual has a vertex at the
+// centroid of each of the tiling's tiles and a tile around each of its vertices, whose points are the centroids of the
+// tiles around the vertex.
+#[derive(Debug)]
+pub struct Dual {
+    // polygons are the dual's distinct tiles up to rotation and translation, in order of first appearance around the
+    // vertices of the tiling's unit cell, in the order of UnitCell::vertex_stars
+    pub polygons: Vec<Tile>,
+    pub vertices: Vec<FullAtlasVertexPost>,
+}
+
+impl Dual {
+    // into_full_atlas_post builds an atlas of the dual, where polygon_ids are the ids of the dual's polygons, in order
+    pub fn into_full_atlas_post(self, tiling: FullSubTilingPost, polygon_ids: Vec<i32>) -> Result<FullAtlasPost, NotationError> {
+        if polygon_ids.len() != self.polygons.len() {
+            return Err(NotationError::PolygonIdCount { expected: self.polygons.len(), received: polygon_ids.len() })
+        }
+        Ok(FullAtlasPost {
+            tiling,
+            polygon_ids,
+            vertices: self.vertices,
+            owner_id: None,
+        })
+    }
+}
+
+// dual derives an atlas of the dual of an atlas' tiling. The dual's tiles are translates of the tiles around the vertices
+// of the tiling's unit cell, as the dual has the same translations as the tiling. When the centroids around a vertex don't
+// bound a simple polygon, as can happen around the dents of star polygons, NonPolygonalDual is returned, and when the dual's
+// tiles overlap one another Overlap is.
+pub fn dual(atlas: &Atlas) -> Result<Dual, NotationError> {
+    let cell = unit_cell(atlas)?;
+    let faces = cell.vertex_stars
+        .iter()
+        .enumerate()
+        .map(|(vertex_index, vertex_star)| face(atlas, vertex_star).ok_or(NotationError::NonPolygonalDual { vertex_index }))
+        .collect::<Result<Vec<Tile>, NotationError>>()?;
+
+    let radius = periodic_radius(cell.translations) + faces
+        .iter()
+        .flat_map(|polygon| polygon.points.iter())
+        .map(Point::norm)
+        .fold(0., f64::max);
+    let indices = [Point(-radius, -radius), Point(radius, -radius), Point(radius, radius), Point(-radius, radius)]
+        .iter()
+        .map(|corner| cell.index(corner))
+        .collect::<Vec<(i64, i64)>>();
+    let (i_min, i_max) = indices.iter().fold((i64::MAX, i64::MIN), |(min, max), (i, _)| (min.min(*i), max.max(*i)));
+    let (j_min, j_max) = indices.iter().fold((i64::MAX, i64::MIN), |(min, max), (_, j)| (min.min(*j), max.max(*j)));
+
+    // tiles[k] is a translate of faces[k % faces.len()], so that they share the indices of their points
+    let mut tiles: Vec<Tile> = vec![];
+    for i in i_min..=i_max {
+        for j in j_min..=j_max {
+            let translate = Euclid::Translate(cell.translation(i, j).values());
+            tiles.extend(faces.iter().map(|face| face.transform(&translate)));
+        }
+    }
+
+    // each face is matched to the first congruent one, whose point offset from it is kept
+    let mut polygons: Vec<Tile> = vec![];
+    let mut face_corners: Vec<(usize, usize)> = Vec::with_capacity(faces.len());
+    for face in faces.iter() {
+        let found = polygons
+            .iter()
+            .enumerate()
+            .find_map(|(polygon_index, polygon)| congruent_offset(polygon, face).map(|offset| (polygon_index, offset)));
+        face_corners.push(found.unwrap_or_else(|| {
+            polygons.push(face.clone());
+            (polygons.len() - 1, 0)
+        }));
+    }
+
+    let vertices = periodic_corner_vertex_posts(&tiles, cell.translations, |tile_index, point_index| {
+        let (polygon_index, offset) = face_corners[tile_index % faces.len()];
+        let size = polygons[polygon_index].size();
+        Ok((polygon_index, (point_index + size - offset) % size))
+    })?;
+    Ok(Dual { polygons, vertices })
+}
+
+// congruent_offset returns the offset by which tile's points follow polygon's if tile is a rotation and translation of
+// polygon, i.e. tile's point (i + offset) is the image of polygon's point i
+fn congruent_offset(polygon: &Tile, tile: &Tile) -> Option<usize> {
+    let size = polygon.size();
+    if tile.size() != size {
+        return None
+    }
+    let side = |tile: &Tile, i: usize| (&tile.points[(i + 1) % size] - &tile.points[i % size]).norm();
+    (0..size).find(|offset| (0..size).all(|i| {
+        (side(polygon, i) - side(tile, i + offset)).abs() < POINT_MARGIN
+            && (polygon.angle(i) - tile.angle((i + offset) % size)).abs() < POINT_MARGIN
+    }))
+}
+
+// face returns the dual's tile around a placed proto vertex star, if the centroids of the star's tiles bound a simple polygon
+fn face(atlas: &Atlas, (i, placement): &PlacedStar) -> Option<Tile> {
+    let mut centroids = atlas.proto_vertex_stars[*i].tiles
+        .iter()
+        .map(|tile| tile.centroid.transform(placement))
+        .collect::<Vec<Point>>();
+    if placement.is_flip() {
+        centroids.reverse();
+    }
+    dual_tile(centroids)
+}
+
+// dual_tile returns the dual's tile around a vertex given the centroids of the tiles around it in anticlockwise order, if
+// they bound a simple polygon
+pub fn dual_tile(centroids: Vec<Point>) -> Option<Tile> {
+    if centroids.len() < 3 || area(&centroids) < POINT_MARGIN || is_self_intersecting(&centroids) {
+        return None
+    }
+    Some(Tile::new(centroids))
+}
+
+// area returns the signed area of the polygon with the given points, which is positive when they're anticlockwise
+fn area(points: &[Point]) -> f64 {
+    points.iter().zip(points.iter().cycle().skip(1)).map(|(a, b)| cross(a, b)).sum::<f64>() / 2.
+}
+
+// is_self_intersecting returns whether any two edges of the polygon with the given points meet, other than adjacent edges
+// at their shared point
+fn is_self_intersecting(points: &[Point]) -> bool {
+    let size = points.len();
+    let edges = (0..size).map(|k| (&points[k], &points[(k + 1) % size])).collect::<Vec<(&Point, &Point)>>();
+    let side = |(a, b): (&Point, &Point), point: &Point| cross(&(b - a), &(point - a));
+    for k in 0..size {
+        for l in k + 1..size {
+            if l == k + 1 || (k == 0 && l == size - 1) {
+                // adjacent edges only meet elsewhere when they fold back over one another
+                let (shared, a, b) = if l == k + 1 { (edges[l].0, edges[k].0, edges[l].1) } else { (edges[k].0, edges[k].1, edges[l].0) };
+                if cross(&(a - shared), &(b - shared)).abs() < POINT_MARGIN && (a - shared).dot(&(b - shared)) > 0. {
+                    return true
+                }
+                continue
+            }
+            let (edge0, edge1) = (edges[k], edges[l]);
+            if side(edge0, edge1.0) * side(edge0, edge1.1) <= 0. && side(edge1, edge0.0) * side(edge1, edge0.1) <= 0. {
+                return true
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{validate, DelaneySymbol, VertexConfiguration};
+
+    fn atlas(notation: &str) -> Atlas {
+        let configuration = VertexConfiguration::parse(notation).unwrap();
+        Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().expect(notation)).unwrap()
+    }
+
+    #[test]
+    fn test_dual() {
+        for (notation, polygon_sizes, vertex_degrees) in [
+            ("4^4", vec![4], vec![4]),
+            ("3^6", vec![6], vec![3, 3]),
+            ("6^3", vec![3], vec![6]),
+            ("3.4.6.4", vec![4], vec![3, 3, 4, 4, 4, 6]),
+            ("4.8^2", vec![3], vec![4, 8]),
+            ("3^4.6", vec![5], vec![3, 3, 3, 3, 3, 3, 3, 3, 6]),
+        ].iter() {
+            let tiling = atlas(notation);
+            let found = dual(&tiling).expect(notation);
+            assert_eq!(*polygon_sizes, found.polygons.iter().map(Tile::size).collect::<Vec<usize>>(), "{}", notation);
+            let mut degrees = found.vertices.iter().map(|vertex| vertex.edges.len()).collect::<Vec<usize>>();
+            degrees.sort_unstable();
+            assert_eq!(*vertex_degrees, degrees, "{}", notation);
+            assert!(validate(&found.polygons, &found.vertices).valid, "{}", notation);
+
+            // the dual atlas describes the tiling whose symbol is the dual of the tiling's symbol
+            let dual_atlas = Atlas::from_vertex_posts(&found.polygons, &found.vertices).unwrap();
+            assert_eq!(
+                DelaneySymbol::from_atlas(&tiling).unwrap().dual().canonical(),
+                DelaneySymbol::from_atlas(&dual_atlas).unwrap().canonical(),
+                "{}",
+                notation,
+            );
+        }
+
+        // the six kites around the vertices of the unit cell of 3.4.6.4 are all one polygon, placed by its different corners
+        let found = dual(&atlas("3.4.6.4")).unwrap();
+        assert_eq!(1, found.polygons.len());
+        let mut corners = found.vertices.iter().flat_map(|vertex| vertex.edges.iter().map(|edge| edge.point_index)).collect::<Vec<usize>>();
+        corners.sort_unstable();
+        corners.dedup();
+        assert_eq!(vec![0, 1, 2, 3], corners);
+
+        // the dual of the square tiling is itself, with its vertices at the squares' centers
+        let found = dual(&atlas("4^4")).unwrap();
+        assert!(found.polygons[0].points.iter().all(|point| (point.0.abs() - 0.5).abs() < POINT_MARGIN && (point.1.abs() - 0.5).abs() < POINT_MARGIN));
+
+        let polygon_ids = vec![7];
+        let tiling = FullSubTilingPost { title: String::from("4^4*"), label_ids: None };
+        assert_eq!(vec![7], dual(&atlas("4^4")).unwrap().into_full_atlas_post(tiling, polygon_ids).unwrap().polygon_ids);
+    }
+
+    #[test]
+    fn test_dual_not_polygonal() {
+        // the centroids of the tiles around a vertex at a star's dent don't bound a simple polygon
+        let tiling = atlas("[4.6apio6.6aapio2.6apio6; 4.6aapio6; 6apio2.6aapio6]");
+        assert!(matches!(dual(&tiling), Err(NotationError::NonPolygonalDual { .. })));
+
+        // two centroids are too few to bound a polygon, and clockwise ones bound it inside out
+        assert!(dual_tile(vec![Point(0., 0.), Point(1., 0.)]).is_none());
+        assert!(dual_tile(vec![Point(0., 0.), Point(0., 1.), Point(1., 0.)]).is_none());
+        assert!(dual_tile(vec![Point(0., 0.), Point(1., 0.), Point(0., 1.)]).is_some());
+        assert!(is_self_intersecting(&[Point(0., 0.), Point(1., 1.), Point(1., 0.), Point(0., 1.)]));
+        assert!(!is_self_intersecting(&[Point(0., 0.), Point(1., 0.), Point(1., 1.), Point(0., 1.)]));
+        assert!(is_self_intersecting(&[Point(0., 0.), Point(2., 0.), Point(1., 0.), Point(0., 1.)]));
+    }
+}
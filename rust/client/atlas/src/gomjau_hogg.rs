@@ -0,0 +1,909 @@
+use crate::{validate, NotationError, NotationPolygon};
+use crate::periodic::{
+    angle_distance, cmp_points, cross, direction, interior_angle, midpoint, normalize_angle, periodic_radius, periodic_vertex_posts,
+    regular_tile, tile_edges, PointMap, MAX_GENERATED, POINT_MARGIN,
+};
+use geometry::{reduce_transforms, Affine, Euclid, Point, Transform, Transformable, IDENTITY_AFFINE, ORIGIN};
+use models::{FullAtlas, FullAtlasEdgePost, FullAtlasPost, FullAtlasVertexPost, FullPolygon, FullSubTilingPost};
+use std::{cmp::Ordering, collections::{HashMap, VecDeque}, f64::consts::{PI, TAU}};
+use tile::{regular_polygon, Tile};
+
+// radius of the patch developed from an atlas when exporting it
+const EXPORT_RADIUS: f64 = 24.;
+// bounds on the seeds and transforms tried when exporting an atlas
+const MAX_EXPORT_STAGES: usize = 5;
+const MAX_EXPORT_POINT_DISTANCES: usize = 6;
+const MAX_EXPORT_POINT_TRANSFORMS: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GomJauHoggPoint {
+    Centroid,
+    Midpoint,
+    Vertex,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GomJauHoggAnchor {
+    // degrees about or through the origin
+    Angle(f64),
+    // the index-th closest point of a kind to the origin, starting from 1
+    Point(GomJauHoggPoint, usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GomJauHoggTransform {
+    Mirror(GomJauHoggAnchor),
+    Rotate(GomJauHoggAnchor),
+}
+
+// GomJauHogg is a tiling in GomJau-Hogg (Antwerp v3) notation, e.g. 3-4-3,3/m30/r(c3).
+//
+// The part before the first "/" grows a seed of regular polygons in stages separated by "-". The first stage is a single
+// polygon centered on the origin with an edge horizontally below it, as tile::regular_polygon's first edge is. Each later
+// stage places its polygons, separated by ",", one to an edge on the free edges of the previous stage's polygons in
+// anticlockwise order of the edges' midpoints from the positive x-axis, where a 0 leaves an edge free. Edges covered by
+// a polygon placed earlier in the same stage are skipped. The seed only needs to list the polygons which the transforms
+// don't place, so that it's usually just a wedge of the tiling around the origin.
+//
+// Each following part is a transform which, together with those before it, is applied to the seed until the plane is
+// covered: m<θ> mirrors across the y-axis, which the seed's first polygon is always symmetric about, and across the
+// lines through the origin every θ degrees from it which the first polygon is also symmetric about, r<θ> rotates about
+// the origin by θ degrees, r(<point>) rotates by 180° about point and m(<point>) mirrors across the line through point
+// perpendicular to the origin's direction. A point is c (centroid), h (edge midpoint) or v (vertex) followed by n, the
+// nth closest such point to the origin in the patch grown by the transforms before it, not counting the origin itself.
+// Points as close as each other are counted anticlockwise from the positive x-axis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GomJauHogg {
+    pub stages: Vec<Vec<usize>>,
+    pub transforms: Vec<GomJauHoggTransform>,
+}
+
+impl GomJauHogg {
+    pub fn parse(notation: &str) -> Result<GomJauHogg, NotationError> {
+        let mut parts = notation.trim().split('/');
+
+        let seed = parts.next().unwrap_or("");
+        let mut stages: Vec<Vec<usize>> = vec![];
+        for stage in seed.split('-') {
+            let invalid = || NotationError::InvalidStage { stage: stage.to_string() };
+            let polygons = stage
+                .split(',')
+                .map(|sides| match sides.trim().parse::<usize>() {
+                    Ok(sides) if sides == 0 || sides >= 3 => Ok(sides),
+                    _ => Err(invalid()),
+                })
+                .collect::<Result<Vec<usize>, NotationError>>()?;
+            if stages.is_empty() && (polygons.len() != 1 || polygons[0] == 0) {
+                return Err(invalid())
+            }
+            stages.push(polygons);
+        }
+
+        let transforms = parts.map(parse_transform).collect::<Result<Vec<GomJauHoggTransform>, NotationError>>()?;
+        if transforms.is_empty() {
+            return Err(NotationError::NoTransforms)
+        }
+
+        Ok(GomJauHogg { stages, transforms })
+    }
+
+    // polygons returns the distinct polygons of the notation in order of first appearance
+    pub fn polygons(&self) -> Vec<NotationPolygon> {
+        let mut polygons: Vec<NotationPolygon> = vec![];
+        for sides in self.stages.iter().flatten() {
+            let polygon = NotationPolygon::Regular { sides: *sides };
+            if *sides != 0 && !polygons.contains(&polygon) {
+                polygons.push(polygon);
+            }
+        }
+        polygons
+    }
+
+    // tiles returns the tile of each of the notation's polygons, in order of polygon index
+    pub fn tiles(&self) -> Vec<Tile> {
+        self.polygons().iter().map(NotationPolygon::tile).collect()
+    }
+
+    // seed grows the notation's stages of polygons
+    pub fn seed(&self) -> Result<Vec<Tile>, NotationError> {
+        Ok(Seed::grow(&self.stages)?.tiles)
+    }
+
+    // to_vertex_posts derives an atlas of the notation's tiling, with one vertex per vertex of the tiling's unit cell
+    pub fn to_vertex_posts(&self) -> Result<Vec<FullAtlasVertexPost>, NotationError> {
+        let seed = self.seed()?;
+        let affines = self.affines(&seed)?;
+        let translations = translations(&affines, 4. * (radius(&seed) + 2.))?;
+        let tiles = expand(&seed, &affines, periodic_radius(translations))?;
+        periodic_vertex_posts(&self.polygons(), &tiles, translations)
+    }
+
+    // to_full_atlas_post builds an atlas of this notation's tiling, where polygon_ids are the ids of
+    // this notation's polygons, in order of polygon index
+    pub fn to_full_atlas_post(&self, tiling: FullSubTilingPost, polygon_ids: Vec<i32>) -> Result<FullAtlasPost, NotationError> {
+        let polygons = self.polygons();
+        if polygon_ids.len() != polygons.len() {
+            return Err(NotationError::PolygonIdCount { expected: polygons.len(), received: polygon_ids.len() })
+        }
+        Ok(FullAtlasPost {
+            tiling,
+            polygon_ids,
+            vertices: self.to_vertex_posts()?,
+            owner_id: None,
+        })
+    }
+
+    // from_full_atlas finds a notation for an atlas' tiling, see from_vertex_posts
+    pub fn from_full_atlas(atlas: &FullAtlas) -> Result<GomJauHogg, NotationError> {
+        let polygons = atlas.polygons.iter().map(FullPolygon::into_tile).collect::<Vec<Tile>>();
+        let vertices = atlas.vertices
+            .iter()
+            .map(|vertex| FullAtlasVertexPost {
+                edges: vertex.edges
+                    .iter()
+                    .map(|edge| FullAtlasEdgePost {
+                        polygon_index: edge.polygon_index,
+                        point_index: edge.point_index,
+                        neighbor_index: edge.neighbor_index,
+                        neighbor_edge_index: edge.neighbor_edge_index,
+                        parity: edge.parity,
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<FullAtlasVertexPost>>();
+        GomJauHogg::from_vertex_posts(&polygons, &vertices)
+    }
+
+    // from_vertex_posts finds a notation for the tiling of an atlas of regular polygons with unit sides. The seed is
+    // centered on the most symmetric polygon and grown a stage at a time, until the tiling's symmetries through that
+    // polygon along with a few of its symmetries about nearby points reproduce the tiling.
+    pub fn from_vertex_posts(polygons: &[Tile], vertices: &[FullAtlasVertexPost]) -> Result<GomJauHogg, NotationError> {
+        let validation = validate(polygons, vertices);
+        if !validation.valid {
+            return Err(NotationError::InvalidAtlas { issues: validation.issues })
+        }
+
+        let sides = polygons
+            .iter()
+            .enumerate()
+            .map(|(polygon_index, polygon)| if is_unit_regular(polygon) { Ok(polygon.size()) } else { Err(NotationError::UnsupportedPolygon { polygon_index }) })
+            .collect::<Result<Vec<usize>, NotationError>>()?;
+
+        let tiles = develop(&sides, vertices, EXPORT_RADIUS)?;
+        let tiling = Tiling::new(center(tiles)?);
+        let check_radius = EXPORT_RADIUS / 2. - 2.;
+
+        // symmetries of the tiling through the origin, which is the centroid of its central polygon
+        let central_sides = tiling.tiles[tiling.centroids.get(&ORIGIN).cloned().ok_or(NotationError::NoTiling)?].size();
+        let is_symmetry = |euclid: Euclid| tiling.is_symmetry(&euclid.as_affine(), check_radius);
+        let mut origin_transforms: Vec<Vec<GomJauHoggTransform>> = vec![];
+        let orders = (1..=central_sides).rev().filter(|order| central_sides % order == 0).collect::<Vec<usize>>();
+        if is_symmetry(Euclid::Flip(PI / 2.)) {
+            if let Some(order) = orders.iter().find(|order| is_symmetry(Euclid::Flip(PI / 2. + PI / **order as f64))) {
+                origin_transforms.push(vec![GomJauHoggTransform::Mirror(GomJauHoggAnchor::Angle(180. / *order as f64))]);
+            }
+        }
+        if let Some(order) = orders.iter().find(|order| **order > 1 && is_symmetry(Euclid::Rotate(TAU / **order as f64))) {
+            origin_transforms.push(vec![GomJauHoggTransform::Rotate(GomJauHoggAnchor::Angle(360. / *order as f64))]);
+        }
+        origin_transforms.push(vec![]);
+
+        for stage_count in 1..=MAX_EXPORT_STAGES {
+            // each choice of origin transforms leaves different polygons of a stage for the seed to place, though
+            // placing them all can leave more room for later stages
+            let mut notations: Vec<(GomJauHogg, Vec<Tile>)> = vec![];
+            for transforms in origin_transforms.iter() {
+                for trimmed in [true, false].iter() {
+                    let stages = match tiling.stages(stage_count, if *trimmed { transforms } else { &[] }) { None => continue, Some(stages) => stages };
+                    let notation = GomJauHogg { stages, transforms: transforms.clone() };
+                    if notations.iter().all(|(other, _)| *other != notation) {
+                        let seed = notation.seed()?;
+                        notations.push((notation, seed));
+                    }
+                }
+            }
+            if notations.is_empty() {
+                break
+            }
+            for point_transform_count in 1..=MAX_EXPORT_POINT_TRANSFORMS {
+                for (notation, seed) in notations.iter() {
+                    if let Some(notation) = tiling.find_point_transforms(seed, notation.clone(), point_transform_count, check_radius) {
+                        return Ok(notation)
+                    }
+                }
+            }
+        }
+
+        Err(NotationError::Unrepresentable)
+    }
+
+    // affines returns the isometries generated by the notation's transforms, along with their inverses
+    fn affines(&self, seed: &[Tile]) -> Result<Vec<Affine>, NotationError> {
+        let mut affines: Vec<Affine> = vec![];
+        for transform in self.transforms.iter() {
+            let anchor = match transform { GomJauHoggTransform::Mirror(anchor) | GomJauHoggTransform::Rotate(anchor) => anchor };
+            match (transform, anchor) {
+                (GomJauHoggTransform::Rotate(_), GomJauHoggAnchor::Angle(degrees)) => {
+                    affines.push(Euclid::Rotate(degrees.to_radians()).as_affine());
+                    affines.push(Euclid::Rotate(-degrees.to_radians()).as_affine());
+                },
+                (GomJauHoggTransform::Mirror(_), GomJauHoggAnchor::Angle(degrees)) => {
+                    affines.push(Euclid::Flip(PI / 2.).as_affine());
+                    affines.push(Euclid::Flip(PI / 2. + mirror_spacing(*degrees, seed[0].size())?.to_radians()).as_affine());
+                },
+                (_, GomJauHoggAnchor::Point(kind, index)) => {
+                    let patch = expand(seed, &affines, patch_radius(seed))?;
+                    let point = *nearest_points(&patch, *kind, patch_radius(seed) / 2.)
+                        .get(index - 1)
+                        .ok_or_else(|| NotationError::MissingPoint { point: fmt_point(*kind, *index) })?;
+                    affines.push(point_affine(transform, &point));
+                },
+            }
+        }
+        Ok(affines)
+    }
+}
+
+impl std::fmt::Display for GomJauHogg {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let stages = self.stages
+            .iter()
+            .map(|stage| stage.iter().map(usize::to_string).collect::<Vec<String>>().join(","))
+            .collect::<Vec<String>>()
+            .join("-");
+        write!(f, "{}", stages)?;
+        for transform in self.transforms.iter() {
+            let (prefix, anchor) = match transform {
+                GomJauHoggTransform::Mirror(anchor) => ("m", anchor),
+                GomJauHoggTransform::Rotate(anchor) => ("r", anchor),
+            };
+            match anchor {
+                GomJauHoggAnchor::Angle(degrees) => write!(f, "/{}{}", prefix, fmt_degrees(*degrees))?,
+                GomJauHoggAnchor::Point(kind, index) => write!(f, "/{}({})", prefix, fmt_point(*kind, *index))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// parse_transform parses a single transform of a GomJau-Hogg notation, e.g. m30, r60, r(c2) or m(h1)
+fn parse_transform(transform: &str) -> Result<GomJauHoggTransform, NotationError> {
+    let invalid = || NotationError::InvalidTransform { transform: transform.to_string() };
+    let transform = transform.trim();
+
+    let anchor = transform.get(1..).ok_or_else(invalid)?;
+    let anchor = match anchor.strip_prefix('(').and_then(|anchor| anchor.strip_suffix(')')) {
+        Some(point) => {
+            let kind = match point.chars().next() {
+                Some('c') => GomJauHoggPoint::Centroid,
+                Some('h') => GomJauHoggPoint::Midpoint,
+                Some('v') => GomJauHoggPoint::Vertex,
+                _ => return Err(invalid()),
+            };
+            match point[1..].parse::<usize>() {
+                Ok(index) if index > 0 => GomJauHoggAnchor::Point(kind, index),
+                _ => return Err(invalid()),
+            }
+        },
+        None => GomJauHoggAnchor::Angle(anchor.parse::<f64>().map_err(|_| invalid())?),
+    };
+
+    let (is_mirror, turn) = match transform.chars().next() {
+        Some('m') => (true, 180.),
+        Some('r') => (false, 360.),
+        _ => return Err(invalid()),
+    };
+    // transforms about the origin must eventually return to where they started
+    if let GomJauHoggAnchor::Angle(degrees) = anchor {
+        if degrees <= 0. || (turn / degrees - (turn / degrees).round()).abs() > POINT_MARGIN {
+            return Err(invalid())
+        }
+    }
+
+    Ok(if is_mirror { GomJauHoggTransform::Mirror(anchor) } else { GomJauHoggTransform::Rotate(anchor) })
+}
+
+fn fmt_point(kind: GomJauHoggPoint, index: usize) -> String {
+    let prefix = match kind {
+        GomJauHoggPoint::Centroid => "c",
+        GomJauHoggPoint::Midpoint => "h",
+        GomJauHoggPoint::Vertex => "v",
+    };
+    format!("{}{}", prefix, index)
+}
+
+fn fmt_degrees(degrees: f64) -> String {
+    let degrees = (degrees * 1_000_000.).round() / 1_000_000.;
+    format!("{}", if degrees == 0. { 0. } else { degrees })
+}
+
+// Seed grows polygons stage by stage onto the free edges of those placed in the previous stage
+struct Seed {
+    tiles: Vec<Tile>,
+    // number of tiles sharing each edge, by midpoint
+    edge_counts: PointMap<usize>,
+    previous: Vec<usize>,
+}
+
+impl Seed {
+    fn new(sides: usize) -> Seed {
+        let tile = regular_polygon(1., sides);
+        let tile = tile.transform(&Euclid::Translate(tile.centroid.neg().values()));
+        let mut seed = Seed { tiles: vec![], edge_counts: PointMap::new(), previous: vec![0] };
+        seed.insert(tile);
+        seed
+    }
+
+    // grow places stages of polygons
+    fn grow(stages: &[Vec<usize>]) -> Result<Seed, NotationError> {
+        let mut seed = Seed::new(stages[0][0]);
+        for stage in stages.iter().skip(1) {
+            let mut polygons = stage.iter();
+            let mut placed: Vec<usize> = vec![];
+            for edge in seed.free_edges() {
+                if !seed.is_free(&edge) {
+                    continue
+                }
+                match polygons.next() {
+                    None => break,
+                    Some(0) => {},
+                    Some(sides) => placed.push(seed.place(*sides, &edge)?),
+                }
+            }
+            if polygons.next().is_some() {
+                return Err(NotationError::InvalidStage { stage: stage.iter().map(usize::to_string).collect::<Vec<String>>().join(",") })
+            }
+            seed.previous = placed;
+        }
+        Ok(seed)
+    }
+
+    // free_edges lists the edges of the previous stage's tiles which aren't shared with another tile,
+    // in anticlockwise order of their midpoints from the positive x-axis
+    fn free_edges(&self) -> Vec<(Point, Point)> {
+        let mut edges = self.previous
+            .iter()
+            .flat_map(|index| tile_edges(&self.tiles[*index]))
+            .filter(|edge| self.is_free(edge))
+            .collect::<Vec<(Point, Point)>>();
+        edges.sort_by(|(a0, a1), (b0, b1)| cmp_angles(&midpoint(a0, a1), &midpoint(b0, b1)));
+        edges
+    }
+
+    fn is_free(&self, (a, b): &(Point, Point)) -> bool {
+        self.edge_counts.get(&midpoint(a, b)).map_or(true, |count| *count < 2)
+    }
+
+    // place adds a polygon with sides on the outside of edge, returning its index
+    fn place(&mut self, sides: usize, (a, b): &(Point, Point)) -> Result<usize, NotationError> {
+        let tile = regular_polygon(1., sides).transform(&reduce_transforms(vec![
+            &Euclid::Rotate(normalize_angle((a - b).1.atan2((a - b).0))),
+            &Euclid::Translate(b.values()),
+        ]));
+        if self.tiles.iter().any(|other| other.overlaps(&tile)) {
+            return Err(NotationError::Overlap)
+        }
+        Ok(self.insert(tile))
+    }
+
+    fn insert(&mut self, tile: Tile) -> usize {
+        for (a, b) in tile_edges(&tile) {
+            let midpoint = midpoint(&a, &b);
+            match self.edge_counts.get_mut(&midpoint) {
+                Some(count) => *count += 1,
+                None => { self.edge_counts.insert(midpoint, 1); },
+            }
+        }
+        self.tiles.push(tile);
+        self.tiles.len() - 1
+    }
+}
+
+// Tiling is a patch of tiles centered on the origin which notations are checked against when exporting
+struct Tiling {
+    tiles: Vec<Tile>,
+    centroids: PointMap<usize>,
+    // the tiles sharing each edge, by midpoint
+    edges: PointMap<Vec<usize>>,
+}
+
+impl Tiling {
+    fn new(tiles: Vec<Tile>) -> Tiling {
+        let mut centroids = PointMap::new();
+        let mut edges: PointMap<Vec<usize>> = PointMap::new();
+        for (index, tile) in tiles.iter().enumerate() {
+            centroids.insert(tile.centroid, index);
+            for (a, b) in tile_edges(tile) {
+                let midpoint = midpoint(&a, &b);
+                match edges.get_mut(&midpoint) {
+                    Some(indices) => indices.push(index),
+                    None => { edges.insert(midpoint, vec![index]); },
+                }
+            }
+        }
+        Tiling { tiles, centroids, edges }
+    }
+
+    // is_symmetry determines whether affine maps the tiles within radius of the origin onto tiles
+    fn is_symmetry(&self, affine: &Affine, radius: f64) -> bool {
+        self.is_symmetry_about(affine, &ORIGIN, radius)
+    }
+
+    // is_symmetry_about determines whether affine maps the tiles within radius of center onto tiles
+    fn is_symmetry_about(&self, affine: &Affine, center: &Point, radius: f64) -> bool {
+        self.tiles
+            .iter()
+            .filter(|tile| (&tile.centroid - center).norm() <= radius)
+            .all(|tile| {
+                let image = tile.centroid.transform(affine);
+                image.norm() > EXPORT_RADIUS - 2. || self.centroids.get(&image).map_or(false, |index| self.tiles[*index].size() == tile.size())
+            })
+    }
+
+    // is_covered_by determines whether notation's seed and transforms reproduce the tiles within radius of the origin
+    fn is_covered_by(&self, seed: &[Tile], notation: &GomJauHogg, radius: f64) -> bool {
+        let expanded = match notation.affines(seed).and_then(|affines| expand(seed, &affines, radius + 4.)) {
+            Ok(expanded) => expanded,
+            Err(_) => return false,
+        };
+        let mut centroids = PointMap::new();
+        for tile in expanded.iter() {
+            if tile.centroid.norm() <= radius && !self.centroids.get(&tile.centroid).map_or(false, |index| self.tiles[*index].size() == tile.size()) {
+                return false
+            }
+            centroids.insert(tile.centroid, ());
+        }
+        self.tiles.iter().filter(|tile| tile.centroid.norm() <= radius).all(|tile| centroids.get(&tile.centroid).is_some())
+    }
+
+    // find_point_transforms appends count transforms about points which are symmetries of the tiling to notation,
+    // returning the first such notation found which reproduces the tiling
+    fn find_point_transforms(&self, seed: &[Tile], notation: GomJauHogg, count: usize, radius: f64) -> Option<GomJauHogg> {
+        if count == 0 {
+            return if self.is_covered_by(seed, &notation, radius) { Some(notation) } else { None }
+        }
+        let patch = notation.affines(seed).and_then(|affines| expand(seed, &affines, patch_radius(seed))).ok()?;
+        for kind in [GomJauHoggPoint::Centroid, GomJauHoggPoint::Midpoint, GomJauHoggPoint::Vertex].iter() {
+            let points = nearest_points(&patch, *kind, patch_radius(seed) / 2.);
+            // only the first of the points as close as each other to the origin is tried, as the others are usually
+            // its images under the transforms about the origin
+            let firsts = points
+                .iter()
+                .enumerate()
+                .filter(|(index, point)| *index == 0 || point.norm() - points[index - 1].norm() > POINT_MARGIN)
+                .take(MAX_EXPORT_POINT_DISTANCES);
+            for (index, point) in firsts {
+                let anchor = GomJauHoggAnchor::Point(*kind, index + 1);
+                for transform in [GomJauHoggTransform::Rotate(anchor.clone()), GomJauHoggTransform::Mirror(anchor)].iter() {
+                    if notation.transforms.contains(transform) || !self.is_symmetry(&point_affine(transform, point), radius) {
+                        continue
+                    }
+                    let mut next = notation.clone();
+                    next.transforms.push(transform.clone());
+                    if let Some(next) = self.find_point_transforms(seed, next, count - 1, radius) {
+                        return Some(next)
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    // stages returns the stages of a seed of stage_count stages which matches the tiling. Each stage lists only as
+    // many of the polygons around the previous one as it takes for transforms, about the origin, to place the rest.
+    fn stages(&self, stage_count: usize, transforms: &[GomJauHoggTransform]) -> Option<Vec<Vec<usize>>> {
+        let central = self.tiles[*self.centroids.get(&ORIGIN)?].size();
+        let mut stages = vec![vec![central]];
+        let affines = GomJauHogg { stages: stages.clone(), transforms: transforms.to_vec() }.affines(&Seed::new(central).tiles).ok()?;
+        for _ in 1..stage_count {
+            let mut seed = Seed::grow(&stages).ok()?;
+            let placed_count = seed.tiles.len();
+            let mut stage: Vec<usize> = vec![];
+            for edge in seed.free_edges() {
+                if !seed.is_free(&edge) {
+                    continue
+                }
+                let (a, b) = &edge;
+                let outside = self.edges
+                    .get(&midpoint(a, b))?
+                    .iter()
+                    .find(|index| cross(&(b - a), &(&self.tiles[**index].centroid - a)) < 0.)?;
+                let sides = self.tiles[*outside].size();
+                stage.push(sides);
+                seed.place(sides, &edge).ok()?;
+            }
+            if stage.is_empty() {
+                return None
+            }
+
+            let placed = &seed.tiles[placed_count..];
+            let reach = radius(placed) + 1.;
+            let length = (1..stage.len())
+                .find(|length| {
+                    let expanded = match expand(&seed.tiles[..placed_count + length], &affines, reach) {
+                        Ok(expanded) => expanded,
+                        Err(_) => return false,
+                    };
+                    let mut centroids = PointMap::new();
+                    for tile in expanded.iter() {
+                        centroids.insert(tile.centroid, tile.size());
+                    }
+                    placed.iter().all(|tile| centroids.get(&tile.centroid) == Some(&tile.size()))
+                })
+                .unwrap_or(stage.len());
+            stage.truncate(length);
+            stages.push(stage);
+        }
+        Some(stages)
+    }
+}
+
+// expand grows tiles by affines until no more tiles with centroids within radius of the origin are found
+fn expand(tiles: &[Tile], affines: &[Affine], radius: f64) -> Result<Vec<Tile>, NotationError> {
+    let mut centroids: PointMap<usize> = PointMap::new();
+    let mut expanded: Vec<Tile> = vec![];
+    let mut queue: VecDeque<usize> = VecDeque::new();
+    for tile in tiles.iter() {
+        if centroids.insert(tile.centroid, expanded.len()) {
+            queue.push_back(expanded.len());
+            expanded.push(tile.clone());
+        }
+    }
+
+    while let Some(index) = queue.pop_front() {
+        for affine in affines.iter() {
+            let image = transform_tile(&expanded[index], affine);
+            if image.centroid.norm() > radius {
+                continue
+            }
+            match centroids.get(&image.centroid) {
+                Some(other) if expanded[*other].size() != image.size() => return Err(NotationError::Overlap),
+                Some(_) => {},
+                None => {
+                    if expanded.len() >= MAX_GENERATED {
+                        return Err(NotationError::NoTiling)
+                    }
+                    centroids.insert(image.centroid, expanded.len());
+                    queue.push_back(expanded.len());
+                    expanded.push(image);
+                },
+            }
+        }
+    }
+
+    Ok(expanded)
+}
+
+// translations finds the two shortest independent translations among the isometries generated by affines
+fn translations(affines: &[Affine], radius: f64) -> Result<(Point, Point), NotationError> {
+    // isometries are grouped by their linear part, which can only take a handful of values in a tiling's symmetry group
+    let linear_key = |affine: &Affine| {
+        let round = |value: f64| (value * 1000.).round() as i64;
+        (round(affine.0[0][0]), round(affine.0[0][1]), round(affine.0[1][0]), round(affine.0[1][1]))
+    };
+    let mut found: HashMap<(i64, i64, i64, i64), PointMap<()>> = HashMap::new();
+    let mut queue: VecDeque<Affine> = VecDeque::new();
+    found.entry(linear_key(&IDENTITY_AFFINE)).or_insert_with(PointMap::new).insert(ORIGIN, ());
+    queue.push_back(IDENTITY_AFFINE);
+
+    let mut count = 1;
+    let mut translations: Vec<Point> = vec![];
+    while let Some(isometry) = queue.pop_front() {
+        for affine in affines.iter() {
+            let next = isometry.transform(affine);
+            let translation = Point(next.1[0], next.1[1]);
+            if translation.norm() > radius {
+                continue
+            }
+            let key = linear_key(&next);
+            if !found.entry(key).or_insert_with(PointMap::new).insert(translation, ()) {
+                continue
+            }
+            count += 1;
+            if count > MAX_GENERATED {
+                return Err(NotationError::NoTiling)
+            }
+            if key == linear_key(&IDENTITY_AFFINE) {
+                translations.push(translation);
+            }
+            queue.push_back(next);
+        }
+    }
+
+    translations.sort_by(|a, b| a.norm().partial_cmp(&b.norm()).unwrap_or(Ordering::Equal));
+    let translation0 = *translations.first().ok_or(NotationError::NoTiling)?;
+    let translation1 = *translations
+        .iter()
+        .find(|translation| cross(&translation0, translation).abs() > POINT_MARGIN)
+        .ok_or(NotationError::NoTiling)?;
+    Ok((translation0, translation1))
+}
+
+// nearest_points returns the points of kind within radius of the origin, other than the origin itself, in order of
+// distance from it and then anticlockwise from the positive x-axis
+fn nearest_points(tiles: &[Tile], kind: GomJauHoggPoint, radius: f64) -> Vec<Point> {
+    let mut seen: PointMap<()> = PointMap::new();
+    let mut points: Vec<Point> = vec![];
+    for tile in tiles.iter() {
+        let candidates = match kind {
+            GomJauHoggPoint::Centroid => vec![tile.centroid],
+            GomJauHoggPoint::Midpoint => tile_edges(tile).iter().map(|(a, b)| midpoint(a, b)).collect(),
+            GomJauHoggPoint::Vertex => tile.points.clone(),
+        };
+        for point in candidates {
+            if point.norm() > POINT_MARGIN && point.norm() <= radius && seen.insert(point, ()) {
+                points.push(point);
+            }
+        }
+    }
+    points.sort_by(cmp_points);
+    points
+}
+
+// mirror_spacing returns the angle in degrees between the mirrors of m<degrees> through a polygon with sides, the
+// smallest multiple of degrees at which the polygon is symmetric
+fn mirror_spacing(degrees: f64, sides: usize) -> Result<f64, NotationError> {
+    let axis = 180. / sides as f64;
+    (1..=2 * sides)
+        .map(|multiple| multiple as f64 * degrees)
+        .find(|spacing| ((spacing / axis).round() * axis - spacing).abs() < POINT_MARGIN)
+        .ok_or_else(|| NotationError::InvalidTransform { transform: format!("m{}", fmt_degrees(degrees)) })
+}
+
+// point_affine returns the isometry of a transform about point
+fn point_affine(transform: &GomJauHoggTransform, point: &Point) -> Affine {
+    let euclid = match transform {
+        GomJauHoggTransform::Rotate(_) => Euclid::Rotate(PI),
+        GomJauHoggTransform::Mirror(_) => Euclid::Flip(point.1.atan2(point.0) + PI / 2.),
+    };
+    reduce_transforms(vec![&Euclid::Translate(point.neg().values()), &euclid, &Euclid::Translate(point.values())])
+}
+
+// patch_radius is the radius of the patch which points of transforms following a seed are found in
+fn patch_radius(seed: &[Tile]) -> f64 {
+    2. * radius(seed) + 4.
+}
+
+// develop places the tiles of an atlas of regular polygons with unit sides within radius of its first vertex
+fn develop(sides: &[usize], vertices: &[FullAtlasVertexPost], radius: f64) -> Result<Vec<Tile>, NotationError> {
+    // rays[i][j] is the angle of edge j of vertex i anticlockwise from edge 0
+    let rays = vertices
+        .iter()
+        .map(|vertex| {
+            vertex.edges
+                .iter()
+                .scan(0., |angle, edge| {
+                    let ray = *angle;
+                    *angle += interior_angle(sides[edge.polygon_index]);
+                    Some(ray)
+                })
+                .collect::<Vec<f64>>()
+        })
+        .collect::<Vec<Vec<f64>>>();
+
+    let mut tiles: Vec<Tile> = vec![];
+    let mut centroids: PointMap<()> = PointMap::new();
+    let mut visited: PointMap<()> = PointMap::new();
+    let mut queue: VecDeque<(Point, f64, f64, usize)> = VecDeque::new(); // (point, rotation, orientation, vertex index)
+    visited.insert(ORIGIN, ());
+    queue.push_back((ORIGIN, 0., 1., 0));
+
+    while let Some((point, rotation, orientation, i)) = queue.pop_front() {
+        for (j, edge) in vertices[i].edges.iter().enumerate() {
+            let n = sides[edge.polygon_index];
+            let angle = rotation + orientation * rays[i][j];
+
+            let tile = regular_tile(&point, angle, orientation, n);
+            if centroids.insert(tile.centroid, ()) {
+                tiles.push(tile);
+                if tiles.len() > MAX_GENERATED {
+                    return Err(NotationError::NoTiling)
+                }
+            }
+
+            let neighbor = &point + &direction(angle);
+            if neighbor.norm() <= radius && visited.insert(neighbor, ()) {
+                let neighbor_orientation = if edge.parity { -orientation } else { orientation };
+                let neighbor_rotation = angle + PI - neighbor_orientation * rays[edge.neighbor_index][edge.neighbor_edge_index];
+                queue.push_back((neighbor, neighbor_rotation, neighbor_orientation, edge.neighbor_index));
+            }
+        }
+    }
+
+    Ok(tiles)
+}
+
+// center moves the most symmetric polygon near the origin onto it, with one of its edges horizontally below it
+fn center(tiles: Vec<Tile>) -> Result<Vec<Tile>, NotationError> {
+    let tiling = Tiling::new(tiles);
+    let mut candidates = tiling.tiles.iter().filter(|tile| tile.centroid.norm() <= 8.).collect::<Vec<&Tile>>();
+    candidates.sort_by(|a, b| cmp_points(&a.centroid, &b.centroid));
+
+    let central = candidates
+        .iter()
+        .map(|tile| {
+            let about = |euclid: Euclid| reduce_transforms(vec![&Euclid::Translate(tile.centroid.neg().values()), &euclid, &Euclid::Translate(tile.centroid.values())]);
+            let n = tile.size();
+            let offset = (&tile.points[0] - &tile.centroid).1.atan2((&tile.points[0] - &tile.centroid).0);
+            let count = (1..n)
+                .map(|k| about(Euclid::Rotate(TAU * k as f64 / n as f64)))
+                .chain((0..n).map(|k| about(Euclid::Flip(offset + PI * k as f64 / n as f64))))
+                .filter(|affine| tiling.is_symmetry_about(affine, &tile.centroid, 6.))
+                .count();
+            (count, n, *tile)
+        })
+        .fold(None, |best: Option<(usize, usize, &Tile)>, candidate| match best {
+            Some(best) if (best.0, best.1) >= (candidate.0, candidate.1) => Some(best),
+            _ => Some(candidate),
+        })
+        .map(|(_, _, tile)| tile.clone())
+        .ok_or(NotationError::NoTiling)?;
+
+    // rotate the edge whose midpoint is closest to straight below the centroid to be straight below it
+    let below = tile_edges(&central)
+        .iter()
+        .map(|(a, b)| { let direction = &midpoint(a, b) - &central.centroid; direction.1.atan2(direction.0) })
+        .min_by(|a, b| angle_distance(*a, 1.5 * PI).partial_cmp(&angle_distance(*b, 1.5 * PI)).unwrap_or(Ordering::Equal))
+        .unwrap();
+    let affine = reduce_transforms(vec![&Euclid::Translate(central.centroid.neg().values()), &Euclid::Rotate(1.5 * PI - below)]);
+    Ok(tiling.tiles.iter().map(|tile| transform_tile(tile, &affine)).collect())
+}
+
+// transform_tile transforms tile, keeping its points in anticlockwise order
+fn transform_tile(tile: &Tile, affine: &Affine) -> Tile {
+    let mut points = tile.points.iter().map(|point| point.transform(affine)).collect::<Vec<Point>>();
+    if affine.is_flip() {
+        points.reverse();
+    }
+    Tile::new(points)
+}
+
+fn is_unit_regular(tile: &Tile) -> bool {
+    let n = tile.size();
+    tile_edges(tile).iter().all(|(a, b)| ((a - b).norm() - 1.).abs() < POINT_MARGIN)
+        && tile.angles().iter().all(|angle| (angle - interior_angle(n)).abs() < POINT_MARGIN)
+}
+
+fn radius(tiles: &[Tile]) -> f64 {
+    tiles.iter().flat_map(|tile| tile.points.iter()).map(Point::norm).fold(0., f64::max)
+}
+
+// cmp_angles orders points anticlockwise from the positive x-axis, then by distance from the origin
+fn cmp_angles(a: &Point, b: &Point) -> Ordering {
+    let (angle_a, angle_b) = (normalize_angle(a.1.atan2(a.0)), normalize_angle(b.1.atan2(b.0)));
+    if (angle_a - angle_b).abs() < POINT_MARGIN {
+        a.norm().partial_cmp(&b.norm()).unwrap_or(Ordering::Equal)
+    } else {
+        angle_a.partial_cmp(&angle_b).unwrap_or(Ordering::Equal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VertexConfiguration;
+
+    // configurations returns the distinct vertex configurations of an atlas of regular polygons,
+    // each as its least rotation or reflection
+    fn configurations(polygons: &[Tile], vertices: &[FullAtlasVertexPost]) -> Vec<Vec<usize>> {
+        let mut configurations = vertices
+            .iter()
+            .map(|vertex| {
+                let sides = vertex.edges.iter().map(|edge| polygons[edge.polygon_index].size()).collect::<Vec<usize>>();
+                let n = sides.len();
+                (0..n)
+                    .flat_map(|offset| vec![
+                        (0..n).map(|j| sides[(offset + j) % n]).collect::<Vec<usize>>(),
+                        (0..n).map(|j| sides[(offset + n - j) % n]).collect::<Vec<usize>>(),
+                    ])
+                    .min()
+                    .unwrap()
+            })
+            .collect::<Vec<Vec<usize>>>();
+        configurations.sort();
+        configurations.dedup();
+        configurations
+    }
+
+    fn assert_tiles(notation: &str) -> Vec<FullAtlasVertexPost> {
+        let notation = GomJauHogg::parse(notation).unwrap();
+        let vertices = notation.to_vertex_posts().expect(&notation.to_string());
+        let validation = validate(&notation.tiles(), &vertices);
+        assert!(validation.valid, "{}: {}", notation, validation);
+        vertices
+    }
+
+    #[test]
+    fn test_gomjau_hogg_parse() {
+        let notation = GomJauHogg::parse("3-4-3,3/m30/r(c3)").unwrap();
+        assert_eq!(vec![vec![3], vec![4], vec![3, 3]], notation.stages);
+        assert_eq!(
+            vec![
+                GomJauHoggTransform::Mirror(GomJauHoggAnchor::Angle(30.)),
+                GomJauHoggTransform::Rotate(GomJauHoggAnchor::Point(GomJauHoggPoint::Centroid, 3)),
+            ],
+            notation.transforms,
+        );
+        assert_eq!("3-4-3,3/m30/r(c3)", notation.to_string());
+        assert_eq!(vec![NotationPolygon::Regular { sides: 3 }, NotationPolygon::Regular { sides: 4 }], notation.polygons());
+
+        assert_eq!(Err(NotationError::NoTransforms), GomJauHogg::parse("6").map(|_| ()));
+        assert_eq!(Err(NotationError::InvalidStage { stage: String::from("0") }), GomJauHogg::parse("0-3/r60").map(|_| ()));
+        assert_eq!(Err(NotationError::InvalidStage { stage: String::from("2") }), GomJauHogg::parse("6-2/r60").map(|_| ()));
+        assert_eq!(Err(NotationError::InvalidTransform { transform: String::from("x30") }), GomJauHogg::parse("6/x30").map(|_| ()));
+        assert_eq!(Err(NotationError::InvalidTransform { transform: String::from("r7") }), GomJauHogg::parse("6/r7").map(|_| ()));
+        assert_eq!(Err(NotationError::InvalidTransform { transform: String::from("r(c0)") }), GomJauHogg::parse("6/r(c0)").map(|_| ()));
+    }
+
+    #[test]
+    fn test_gomjau_hogg_to_vertex_posts() {
+        // symbols as published in the catalog of GomJau-Hogg's paper
+        for (notation, expected) in [
+            ("3/m30/r(h2)", vec![vec![3, 3, 3, 3, 3, 3]]),
+            ("4/m45/r(h1)", vec![vec![4, 4, 4, 4]]),
+            ("6/m30/r(h1)", vec![vec![6, 6, 6]]),
+            ("6-3-6/m30/r(v4)", vec![vec![3, 6, 3, 6]]),
+            ("6-4-3/m30/r(c2)", vec![vec![3, 4, 6, 4]]),
+            ("8-4/m90/r(h4)", vec![vec![4, 8, 8]]),
+            ("12-3/m30/r(h2)", vec![vec![3, 12, 12]]),
+            ("12-6,4/m30/r(c2)", vec![vec![4, 6, 12]]),
+            ("3-4-3,3/m30/r(c3)", vec![vec![3, 3, 3, 3, 3, 3], vec![3, 3, 4, 3, 4]]),
+        ].iter() {
+            let vertices = assert_tiles(notation);
+            assert_eq!(*expected, configurations(&GomJauHogg::parse(notation).unwrap().tiles(), &vertices), "{}", notation);
+        }
+
+        // octagons leave square gaps
+        assert_eq!(Err(NotationError::NoTiling), GomJauHogg::parse("8/m45/r(h1)").unwrap().to_vertex_posts().map(|_| ()));
+        // neighboring octagons around an octagon overlap
+        assert_eq!(Err(NotationError::Overlap), GomJauHogg::parse("8-8,8/m45/r(h1)").unwrap().to_vertex_posts().map(|_| ()));
+        assert_eq!(
+            Err(NotationError::MissingPoint { point: String::from("h400") }),
+            GomJauHogg::parse("6/m30/r(h400)").unwrap().to_vertex_posts().map(|_| ()),
+        );
+        // a hexagon has only six edges for a stage's polygons
+        assert_eq!(
+            Err(NotationError::InvalidStage { stage: String::from("3,3,3,3,3,3,3") }),
+            GomJauHogg::parse("6-3,3,3,3,3,3,3/r60").unwrap().to_vertex_posts().map(|_| ()),
+        );
+    }
+
+    #[test]
+    fn test_gomjau_hogg_from_vertex_posts() {
+        for (configuration, expected) in [
+            ("6.6.6", "6/m30/r(h1)"),
+            ("4.8.8", "8-4,8/m45/r(c1)"),
+            ("4.6.12", "12-6,4/m30/r(c1)"),
+            ("3.4.6.4", "6-4-3,6/m30/r(c1)"),
+            ("3.3.3.3.6", "6-3-3,3-3,3,6/r60/r(h43)"),
+            ("[3^6; 3^2.4.3.4]", "3-4-3,3/m60/r(c1)"),
+            ("[3.4^2.6; 3.6.3.6]", "6-3,3,4,3,3,3/r(h17)/r(h20)/r(v1)"),
+        ].iter() {
+            let configuration = VertexConfiguration::parse(configuration).unwrap();
+            let polygons = configuration.tiles();
+            let vertices = configuration.to_vertex_posts().unwrap();
+            let notation = GomJauHogg::from_vertex_posts(&polygons, &vertices).unwrap();
+            assert_eq!(*expected, notation.to_string());
+            assert_eq!(configurations(&polygons, &vertices), configurations(&notation.tiles(), &assert_tiles(expected)));
+        }
+
+        // exporting the tilings of published symbols gives notations for the same tilings
+        for notation in ["3/m30/r(h2)", "6-3-6/m30/r(v4)", "8-4/m90/r(h4)", "12-3/m30/r(h2)", "3-4-3,3/m30/r(c3)"].iter() {
+            let polygons = GomJauHogg::parse(notation).unwrap().tiles();
+            let vertices = assert_tiles(notation);
+            let exported = GomJauHogg::from_vertex_posts(&polygons, &vertices).unwrap().to_string();
+            assert_eq!(configurations(&polygons, &vertices), configurations(&polygons, &assert_tiles(&exported)), "{} exported as {}", notation, exported);
+        }
+
+        let configuration = VertexConfiguration::parse("[4.6apio6.6aapio2.6apio6; 4.6aapio6; 6apio2.6aapio6]").unwrap();
+        assert_eq!(
+            Err(NotationError::UnsupportedPolygon { polygon_index: 1 }),
+            GomJauHogg::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().unwrap()),
+        );
+
+        let configuration = VertexConfiguration::parse("6.6.6").unwrap();
+        let mut vertices = configuration.to_vertex_posts().unwrap();
+        vertices[0].edges[0].neighbor_index = 3;
+        assert!(matches!(GomJauHogg::from_vertex_posts(&configuration.tiles(), &vertices), Err(NotationError::InvalidAtlas { .. })));
+    }
+}
@@ -1 +1,17 @@
+mod delaney;
+mod dual;
+mod gomjau_hogg;
+mod notation;
+mod periodic;
+mod symmetry;
+mod unit_cell;
+mod vertex_type;
+
 pub use models::client::*;
+pub use self::delaney::*;
+pub use self::dual::*;
+pub use self::gomjau_hogg::*;
+pub use self::notation::*;
+pub use self::symmetry::*;
+pub use self::unit_cell::*;
+pub use self::vertex_type::*;
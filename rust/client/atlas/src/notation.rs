@@ -0,0 +1,840 @@
+use crate::AtlasError;
+use common::DEFAULT_F64_MARGIN;
+use float_cmp::ApproxEq;
+use models::{FullAtlasEdgePost, FullAtlasPost, FullAtlasVertexPost, FullSubTilingPost};
+use serde::Serialize;
+use std::{collections::HashSet, f64::consts::{PI, TAU}};
+use tile::{regular_polygon, star_polygon, Tile};
+
+// bounds on the number of edge pairings tried while searching for a tiling, first with each repetition
+// of the configuration's vertices and then in total
+const MIN_SEARCH_STEPS: usize = 1_000;
+const MAX_SEARCH_STEPS: usize = 10_000_000;
+// upper bound on the number of atlas vertices a configuration's vertices may be repeated into
+const MAX_ATLAS_VERTICES: usize = 12;
+// upper bound on the denominator of a star's angle as a fraction of π when it's written out
+const MAX_ANGLE_DENOMINATOR: usize = 360;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub enum NotationError {
+    // notation has no vertices or one of its vertices has no polygons
+    Empty,
+    // polygon couldn't be parsed as a regular or star polygon
+    InvalidPolygon { polygon: String },
+    // the interior angles of the vertex's polygons don't sum to 360°
+    AngleSumMismatch {
+        #[serde(rename = "vertexIndex")]
+        vertex_index: usize,
+        degrees: f64,
+    },
+    // the vertices can't be connected into a tiling
+    NoTiling,
+    // the search for a tiling gave up after max_steps pairings of edges, without finding one or ruling one out
+    SearchLimit {
+        #[serde(rename = "maxSteps")]
+        max_steps: usize,
+    },
+    // listing the vertex types of a set of polygons gave up after max_steps extensions of a sequence of corners
+    VertexTypeLimit {
+        #[serde(rename = "maxSteps")]
+        max_steps: usize,
+    },
+    // the number of polygon ids doesn't match the number of distinct polygons in the notation
+    PolygonIdCount { expected: usize, received: usize },
+    // a stage of a GomJau-Hogg seed couldn't be parsed or has more polygons than free edges
+    InvalidStage { stage: String },
+    // a transform of a GomJau-Hogg notation couldn't be parsed
+    InvalidTransform { transform: String },
+    // a GomJau-Hogg notation has no transforms to cover the plane with
+    NoTransforms,
+    // no point of a GomJau-Hogg transform's kind is found at its index
+    MissingPoint { point: String },
+    // polygons placed by a notation overlap one another
+    Overlap,
+    // polygon can't be written in the notation
+    UnsupportedPolygon {
+        #[serde(rename = "polygonIndex")]
+        polygon_index: usize,
+    },
+    // the atlas being written in a notation is invalid
+    InvalidAtlas { issues: Vec<AtlasError> },
+    // no notation was found for the atlas' tiling
+    Unrepresentable,
+    // Delaney-Dress symbol couldn't be parsed
+    InvalidSymbol { symbol: String },
+    // an operation of a Delaney-Dress symbol isn't an involution on its chambers
+    InvalidOperation { operation: usize, chamber: usize },
+    // a degree of a Delaney-Dress symbol isn't constant on its orbit or isn't a multiple of the orbit's period
+    InvalidDegree { chamber: usize },
+    // a Delaney-Dress symbol's chambers aren't all connected by its operations
+    DisconnectedSymbol,
+    // a Delaney-Dress symbol describes a tiling of the sphere or the hyperbolic plane rather than the euclidean plane
+    NonEuclidean { curvature: f64 },
+    // the centroids of the tiles around a vertex of the unit cell of a tiling don't bound a polygon, so its dual isn't a
+    // tiling by polygons
+    NonPolygonalDual {
+        #[serde(rename = "vertexIndex")]
+        vertex_index: usize,
+    },
+}
+
+impl std::fmt::Display for NotationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            NotationError::Empty => write!(f, "vertex configuration is empty"),
+            NotationError::InvalidPolygon { polygon } => write!(f, "couldn't parse polygon {:?}", polygon),
+            NotationError::AngleSumMismatch { vertex_index, degrees } =>
+                write!(f, "vertex {} - prototiles don't fit together perfectly - expected 360° fill but received ~{}°", vertex_index, common::fmt_float(*degrees, 2)),
+            NotationError::NoTiling => write!(f, "couldn't find a tiling with the given vertex configuration"),
+            NotationError::SearchLimit { max_steps } => write!(f, "gave up searching for a tiling after {} steps", max_steps),
+            NotationError::VertexTypeLimit { max_steps } => write!(f, "gave up listing vertex types after {} steps", max_steps),
+            NotationError::PolygonIdCount { expected, received } => write!(f, "expected {} polygon ids but received {}", expected, received),
+            NotationError::InvalidStage { stage } => write!(f, "couldn't place stage {:?}", stage),
+            NotationError::InvalidTransform { transform } => write!(f, "couldn't parse transform {:?}", transform),
+            NotationError::NoTransforms => write!(f, "notation has no transforms"),
+            NotationError::MissingPoint { point } => write!(f, "couldn't find point {}", point),
+            NotationError::Overlap => write!(f, "polygons overlap"),
+            NotationError::UnsupportedPolygon { polygon_index } => write!(f, "polygon {} isn't a regular polygon with unit sides", polygon_index),
+            NotationError::InvalidAtlas { issues } =>
+                write!(f, "invalid atlas: {}", issues.iter().map(AtlasError::to_string).collect::<Vec<String>>().join(", ")),
+            NotationError::Unrepresentable => write!(f, "couldn't find a notation for the tiling"),
+            NotationError::InvalidSymbol { symbol } => write!(f, "couldn't parse symbol {:?}", symbol),
+            NotationError::InvalidOperation { operation, chamber } => write!(f, "operation {} isn't an involution at chamber {}", operation, chamber + 1),
+            NotationError::InvalidDegree { chamber } => write!(f, "degrees of chamber {} don't match its orbits", chamber + 1),
+            NotationError::DisconnectedSymbol => write!(f, "symbol's chambers aren't connected"),
+            NotationError::NonEuclidean { curvature } => write!(f, "symbol isn't euclidean - expected curvature 0 but received ~{}", common::fmt_float(*curvature, 4)),
+            NotationError::NonPolygonalDual { vertex_index } => write!(f, "vertex {} - dual isn't a polygon tiling, the centroids of the tiles around it don't bound a polygon", vertex_index),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum NotationPolygon {
+    Regular { sides: usize },
+    // a star with points many tips of internal angle angle
+    Star { points: usize, angle: f64 },
+}
+
+impl NotationPolygon {
+    // tile returns this polygon with unit side length, as given by tile::regular_polygon and tile::star_polygon
+    pub fn tile(&self) -> Tile {
+        match self {
+            NotationPolygon::Regular { sides } => regular_polygon(1., *sides),
+            NotationPolygon::Star { points, angle } => star_polygon(1., *points, *angle),
+        }
+    }
+
+    // from_tile returns the polygon tile is congruent to, if it's a regular polygon or a star with unit sides
+    pub fn from_tile(tile: &Tile) -> Option<NotationPolygon> {
+        let size = tile.size();
+        let angles = tile.angles();
+        let is_unit = (0..size).all(|i| (&tile.points[(i + 1) % size] - &tile.points[i]).norm().approx_eq(1., DEFAULT_F64_MARGIN));
+        if size < 3 || !is_unit {
+            return None
+        }
+
+        let polygon = if angles.iter().all(|angle| angle.approx_eq(angles[0], DEFAULT_F64_MARGIN)) {
+            NotationPolygon::Regular { sides: size }
+        } else {
+            let (points, angle) = (size / 2, angles[0].min(angles[1]));
+            if size % 2 != 0 || points < 3 || angle >= PI * (1. - 2. / points as f64) {
+                return None
+            }
+            NotationPolygon::Star { points, angle }
+        };
+
+        // sides of unit length and matching angles in the same cyclic order make for a congruent polygon
+        let expected = polygon.tile().angles();
+        let is_congruent = (0..size).any(|offset| (0..size).all(|i| angles[(i + offset) % size].approx_eq(expected[i], DEFAULT_F64_MARGIN)));
+        if is_congruent { Some(polygon) } else { None }
+    }
+
+    // notation writes this polygon as it's parsed at a corner of kind, e.g. 4, 6apio6 or 6aapio2
+    fn notation(&self, kind: CornerKind) -> String {
+        match self {
+            NotationPolygon::Regular { sides } => sides.to_string(),
+            NotationPolygon::Star { points, angle } => {
+                // the angle is written as the simplest fraction of π within margin
+                let ratio = angle / PI;
+                let (numerator, denominator) = (1..=MAX_ANGLE_DENOMINATOR)
+                    .map(|denominator| ((ratio * denominator as f64).round() as usize, denominator))
+                    .find(|(numerator, denominator)| (*numerator as f64 / *denominator as f64).approx_eq(ratio, DEFAULT_F64_MARGIN))
+                    .unwrap_or(((ratio * MAX_ANGLE_DENOMINATOR as f64).round() as usize, MAX_ANGLE_DENOMINATOR));
+                let numerator = if numerator == 1 { String::new() } else { numerator.to_string() };
+                let prefix = if kind == CornerKind::Dent { "aa" } else { "a" };
+                format!("{}{}{}pio{}", points, prefix, numerator, denominator)
+            },
+        }
+    }
+
+    fn approx_eq(&self, other: &NotationPolygon) -> bool {
+        match (self, other) {
+            (NotationPolygon::Regular { sides: a }, NotationPolygon::Regular { sides: b }) => a == b,
+            (NotationPolygon::Star { points: a, angle: alpha }, NotationPolygon::Star { points: b, angle: beta }) =>
+                a == b && alpha.approx_eq(*beta, DEFAULT_F64_MARGIN),
+            _ => false,
+        }
+    }
+}
+
+// CornerKind distinguishes which of a polygon's points sits at a vertex,
+// a regular polygon's points are all alike whereas a star's alternate between tips and dents
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CornerKind {
+    Regular,
+    Tip,
+    Dent,
+}
+
+impl CornerKind {
+    // next returns the kind of the point following one of this kind around its polygon
+    pub fn next(&self) -> CornerKind {
+        match self {
+            CornerKind::Regular => CornerKind::Regular,
+            CornerKind::Tip => CornerKind::Dent,
+            CornerKind::Dent => CornerKind::Tip,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Corner {
+    pub polygon_index: usize,
+    pub kind: CornerKind,
+}
+
+// VertexConfiguration is a parsed vertex configuration such as 3.3.4.3.4, [3^6; 3^2.4.3.4] or 4.6apio6.6aapio2.6apio6.
+// Each polygon is either a regular polygon given by its number of sides, or a star given by its number of tips followed by
+// "a" (meeting the vertex at a tip) or "aa" (meeting the vertex at a dent) and its tips' internal angle, e.g. 6apio6 is
+// a 6 pointed star with tips of angle π/6 and 6a2pio3 one with tips of angle 2π/3.
+#[derive(Clone, Debug)]
+pub struct VertexConfiguration {
+    pub polygons: Vec<NotationPolygon>,
+    pub vertices: Vec<Vec<Corner>>,
+}
+
+impl VertexConfiguration {
+    pub fn parse(notation: &str) -> Result<VertexConfiguration, NotationError> {
+        let notation = notation.trim();
+        let notation = match notation.strip_prefix('[').and_then(|notation| notation.strip_suffix(']')) {
+            Some(notation) => notation,
+            None => notation,
+        };
+
+        let mut polygons: Vec<NotationPolygon> = vec![];
+        let mut vertices: Vec<Vec<Corner>> = vec![];
+
+        for vertex in notation.split(&[';', ','][..]) {
+            let mut corners: Vec<Corner> = vec![];
+            for polygon in vertex.trim().split('.') {
+                let (polygon, count) = match polygon.split_once('^') {
+                    None => (polygon, 1),
+                    Some((polygon, count)) => (polygon, count.parse::<usize>().map_err(|_| NotationError::InvalidPolygon { polygon: polygon.to_string() })?),
+                };
+                let (notation_polygon, kind) = parse_polygon(polygon)?;
+                let polygon_index = match polygons.iter().position(|other| other.approx_eq(&notation_polygon)) {
+                    Some(polygon_index) => polygon_index,
+                    None => {
+                        polygons.push(notation_polygon);
+                        polygons.len() - 1
+                    },
+                };
+                corners.extend(std::iter::repeat(Corner { polygon_index, kind }).take(count));
+            }
+            if corners.is_empty() {
+                return Err(NotationError::Empty)
+            }
+            vertices.push(corners);
+        }
+
+        if vertices.is_empty() {
+            return Err(NotationError::Empty)
+        }
+
+        Ok(VertexConfiguration { polygons, vertices })
+    }
+
+    // tiles returns the tile of each of this configuration's polygons, in order of polygon index
+    pub fn tiles(&self) -> Vec<Tile> {
+        self.polygons.iter().map(NotationPolygon::tile).collect()
+    }
+
+    // to_vertex_posts derives each vertex's edges, i.e. the neighbor vertex and edge at the other end of each
+    // edge and whether that neighbor is reflected, from the polygons surrounding the vertices. If more than one
+    // tiling has this vertex configuration, the first one found is returned. A vertex of the configuration may
+    // be repeated in the returned vertices when it can't otherwise be consistently connected to its neighbors.
+    // SearchLimit is returned if no tiling is found or ruled out within MAX_SEARCH_STEPS pairings of edges.
+    pub fn to_vertex_posts(&self) -> Result<Vec<FullAtlasVertexPost>, NotationError> {
+        self.search(1, MAX_SEARCH_STEPS, &mut |_| true).map(|(mut atlases, _)| atlases.remove(0))
+    }
+
+    // enumerate_vertex_posts derives the vertices of up to limit atlases with this vertex configuration, see
+    // to_vertex_posts, trying at most max_steps pairings of edges. Only atlases accept returns true for are counted and
+    // returned, and each atlas is passed to it once. Atlases are distinct as pairings of edges but may still describe
+    // the same tiling unless accept rejects repeats. The atlases are returned with whether the search was exhausted,
+    // which it isn't if it stopped at limit atlases or max_steps pairings while there may have been more atlases.
+    pub fn enumerate_vertex_posts<F>(&self, limit: usize, max_steps: usize, mut accept: F) -> Result<(Vec<Vec<FullAtlasVertexPost>>, bool), NotationError>
+    where F: FnMut(&[FullAtlasVertexPost]) -> bool
+    {
+        self.search(limit, max_steps, &mut accept)
+    }
+
+    // search finds up to max_solutions atlases of this configuration accepted by accept within max_steps pairings of edges,
+    // along with whether it was exhausted, returning SearchLimit if it runs out of steps before finding any or ruling them out
+    fn search(
+        &self,
+        max_solutions: usize,
+        max_steps: usize,
+        accept: &mut dyn FnMut(&[FullAtlasVertexPost]) -> bool,
+    ) -> Result<(Vec<Vec<FullAtlasVertexPost>>, bool), NotationError> {
+        let tiles = self.tiles();
+
+        let mut corner_point_indices: Vec<Vec<usize>> = Vec::with_capacity(self.vertices.len());
+        for (i, corners) in self.vertices.iter().enumerate() {
+            let point_indices = corners
+                .iter()
+                .map(|corner| corner_point_index(&tiles[corner.polygon_index], &self.polygons[corner.polygon_index], corner.kind))
+                .collect::<Vec<usize>>();
+
+            let angle: f64 = corners
+                .iter()
+                .zip(point_indices.iter())
+                .map(|(corner, point_index)| tiles[corner.polygon_index].angle(*point_index))
+                .sum();
+            if !angle.approx_eq(TAU, DEFAULT_F64_MARGIN) {
+                return Err(NotationError::AngleSumMismatch { vertex_index: i, degrees: angle * 360. / TAU })
+            }
+
+            corner_point_indices.push(point_indices);
+        }
+
+        // a vertex orbit whose vertex stars are symmetric may need more than one atlas vertex to describe it,
+        // so each of the configuration's vertices is repeated as few times as possible. Some repetitions take
+        // far longer to rule out than others, so each is searched with a growing step limit until it either
+        // finds enough atlases or is exhausted. Atlases found by a search cut short are kept and skipped when it's retried.
+        let mut pending = vertex_counts(self.vertices.len(), MAX_ATLAS_VERTICES);
+        let mut limit = MIN_SEARCH_STEPS;
+        let mut steps = 0;
+        let mut atlases: Vec<Vec<FullAtlasVertexPost>> = vec![];
+        let mut found: HashSet<(Vec<usize>, Vec<Vec<Pairing>>)> = HashSet::new();
+        while atlases.len() < max_solutions && !pending.is_empty() && steps < max_steps {
+            let mut finished = vec![];
+            for (index, counts) in pending.iter().enumerate() {
+                let limit = limit.min(max_steps - steps);
+                let origins = counts
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, count)| std::iter::repeat(i).take(*count))
+                    .collect::<Vec<usize>>();
+                let mut attempt = Search {
+                    vertices: origins.iter().map(|i| self.vertices[*i].clone()).collect(),
+                    symmetries: origins.iter().map(|i| symmetries(&self.vertices[*i])).collect(),
+                    edges: origins.iter().map(|i| vec![None; self.vertices[*i].len()]).collect(),
+                    origins,
+                    sizes: tiles.iter().map(Tile::size).collect(),
+                    solutions: 0,
+                    max_solutions: max_solutions - atlases.len(),
+                    steps: 0,
+                    limit,
+                };
+                attempt.run(&mut |attempt, edges| {
+                    if !found.insert((attempt.origins.clone(), edges.to_vec())) {
+                        return false
+                    }
+                    let vertices = self.vertex_posts(attempt, edges, &corner_point_indices);
+                    if !accept(&vertices) {
+                        return false
+                    }
+                    atlases.push(vertices);
+                    true
+                });
+                steps += attempt.steps;
+                if attempt.steps <= limit || attempt.solutions >= attempt.max_solutions {
+                    finished.push(index);
+                    if atlases.len() >= max_solutions {
+                        break
+                    }
+                }
+                if steps >= max_steps {
+                    break
+                }
+            }
+            for index in finished.into_iter().rev() {
+                pending.remove(index);
+            }
+            limit *= 10;
+        }
+
+        if atlases.is_empty() {
+            return Err(if pending.is_empty() { NotationError::NoTiling } else { NotationError::SearchLimit { max_steps } })
+        }
+        // a repetition which stopped at max_solutions counts as finished, but may have had more atlases to find
+        let is_exhausted = pending.is_empty() && atlases.len() < max_solutions;
+        Ok((atlases, is_exhausted))
+    }
+
+    // vertex_posts writes out the pairing of edges found by search
+    fn vertex_posts(&self, search: &Search, edges: &[Vec<Pairing>], corner_point_indices: &[Vec<usize>]) -> Vec<FullAtlasVertexPost> {
+        edges
+            .iter()
+            .enumerate()
+            .map(|(i, edges)| FullAtlasVertexPost {
+                edges: edges
+                    .iter()
+                    .enumerate()
+                    .map(|(j, (neighbor_index, neighbor_edge_index, parity))| FullAtlasEdgePost {
+                        polygon_index: search.vertices[i][j].polygon_index,
+                        point_index: corner_point_indices[search.origins[i]][j],
+                        neighbor_index: *neighbor_index,
+                        neighbor_edge_index: *neighbor_edge_index,
+                        parity: *parity,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    // to_full_atlas_post builds an atlas of this configuration, where polygon_ids are the ids of
+    // this configuration's polygons, in order of polygon index
+    pub fn to_full_atlas_post(&self, tiling: FullSubTilingPost, polygon_ids: Vec<i32>) -> Result<FullAtlasPost, NotationError> {
+        if polygon_ids.len() != self.polygons.len() {
+            return Err(NotationError::PolygonIdCount { expected: self.polygons.len(), received: polygon_ids.len() })
+        }
+        Ok(FullAtlasPost {
+            tiling,
+            polygon_ids,
+            vertices: self.to_vertex_posts()?,
+            owner_id: None,
+        })
+    }
+}
+
+// VertexConfiguration is written the way it's parsed, with repeated polygons as powers, e.g. [3^6; 3^2.4.3.4]
+impl std::fmt::Display for VertexConfiguration {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let vertices = self.vertices
+            .iter()
+            .map(|corners| {
+                let mut runs: Vec<(String, usize)> = vec![];
+                for corner in corners.iter() {
+                    let polygon = self.polygons[corner.polygon_index].notation(corner.kind);
+                    match runs.last_mut() {
+                        Some((last, count)) if *last == polygon => *count += 1,
+                        _ => runs.push((polygon, 1)),
+                    }
+                }
+                runs
+                    .iter()
+                    .map(|(polygon, count)| if *count == 1 { polygon.clone() } else { format!("{}^{}", polygon, count) })
+                    .collect::<Vec<String>>()
+                    .join(".")
+            })
+            .collect::<Vec<String>>();
+        if vertices.len() == 1 {
+            write!(f, "{}", vertices[0])
+        } else {
+            write!(f, "[{}]", vertices.join("; "))
+        }
+    }
+}
+
+// parse_polygon parses a single polygon of a vertex configuration, e.g. 4, 6apio6 or 6aapio2
+fn parse_polygon(polygon: &str) -> Result<(NotationPolygon, CornerKind), NotationError> {
+    let invalid = || NotationError::InvalidPolygon { polygon: polygon.to_string() };
+    let polygon = polygon.trim();
+
+    let digits = polygon.find(|c: char| !c.is_ascii_digit()).unwrap_or(polygon.len());
+    let sides = polygon[..digits].parse::<usize>().map_err(|_| invalid())?;
+    if sides < 3 {
+        return Err(invalid())
+    }
+
+    let suffix = &polygon[digits..];
+    if suffix.is_empty() {
+        return Ok((NotationPolygon::Regular { sides }, CornerKind::Regular))
+    }
+
+    let (kind, angle) = match suffix.strip_prefix("aa") {
+        Some(angle) => (CornerKind::Dent, angle),
+        None => (CornerKind::Tip, suffix.strip_prefix('a').ok_or_else(invalid)?),
+    };
+    let (numerator, denominator) = angle.split_once("pio").ok_or_else(invalid)?;
+    let numerator = if numerator.is_empty() { 1. } else { numerator.parse::<f64>().map_err(|_| invalid())? };
+    let denominator = denominator.parse::<f64>().map_err(|_| invalid())?;
+    let angle = numerator * PI / denominator;
+
+    // a star's tips must be sharper than the corners of the regular polygon it's built on
+    if angle <= 0. || angle >= PI * (1. - 2. / sides as f64) {
+        return Err(invalid())
+    }
+
+    Ok((NotationPolygon::Star { points: sides, angle }, kind))
+}
+
+// corner_point_index returns the index of a point of kind in tile
+fn corner_point_index(tile: &Tile, polygon: &NotationPolygon, kind: CornerKind) -> usize {
+    match (polygon, kind) {
+        (NotationPolygon::Star { angle, .. }, CornerKind::Dent) =>
+            (0..tile.size()).find(|point_index| !tile.angle(*point_index).approx_eq(*angle, DEFAULT_F64_MARGIN)).unwrap_or(0),
+        (NotationPolygon::Star { angle, .. }, _) =>
+            (0..tile.size()).find(|point_index| tile.angle(*point_index).approx_eq(*angle, DEFAULT_F64_MARGIN)).unwrap_or(0),
+        _ => 0,
+    }
+}
+
+// vertex_counts lists the ways of repeating each of len vertices at least once into at most max vertices,
+// in order of total
+fn vertex_counts(len: usize, max: usize) -> Vec<Vec<usize>> {
+    let mut counts: Vec<Vec<usize>> = vec![];
+    for total in len..=max.max(len) {
+        let mut count = vec![1; len];
+        count[len - 1] = total - len + 1;
+        loop {
+            counts.push(count.clone());
+            // step to the next way of splitting total: increment the rightmost count with any excess after it
+            let i = match (0..len - 1).rev().find(|i| count[i + 1..].iter().any(|c| *c > 1)) {
+                None => break,
+                Some(i) => i,
+            };
+            let excess: usize = count[i + 1..].iter().map(|c| c - 1).sum();
+            count[i] += 1;
+            for c in count[i + 1..].iter_mut() {
+                *c = 1;
+            }
+            count[len - 1] += excess - 1;
+        }
+    }
+    counts
+}
+
+// symmetries lists the relabelings (offset, reflected) of a vertex's edges which leave its corners unchanged,
+// edge j being relabeled offset + j, or offset - j if reflected
+fn symmetries(corners: &[Corner]) -> Vec<(usize, bool)> {
+    let size = corners.len();
+    (1..size)
+        .map(|offset| (offset, false))
+        .filter(|(offset, _)| (0..size).all(|j| corners[(j + offset) % size] == corners[j]))
+        .chain(
+            (0..size)
+                .map(|offset| (offset, true))
+                .filter(|(offset, _)| (0..size).all(|j| corners[(offset + 2 * size - j - 1) % size] == corners[j]))
+        )
+        .collect()
+}
+
+// Chamber is one side of an edge at a vertex: (vertex index, edge index, whether it's the side of the edge's tile
+// rather than that of the preceding edge's tile). Tilings are searched for by pairing up edges so that the tiles on
+// either side of each edge agree and walking around any tile returns to where it started.
+type Chamber = (usize, usize, bool);
+
+// Pairing is the other end of an edge: (vertex index, edge index, parity)
+type Pairing = (usize, usize, bool);
+
+struct Search {
+    vertices: Vec<Vec<Corner>>,
+    // index of the configuration vertex each of vertices repeats
+    origins: Vec<usize>,
+    symmetries: Vec<Vec<(usize, bool)>>,
+    sizes: Vec<usize>,
+    edges: Vec<Vec<Option<Pairing>>>,
+    // number of complete pairings recorded so far, the search stops once it has max_solutions of them
+    solutions: usize,
+    max_solutions: usize,
+    steps: usize,
+    limit: usize,
+}
+
+impl Search {
+    // run pairs up edges until max_solutions complete pairings are recorded by record, which returns whether it
+    // recorded the pairing it's given, and returns whether they were
+    fn run(&mut self, record: &mut dyn FnMut(&Search, &[Vec<Pairing>]) -> bool) -> bool {
+        // the edge with the fewest possible pairings is paired first so that dead ends are found early, and
+        // only edges of vertices which have already been reached are considered so that the tiling stays connected
+        let reached = self.edges.iter().any(|edges| edges.iter().any(Option::is_some));
+        let mut next: Option<((usize, usize), Vec<Pairing>)> = None;
+        for i in 0..self.vertices.len() {
+            if reached == self.is_unpaired(i) {
+                continue
+            }
+            for j in 0..self.vertices[i].len() {
+                if self.edges[i][j].is_some() {
+                    continue
+                }
+                let pairings = self.pairings((i, j));
+                if self.steps > self.limit {
+                    return false
+                }
+                if next.as_ref().map_or(true, |(_, next_pairings)| pairings.len() < next_pairings.len()) {
+                    next = Some(((i, j), pairings));
+                }
+            }
+            if !reached {
+                break
+            }
+        }
+
+        let ((i, j), pairings) = match next {
+            None => {
+                if self.is_connected() {
+                    let edges = self.edges.iter().map(|edges| edges.iter().flatten().cloned().collect()).collect::<Vec<Vec<Pairing>>>();
+                    if record(self, &edges) {
+                        self.solutions += 1;
+                    }
+                }
+                return self.solutions >= self.max_solutions
+            },
+            Some(next) => next,
+        };
+        for (n, k, parity) in pairings {
+            self.edges[i][j] = Some((n, k, parity));
+            self.edges[n][k] = Some((i, j, parity));
+            if self.run(record) {
+                return true
+            }
+            self.edges[i][j] = None;
+            self.edges[n][k] = None;
+        }
+
+        false
+    }
+
+    // pairings lists the edges (n, k) and parities edge (i, j) can be paired with
+    fn pairings(&mut self, (i, j): (usize, usize)) -> Vec<Pairing> {
+        let mut pairings = vec![];
+        for n in 0..self.vertices.len() {
+            // repeats of a vertex which haven't been paired with anything yet are interchangeable
+            if n > 0 && n - 1 != i && self.origins[n] == self.origins[n - 1] && self.is_unpaired(n) && self.is_unpaired(n - 1) {
+                continue
+            }
+            for k in 0..self.vertices[n].len() {
+                if self.edges[n][k].is_some() && (n, k) != (i, j) {
+                    continue
+                }
+                for parity in [false, true].iter().cloned() {
+                    // an unpaired vertex can be relabeled by any of its symmetries, so only the least of each
+                    // set of equivalent ways of pairing with it is tried
+                    if n != i && self.is_unpaired(n) && !self.is_least(n, k, parity) {
+                        continue
+                    }
+                    self.steps += 1;
+                    if !self.is_compatible((i, j), (n, k), parity) {
+                        continue
+                    }
+
+                    self.edges[i][j] = Some((n, k, parity));
+                    self.edges[n][k] = Some((i, j, parity));
+                    let chambers = [(i, j, true), (i, j, false), (n, k, true), (n, k, false)];
+                    if chambers.iter().all(|chamber| self.is_closable(chamber)) {
+                        pairings.push((n, k, parity));
+                    }
+                    self.edges[i][j] = None;
+                    self.edges[n][k] = None;
+                }
+            }
+        }
+        pairings
+    }
+
+    // is_least determines whether pairing edge k of vertex n with the given parity comes first among
+    // the pairings it's equivalent to under n's symmetries
+    fn is_least(&self, n: usize, k: usize, parity: bool) -> bool {
+        let size = self.vertices[n].len();
+        self.symmetries[n].iter().all(|(offset, reflected)| {
+            if *reflected { (k, parity) <= ((offset + size - k) % size, !parity) } else { k <= (k + offset) % size }
+        })
+    }
+
+    fn is_unpaired(&self, i: usize) -> bool {
+        self.edges[i].iter().all(Option::is_none)
+    }
+
+    // corner returns the corner of the tile on chamber's side of its edge
+    fn corner(&self, (i, j, forward): Chamber) -> Corner {
+        let corners = &self.vertices[i];
+        if forward { corners[j] } else { corners[(j + corners.len() - 1) % corners.len()] }
+    }
+
+    // across returns the chamber at the other end of chamber's edge on the same tile
+    fn across(&self, (i, j, forward): Chamber) -> Option<Chamber> {
+        self.edges[i][j].map(|(n, k, parity)| (n, k, if parity { forward } else { !forward }))
+    }
+
+    // around returns the chamber on the same tile and vertex as chamber but along the tile's other edge
+    fn around(&self, (i, j, forward): Chamber) -> Chamber {
+        let size = self.vertices[i].len();
+        if forward { (i, (j + 1) % size, false) } else { (i, (j + size - 1) % size, true) }
+    }
+
+    // is_compatible determines whether the tiles on either side of edge (i, j) continue on to (n, k)
+    fn is_compatible(&self, (i, j): (usize, usize), (n, k): (usize, usize), parity: bool) -> bool {
+        [true, false].iter().all(|forward| {
+            let corner = self.corner((i, j, *forward));
+            let neighbor_corner = self.corner((n, k, if parity { *forward } else { !*forward }));
+            corner.polygon_index == neighbor_corner.polygon_index && corner.kind.next() == neighbor_corner.kind
+        })
+    }
+
+    // is_closable determines whether walking around chamber's tile could return to chamber after visiting
+    // each of the tile's points, walks cut short by an unpaired edge are given the benefit of the doubt
+    fn is_closable(&self, chamber: &Chamber) -> bool {
+        let size = self.sizes[self.corner(*chamber).polygon_index];
+        let mut current = *chamber;
+        for step in 1..=size {
+            current = match self.across(current) {
+                None => return true,
+                Some(across) => self.around(across),
+            };
+            if current == *chamber {
+                return size % step == 0
+            }
+        }
+        false
+    }
+
+    fn is_connected(&self) -> bool {
+        let mut reached = vec![false; self.vertices.len()];
+        let mut stack = vec![0];
+        reached[0] = true;
+        while let Some(i) = stack.pop() {
+            for (n, _, _) in self.edges[i].iter().flatten() {
+                if !reached[*n] {
+                    reached[*n] = true;
+                    stack.push(*n);
+                }
+            }
+        }
+        reached.into_iter().all(|reached| reached)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{symmetry, validate, Atlas, WallpaperGroup};
+    use geometry::Point;
+
+    fn assert_tiles(notation: &str) -> Vec<FullAtlasVertexPost> {
+        let configuration = VertexConfiguration::parse(notation).unwrap();
+        let vertices = configuration.to_vertex_posts().expect(notation);
+        let validation = validate(&configuration.tiles(), &vertices);
+        assert!(validation.valid, "{}: {}", notation, validation);
+        vertices
+    }
+
+    #[test]
+    fn test_vertex_configuration_parse() {
+        let configuration = VertexConfiguration::parse("[3^6; 3.3.4.3.4]").unwrap();
+        assert_eq!(vec![NotationPolygon::Regular { sides: 3 }, NotationPolygon::Regular { sides: 4 }], configuration.polygons);
+        assert_eq!(vec![6, 5], configuration.vertices.iter().map(Vec::len).collect::<Vec<usize>>());
+
+        let configuration = VertexConfiguration::parse("4.6apio6.6aapio2.6apio6").unwrap();
+        assert_eq!(3, configuration.polygons.len());
+        assert_eq!(NotationPolygon::Star { points: 6, angle: PI / 6. }, configuration.polygons[1]);
+        assert_eq!(
+            vec![CornerKind::Regular, CornerKind::Tip, CornerKind::Dent, CornerKind::Tip],
+            configuration.vertices[0].iter().map(|corner| corner.kind).collect::<Vec<CornerKind>>(),
+        );
+
+        assert_eq!(Err(NotationError::InvalidPolygon { polygon: String::from("x") }), VertexConfiguration::parse("3.x").map(|_| ()));
+        assert_eq!(Err(NotationError::InvalidPolygon { polygon: String::from("4apio2") }), VertexConfiguration::parse("4apio2").map(|_| ()));
+    }
+
+    #[test]
+    fn test_vertex_configuration_to_vertex_posts() {
+        for notation in [
+            "3.3.3.3.3.3",
+            "4.4.4.4",
+            "6.6.6",
+            "3.3.3.3.6",
+            "3.3.3.4.4",
+            "3.3.4.3.4",
+            "3.4.6.4",
+            "3.6.3.6",
+            "3.12.12",
+            "4.6.12",
+            "4.8.8",
+            "[3^6; 3^2.4.3.4]",
+            "[3^6; 3^2.6^2]",
+            "[3^3.4^2; 4^4]",
+            "[3.4.6.4; 4.6.12]",
+            "[3.4^2.6; 3.6.3.6]",
+            "[4.6apio6.6aapio2.6apio6; 4.6aapio6; 6apio2.6aapio6]",
+        ].iter() {
+            assert_tiles(notation);
+        }
+
+        // 4.6.12 is achiral, the mirrors of p6m running along each of its edges. Its vertex stars have no mirror of their
+        // own, as their polygons all differ, so the vertex star across each edge is the reflection of the one it's from.
+        let vertices = assert_tiles("4.6.12");
+        assert!(vertices.iter().all(|vertex| vertex.edges.iter().all(|edge| edge.parity)));
+        let atlas = Atlas::from_vertex_posts(&VertexConfiguration::parse("4.6.12").unwrap().tiles(), &vertices).unwrap();
+        assert_eq!(WallpaperGroup::P6m, symmetry(&atlas).unwrap().group);
+
+        // 3^4.6 is chiral, so no vertex star is the reflection of another
+        let vertices = assert_tiles("3^4.6");
+        assert!(vertices.iter().all(|vertex| vertex.edges.iter().all(|edge| !edge.parity)));
+        let atlas = Atlas::from_vertex_posts(&VertexConfiguration::parse("3^4.6").unwrap().tiles(), &vertices).unwrap();
+        assert_eq!(WallpaperGroup::P6, symmetry(&atlas).unwrap().group);
+
+        // the 3^6 vertices' rotational symmetry means no single 3^2.4.3.4 atlas vertex can be consistently connected to them
+        assert_eq!(7, assert_tiles("[3^6; 3^2.4.3.4]").len());
+
+        // a star's tips and dents are always separate vertices, so 4.6apio6.6aapio2.6apio6 alone doesn't tile
+        assert_eq!(Err(NotationError::NoTiling), VertexConfiguration::parse("4.6apio6.6aapio2.6apio6").unwrap().to_vertex_posts().map(|_| ()));
+
+        let result = VertexConfiguration::parse("3.4.4.4").unwrap().to_vertex_posts().map(|_| ());
+        assert!(
+            matches!(result, Err(NotationError::AngleSumMismatch { vertex_index: 0, degrees }) if degrees.approx_eq(330., DEFAULT_F64_MARGIN)),
+            "{:?}", result,
+        );
+        assert_eq!(Err(NotationError::NoTiling), VertexConfiguration::parse("3.7.42").unwrap().to_vertex_posts().map(|_| ()));
+    }
+
+    #[test]
+    fn test_vertex_configuration_enumerate_vertex_posts() {
+        let configuration = VertexConfiguration::parse("4.8.8").unwrap();
+        let (atlases, is_exhausted) = configuration.enumerate_vertex_posts(3, MAX_SEARCH_STEPS, |_| true).unwrap();
+        assert_eq!(3, atlases.len());
+        assert!(!is_exhausted);
+        for vertices in atlases.iter() {
+            assert!(validate(&configuration.tiles(), vertices).valid);
+        }
+
+        // only accepted atlases are counted
+        let mut count = 0;
+        let (atlases, _) = configuration.enumerate_vertex_posts(2, MAX_SEARCH_STEPS, |vertices| { count += 1; vertices.len() > 1 }).unwrap();
+        assert!(count > 2);
+        assert!(atlases.iter().all(|vertices| vertices.len() > 1));
+        assert_eq!(Err(NotationError::NoTiling), configuration.enumerate_vertex_posts(1, MAX_SEARCH_STEPS, |_| false).map(|_| ()));
+
+        // a search which runs its course says so
+        let configuration = VertexConfiguration::parse("6^3").unwrap();
+        let (atlases, is_exhausted) = configuration.enumerate_vertex_posts(usize::MAX, MAX_SEARCH_STEPS, |_| true).unwrap();
+        assert!(!atlases.is_empty());
+        assert!(is_exhausted);
+
+        // a search cut short before finding a tiling says so rather than that there's none
+        let configuration = VertexConfiguration::parse("[3^6; 3^2.4.3.4]").unwrap();
+        assert_eq!(Err(NotationError::SearchLimit { max_steps: 10 }), configuration.enumerate_vertex_posts(1, 10, |_| true).map(|_| ()));
+    }
+
+    #[test]
+    fn test_vertex_configuration_display() {
+        for notation in ["3^6", "3^2.4.3.4", "[3^6; 3^2.4.3.4]", "[4.6apio6.6aapio2.6apio6; 4.6aapio6; 6apio2.6aapio6]"].iter() {
+            assert_eq!(*notation, VertexConfiguration::parse(notation).unwrap().to_string());
+        }
+        assert_eq!("3^4.6", VertexConfiguration::parse("3.3.3.3.6").unwrap().to_string());
+    }
+
+    #[test]
+    fn test_notation_polygon_from_tile() {
+        for polygon in [NotationPolygon::Regular { sides: 3 }, NotationPolygon::Regular { sides: 12 }, NotationPolygon::Star { points: 6, angle: PI / 6. }].iter() {
+            assert!(NotationPolygon::from_tile(&polygon.tile()).map_or(false, |from_tile| from_tile.approx_eq(polygon)), "{:?}", polygon);
+        }
+        assert_eq!(None, NotationPolygon::from_tile(&regular_polygon(2., 4)));
+        assert_eq!(None, NotationPolygon::from_tile(&Tile::new(vec![Point(0., 0.), Point(2., 0.), Point(2., 1.), Point(0., 1.)])));
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{NotationError, NotationPolygon};
+use geometry::Point;
+use models::{FullAtlasEdgePost, FullAtlasVertexPost};
+use std::{cmp::Ordering, collections::HashMap, f64::consts::{PI, TAU}};
+use tile::Tile;
+
+// distance within which two points are considered the same
+pub(crate) const POINT_MARGIN: f64 = 0.000_01;
+
+// upper bound on the number of tiles or symmetries generated while developing a tiling
+pub(crate) const MAX_GENERATED: usize = 100_000;
+
+// PointMap looks up values by point, treating points within POINT_MARGIN of each other as the same
+pub(crate) struct PointMap<V> {
+    buckets: HashMap<(i64, i64), Vec<(Point, V)>>,
+}
+
+impl<V> PointMap<V> {
+    pub(crate) fn new() -> PointMap<V> {
+        PointMap { buckets: HashMap::new() }
+    }
+
+    fn bucket(point: &Point) -> (i64, i64) {
+        ((point.0 / POINT_MARGIN / 10.).floor() as i64, (point.1 / POINT_MARGIN / 10.).floor() as i64)
+    }
+
+    fn find(&self, point: &Point) -> Option<((i64, i64), usize)> {
+        let (x, y) = PointMap::<V>::bucket(point);
+        for key in [(x - 1, y - 1), (x - 1, y), (x - 1, y + 1), (x, y - 1), (x, y), (x, y + 1), (x + 1, y - 1), (x + 1, y), (x + 1, y + 1)].iter() {
+            if let Some(index) = self.buckets.get(key).and_then(|entries| entries.iter().position(|(other, _)| (other - point).norm() < POINT_MARGIN)) {
+                return Some((*key, index))
+            }
+        }
+        None
+    }
+
+    pub(crate) fn get(&self, point: &Point) -> Option<&V> {
+        self.find(point).map(|(key, index)| &self.buckets[&key][index].1)
+    }
+
+    pub(crate) fn get_mut(&mut self, point: &Point) -> Option<&mut V> {
+        match self.find(point) {
+            None => None,
+            Some((key, index)) => self.buckets.get_mut(&key).map(|entries| &mut entries[index].1),
+        }
+    }
+
+    // insert adds value at point unless there's already a value there, returning whether it was added
+    pub(crate) fn insert(&mut self, point: Point, value: V) -> bool {
+        if self.find(&point).is_some() {
+            return false
+        }
+        self.buckets.entry(PointMap::<V>::bucket(&point)).or_default().push((point, value));
+        true
+    }
+
+    pub(crate) fn points(&self) -> Vec<Point> {
+        self.buckets.values().flatten().map(|(point, _)| *point).collect()
+    }
+}
+
+// periodic_vertex_posts derives an atlas of a periodic tiling of polygons, all of them Regular, from its translations, the
+// shortest two independent ones, and its tiles, which must cover the disc of periodic_radius about the origin. The atlas
+// has one vertex per vertex of the tiling's unit cell, so that every atlas vertex has the same orientation.
+pub(crate) fn periodic_vertex_posts(
+    polygons: &[NotationPolygon],
+    tiles: &[Tile],
+    translations: (Point, Point),
+) -> Result<Vec<FullAtlasVertexPost>, NotationError> {
+    periodic_corner_vertex_posts(tiles, translations, |tile_index, _| {
+        let sides = tiles[tile_index].size();
+        let polygon_index = polygons.iter().position(|polygon| *polygon == NotationPolygon::Regular { sides }).ok_or(NotationError::NoTiling)?;
+        Ok((polygon_index, 0))
+    })
+}
+
+// periodic_corner_vertex_posts is periodic_vertex_posts for tiles of any polygons with their points in anticlockwise
+// order, where corner returns the polygon index and point index of the given point of the given tile
+pub(crate) fn periodic_corner_vertex_posts(
+    tiles: &[Tile],
+    (translation0, translation1): (Point, Point),
+    corner: impl Fn(usize, usize) -> Result<(usize, usize), NotationError>,
+) -> Result<Vec<FullAtlasVertexPost>, NotationError> {
+    let core_radius = translation0.norm() + translation1.norm() + 1.;
+    let stars = vertex_stars(tiles);
+
+    let fractional = |point: &Point| {
+        let determinant = translation0.0 * translation1.1 - translation0.1 * translation1.0;
+        let x = (point.0 * translation1.1 - point.1 * translation1.0) / determinant;
+        let y = (translation0.0 * point.1 - translation0.1 * point.0) / determinant;
+        (x - x.floor(), y - y.floor())
+    };
+    let is_same_class = |a: (f64, f64), b: (f64, f64)| {
+        let wrap = |d: f64| d.abs().min(1. - d.abs());
+        wrap(a.0 - b.0) < POINT_MARGIN && wrap(a.1 - b.1) < POINT_MARGIN
+    };
+
+    let mut points = stars.points().into_iter().filter(|point| point.norm() <= core_radius).collect::<Vec<Point>>();
+    points.sort_by(cmp_points);
+    let mut classes: Vec<(Point, (f64, f64))> = vec![];
+    for point in points {
+        let class = fractional(&point);
+        if !classes.iter().any(|(_, other)| is_same_class(class, *other)) {
+            classes.push((point, class));
+        }
+    }
+    let class_of = |point: &Point| {
+        let class = fractional(point);
+        classes.iter().position(|(_, other)| is_same_class(class, *other)).ok_or(NotationError::NoTiling)
+    };
+
+    let rays = classes
+        .iter()
+        .map(|(point, _)| vertex_rays(tiles, &stars, point))
+        .collect::<Result<Vec<Vec<Ray>>, NotationError>>()?;
+
+    let mut vertices: Vec<FullAtlasVertexPost> = Vec::with_capacity(classes.len());
+    for vertex_rays in rays.iter() {
+        let mut edges: Vec<FullAtlasEdgePost> = Vec::with_capacity(vertex_rays.len());
+        for ray in vertex_rays.iter() {
+            let neighbor_index = class_of(&ray.neighbor)?;
+            let back = normalize_angle(ray.angle + PI);
+            let neighbor_edge_index = rays[neighbor_index]
+                .iter()
+                .position(|neighbor_ray| angle_distance(neighbor_ray.angle, back) < POINT_MARGIN)
+                .ok_or(NotationError::NoTiling)?;
+            let (polygon_index, point_index) = corner(ray.tile_index, ray.point_index)?;
+            edges.push(FullAtlasEdgePost {
+                polygon_index,
+                point_index,
+                neighbor_index,
+                neighbor_edge_index,
+                parity: false,
+            });
+        }
+        vertices.push(FullAtlasVertexPost { edges });
+    }
+
+    Ok(vertices)
+}
+
+// periodic_radius is the radius of the disc about the origin which periodic_vertex_posts needs tiles to cover
+pub(crate) fn periodic_radius((translation0, translation1): (Point, Point)) -> f64 {
+    2. * (translation0.norm() + translation1.norm() + 5.)
+}
+
+// Ray is an edge leaving a vertex along with the tile anticlockwise of it and the index of the vertex among its points
+struct Ray {
+    angle: f64,
+    neighbor: Point,
+    tile_index: usize,
+    point_index: usize,
+}
+
+// vertex_stars maps each vertex of tiles to the tiles it's a point of, along with its index in each
+fn vertex_stars(tiles: &[Tile]) -> PointMap<Vec<(usize, usize)>> {
+    let mut stars: PointMap<Vec<(usize, usize)>> = PointMap::new();
+    for (tile_index, tile) in tiles.iter().enumerate() {
+        for (point_index, point) in tile.points.iter().enumerate() {
+            match stars.get_mut(point) {
+                Some(star) => star.push((tile_index, point_index)),
+                None => { stars.insert(*point, vec![(tile_index, point_index)]); },
+            }
+        }
+    }
+    stars
+}
+
+// vertex_rays lists the rays of the vertex at point in anticlockwise order from the positive x-axis
+fn vertex_rays(tiles: &[Tile], stars: &PointMap<Vec<(usize, usize)>>, point: &Point) -> Result<Vec<Ray>, NotationError> {
+    let star = stars.get(point).ok_or(NotationError::NoTiling)?;
+    let angle: f64 = star.iter().map(|(tile_index, point_index)| tiles[*tile_index].angle(*point_index)).sum();
+    if angle > TAU + POINT_MARGIN {
+        return Err(NotationError::Overlap)
+    }
+    if angle < TAU - POINT_MARGIN {
+        return Err(NotationError::NoTiling)
+    }
+
+    let mut rays = star
+        .iter()
+        .map(|(tile_index, point_index)| {
+            let tile = &tiles[*tile_index];
+            let neighbor = tile.points[(point_index + 1) % tile.size()];
+            let direction = &neighbor - point;
+            Ray { angle: normalize_angle(direction.1.atan2(direction.0)), neighbor, tile_index: *tile_index, point_index: *point_index }
+        })
+        .collect::<Vec<Ray>>();
+    rays.sort_by(|a, b| a.angle.partial_cmp(&b.angle).unwrap_or(Ordering::Equal));
+    Ok(rays)
+}
+
+// regular_tile returns the regular polygon with unit sides which has an edge leaving point at angle, lying anticlockwise
+// of it when orientation is 1 and clockwise of it when orientation is -1
+pub(crate) fn regular_tile(point: &Point, angle: f64, orientation: f64, sides: usize) -> Tile {
+    let circumradius = 0.5 / (PI / sides as f64).sin();
+    let centroid = point + &direction(angle + orientation * interior_angle(sides) / 2.).mul(circumradius);
+    let start = (point - &centroid).1.atan2((point - &centroid).0);
+    Tile::new((0..sides).map(|k| &centroid + &direction(start + TAU * k as f64 / sides as f64).mul(circumradius)).collect())
+}
+
+pub(crate) fn tile_edges(tile: &Tile) -> Vec<(Point, Point)> {
+    (0..tile.size()).map(|i| (tile.points[i], tile.points[(i + 1) % tile.size()])).collect()
+}
+
+pub(crate) fn interior_angle(sides: usize) -> f64 {
+    PI * (1. - 2. / sides as f64)
+}
+
+pub(crate) fn midpoint(a: &Point, b: &Point) -> Point {
+    (a + b).mul(0.5)
+}
+
+pub(crate) fn direction(angle: f64) -> Point {
+    Point(angle.cos(), angle.sin())
+}
+
+pub(crate) fn cross(a: &Point, b: &Point) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+// normalize_angle maps an angle in radians into [0, τ), treating angles just short of τ as 0
+pub(crate) fn normalize_angle(angle: f64) -> f64 {
+    let angle = angle.rem_euclid(TAU);
+    if TAU - angle < POINT_MARGIN { 0. } else { angle }
+}
+
+pub(crate) fn angle_distance(a: f64, b: f64) -> f64 {
+    let distance = (a - b).rem_euclid(TAU);
+    distance.min(TAU - distance)
+}
+
+// cmp_points orders points by distance from the origin, then anticlockwise from the positive x-axis
+pub(crate) fn cmp_points(a: &Point, b: &Point) -> Ordering {
+    if (a.norm() - b.norm()).abs() < POINT_MARGIN {
+        normalize_angle(a.1.atan2(a.0)).partial_cmp(&normalize_angle(b.1.atan2(b.0))).unwrap_or(Ordering::Equal)
+    } else {
+        a.norm().partial_cmp(&b.norm()).unwrap_or(Ordering::Equal)
+    }
+}
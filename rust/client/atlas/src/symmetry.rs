@@ -0,0 +1,488 @@
+use crate::{Atlas, NotationError};
+use crate::delaney::labelled_symbol;
+use crate::periodic::{cmp_points, cross, normalize_angle, PointMap, MAX_GENERATED, POINT_MARGIN};
+use geometry::{Affine, Euclid, Point, Transform, Transformable, IDENTITY_AFFINE, ORIGIN};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+// upper bound on the radius, in units of the atlas' largest tile, its tiling is developed within while looking for its symmetries
+const MAX_SYMMETRY_RADIUS: f64 = 64.;
+
+// WallpaperGroup is one of the 17 symmetry groups of periodic tilings of the plane, named in short Hermann-Mauguin notation
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum WallpaperGroup {
+    #[serde(rename = "p1")]
+    P1,
+    #[serde(rename = "p2")]
+    P2,
+    #[serde(rename = "pm")]
+    Pm,
+    #[serde(rename = "pg")]
+    Pg,
+    #[serde(rename = "cm")]
+    Cm,
+    #[serde(rename = "pmm")]
+    Pmm,
+    #[serde(rename = "pmg")]
+    Pmg,
+    #[serde(rename = "pgg")]
+    Pgg,
+    #[serde(rename = "cmm")]
+    Cmm,
+    #[serde(rename = "p4")]
+    P4,
+    #[serde(rename = "p4m")]
+    P4m,
+    #[serde(rename = "p4g")]
+    P4g,
+    #[serde(rename = "p3")]
+    P3,
+    #[serde(rename = "p3m1")]
+    P3m1,
+    #[serde(rename = "p31m")]
+    P31m,
+    #[serde(rename = "p6")]
+    P6,
+    #[serde(rename = "p6m")]
+    P6m,
+}
+
+// WALLPAPER_GROUPS lists each wallpaper group with its name and its orbifold symbol as written by DelaneySymbol::orbifold
+const WALLPAPER_GROUPS: [(WallpaperGroup, &str, &str); 17] = [
+    (WallpaperGroup::P1, "p1", "o"),
+    (WallpaperGroup::P2, "p2", "2222"),
+    (WallpaperGroup::Pm, "pm", "**"),
+    (WallpaperGroup::Pg, "pg", "xx"),
+    (WallpaperGroup::Cm, "cm", "*x"),
+    (WallpaperGroup::Pmm, "pmm", "*2222"),
+    (WallpaperGroup::Pmg, "pmg", "22*"),
+    (WallpaperGroup::Pgg, "pgg", "22x"),
+    (WallpaperGroup::Cmm, "cmm", "2*22"),
+    (WallpaperGroup::P4, "p4", "442"),
+    (WallpaperGroup::P4m, "p4m", "*442"),
+    (WallpaperGroup::P4g, "p4g", "4*2"),
+    (WallpaperGroup::P3, "p3", "333"),
+    (WallpaperGroup::P3m1, "p3m1", "*333"),
+    (WallpaperGroup::P31m, "p31m", "3*3"),
+    (WallpaperGroup::P6, "p6", "632"),
+    (WallpaperGroup::P6m, "p6m", "*632"),
+];
+
+impl WallpaperGroup {
+    // parse returns the group with the given name, e.g. p4m
+    pub fn parse(name: &str) -> Option<WallpaperGroup> {
+        WALLPAPER_GROUPS.iter().find(|(_, other, _)| *other == name).map(|(group, _, _)| *group)
+    }
+
+    // from_orbifold returns the group with the given Conway orbifold symbol, e.g. *442, with x for crosscaps
+    pub fn from_orbifold(orbifold: &str) -> Option<WallpaperGroup> {
+        WALLPAPER_GROUPS.iter().find(|(_, _, other)| *other == orbifold).map(|(group, _, _)| *group)
+    }
+
+    pub fn name(&self) -> &'static str {
+        WALLPAPER_GROUPS.iter().find(|(group, _, _)| group == self).unwrap().1
+    }
+
+    pub fn orbifold(&self) -> &'static str {
+        WALLPAPER_GROUPS.iter().find(|(group, _, _)| group == self).unwrap().2
+    }
+
+    // point_group_order returns the number of the group's symmetries fixing a point, up to translation, which is also the
+    // number of fundamental domains making up a unit cell of its lattice
+    pub fn point_group_order(&self) -> usize {
+        match self {
+            WallpaperGroup::P1 => 1,
+            WallpaperGroup::P2 | WallpaperGroup::Pm | WallpaperGroup::Pg | WallpaperGroup::Cm => 2,
+            WallpaperGroup::P3 => 3,
+            WallpaperGroup::Pmm | WallpaperGroup::Pmg | WallpaperGroup::Pgg | WallpaperGroup::Cmm | WallpaperGroup::P4 => 4,
+            WallpaperGroup::P3m1 | WallpaperGroup::P31m | WallpaperGroup::P6 => 6,
+            WallpaperGroup::P4m | WallpaperGroup::P4g => 8,
+            WallpaperGroup::P6m => 12,
+        }
+    }
+}
+
+impl std::fmt::Display for WallpaperGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// Symmetry describes the symmetries of an atlas' tiling, placed with proto vertex star 0 at the origin as it's
+// placed in the atlas
+#[derive(Clone)]
+pub struct Symmetry {
+    pub group: WallpaperGroup,
+    // translations are the shortest two independent translations of the tiling, which generate its lattice
+    pub translations: (Point, Point),
+    // generators are a rotation through the least angle of any of the tiling's rotations and a reflection, or a glide
+    // reflection if the tiling has no reflections, whichever of these the tiling has; together with the translations
+    // they generate the group. Of the candidates, the ones with their center or axis nearest the origin are chosen.
+    pub generators: Vec<Affine>,
+    // vertex_star_orbits[i] is the orbit of proto vertex star i's vertex, orbits numbered in order of first appearance
+    pub vertex_star_orbits: Vec<usize>,
+    // tile_orbits[i][j] is the orbit of tile j of proto vertex star i, orbits numbered in order of first appearance
+    pub tile_orbits: Vec<Vec<usize>>,
+}
+
+// PlacedStar is a proto vertex star placed in the plane: (proto vertex star index, affine placing its tiles)
+pub(crate) type PlacedStar = (usize, Affine);
+
+// symmetry finds the symmetries of an atlas' tiling. Its group is read off of the orbifold of the atlas' symbol and the
+// isometries are found by developing the atlas about the origin and matching the flag of proto vertex star 0's first edge
+// and tile against every flag the tiling's symmetries map it to.
+pub fn symmetry(atlas: &Atlas) -> Result<Symmetry, NotationError> {
+    let (symbol, labels) = labelled_symbol(atlas)?;
+    let group = WallpaperGroup::from_orbifold(&symbol.labelled_minimal(&labels).0.orbifold()).ok_or(NotationError::NoTiling)?;
+    let classes = symbol.labelled_classes(&labels);
+
+    let offsets = chamber_offsets(atlas);
+    let scale = tile_scale(atlas);
+    let seed_inverse = flag(&atlas.proto_vertex_stars[0].tiles[0].points[1], 1.).inverse();
+
+    let mut radius = 4. * scale;
+    loop {
+        if radius > MAX_SYMMETRY_RADIUS * scale {
+            return Err(NotationError::NoTiling)
+        }
+        let (stars, points) = develop(atlas, radius + scale)?;
+
+        let mut symmetries: Vec<Affine> = vec![];
+        for (i, placement) in stars.iter() {
+            if ORIGIN.transform(placement).norm() > radius {
+                continue
+            }
+            let star = &atlas.proto_vertex_stars[*i];
+            for (j, tile) in star.tiles.iter().enumerate() {
+                for (side, (point, orientation)) in [(&tile.points[1], 1.), (&tile.points[tile.size() - 1], -1.)].iter().enumerate() {
+                    if classes[offsets[*i] + 2 * j + side] == classes[0] {
+                        symmetries.push(seed_inverse.transform(&flag(point, *orientation).transform(placement)));
+                    }
+                }
+            }
+        }
+
+        let mut translations = symmetries
+            .iter()
+            .filter(|symmetry| is_translation(symmetry))
+            .map(|symmetry| Point(symmetry.1[0], symmetry.1[1]))
+            .collect::<Vec<Point>>();
+        translations.sort_by(cmp_points);
+        let translations = translations.first().and_then(|translation0| {
+            translations
+                .iter()
+                .find(|translation1| cross(translation0, translation1).abs() > POINT_MARGIN)
+                .map(|translation1| (*translation0, *translation1))
+        });
+        let translations = match translations {
+            Some(translations) => translations,
+            None => { radius *= 2.; continue },
+        };
+
+        // every orbit has a member close enough to the origin that a symmetry moving the origin no farther than the
+        // translations' sum maps it onto each of its other members close to the origin
+        let mut first_placements: Vec<Option<&PlacedStar>> = vec![None; atlas.proto_vertex_stars.len()];
+        for placed_star in stars.iter() {
+            first_placements[placed_star.0].get_or_insert(placed_star);
+        }
+        let reach = first_placements
+            .iter()
+            .map(|placed_star| placed_star.map(|(_, placement)| ORIGIN.transform(placement).norm()))
+            .try_fold(0., |reach: f64, norm| norm.map(|norm| reach.max(norm)));
+        let required_radius = match reach {
+            Some(reach) => translations.0.norm() + translations.1.norm() + reach + scale,
+            None => 2. * radius,
+        };
+        if required_radius > radius {
+            radius = required_radius;
+            continue
+        }
+        let first_placements = first_placements.into_iter().flatten().collect::<Vec<&PlacedStar>>();
+
+        let generators = vec![
+            least(&symmetries, |symmetry| !symmetry.is_flip() && !is_translation(symmetry), rotation_key),
+            least(&symmetries, Affine::is_flip, reflection_key),
+        ];
+
+        let mut vertex_star_parents = (0..atlas.proto_vertex_stars.len()).collect::<Vec<usize>>();
+        for (i, placement) in first_placements.iter() {
+            let point = ORIGIN.transform(placement);
+            for symmetry in symmetries.iter() {
+                if let Some(k) = points.get(&point.transform(symmetry)) {
+                    union(&mut vertex_star_parents, *i, stars[*k].0);
+                }
+            }
+        }
+
+        // tiles are numbered in order of proto vertex star, then of their index in it
+        let tile_offsets = offsets.iter().map(|offset| offset / 2).collect::<Vec<usize>>();
+        let mut tile_parents = (0..symbol.size() / 2).collect::<Vec<usize>>();
+        let mut centroids: PointMap<usize> = PointMap::new();
+        for (i, placement) in stars.iter() {
+            for (j, tile) in atlas.proto_vertex_stars[*i].tiles.iter().enumerate() {
+                let centroid = tile.centroid.transform(placement);
+                match centroids.get(&centroid) {
+                    Some(other) => union(&mut tile_parents, tile_offsets[*i] + j, *other),
+                    None => { centroids.insert(centroid, tile_offsets[*i] + j); },
+                }
+            }
+        }
+        for (i, placement) in first_placements.iter() {
+            for (j, tile) in atlas.proto_vertex_stars[*i].tiles.iter().enumerate() {
+                let centroid = tile.centroid.transform(placement);
+                for symmetry in symmetries.iter() {
+                    if let Some(other) = centroids.get(&centroid.transform(symmetry)) {
+                        union(&mut tile_parents, tile_offsets[*i] + j, *other);
+                    }
+                }
+            }
+        }
+        let tile_orbits = number_orbits(&mut tile_parents);
+
+        return Ok(Symmetry {
+            group,
+            translations,
+            generators: generators.into_iter().flatten().collect(),
+            vertex_star_orbits: number_orbits(&mut vertex_star_parents),
+            tile_orbits: atlas.proto_vertex_stars
+                .iter()
+                .zip(tile_offsets.iter())
+                .map(|(star, offset)| tile_orbits[*offset..offset + star.size()].to_vec())
+                .collect(),
+        })
+    }
+}
+
+// develop places the atlas' proto vertex stars outward from proto vertex star 0 as it's placed in the atlas, skipping
+// vertices farther than radius from the origin. It returns the placed stars along with the index of the star at each point.
+pub(crate) fn develop(atlas: &Atlas, radius: f64) -> Result<(Vec<PlacedStar>, PointMap<usize>), NotationError> {
+    let mut stars: Vec<PlacedStar> = vec![(0, IDENTITY_AFFINE)];
+    let mut points: PointMap<usize> = PointMap::new();
+    points.insert(ORIGIN, 0);
+
+    let mut index = 0;
+    while index < stars.len() {
+        let (i, placement) = stars[index];
+        let point = ORIGIN.transform(&placement);
+        let star = &atlas.proto_vertex_stars[i];
+        for (tile, proto_neighbor) in star.tiles.iter().zip(star.proto_neighbors.iter()) {
+            let neighbor_point = tile.points[1].transform(&placement);
+            if neighbor_point.norm() > radius || points.get(&neighbor_point).is_some() {
+                continue
+            }
+
+            // the neighbor's edge back to this star is turned to point at this star's vertex, reflecting the neighbor
+            // first when it has the opposite parity to this star's placement
+            let neighbor = &atlas.proto_vertex_stars[proto_neighbor.proto_vertex_star_index];
+            let edge_point = neighbor.tiles[proto_neighbor.neighbor_index].points[1];
+            let is_flip = placement.is_flip() ^ proto_neighbor.transform.parity;
+            let edge_point = if is_flip { Point(edge_point.0, -edge_point.1) } else { edge_point };
+            let rotate = Euclid::Rotate((&point - &neighbor_point).arg() - edge_point.arg());
+            let orient = if is_flip { Euclid::Flip(0.).transform(&rotate).as_affine() } else { rotate.as_affine() };
+
+            points.insert(neighbor_point, stars.len());
+            stars.push((proto_neighbor.proto_vertex_star_index, orient.transform(&Euclid::Translate(neighbor_point.values()))));
+            if stars.len() > MAX_GENERATED {
+                return Err(NotationError::NoTiling)
+            }
+        }
+        index += 1;
+    }
+    Ok((stars, points))
+}
+
+// chamber_offsets returns the number of proto vertex star i's first chamber. Chambers are numbered as by
+// DelaneySymbol::from_atlas: two for each edge of each proto vertex star, the first on the edge's tile's side and the second
+// on the side of the tile preceding it.
+pub(crate) fn chamber_offsets(atlas: &Atlas) -> Vec<usize> {
+    atlas.proto_vertex_stars
+        .iter()
+        .scan(0, |offset, star| {
+            let chamber = *offset;
+            *offset += 2 * star.size();
+            Some(chamber)
+        })
+        .collect()
+}
+
+// tile_scale returns the farthest any point of a proto vertex star's tiles is from its vertex
+pub(crate) fn tile_scale(atlas: &Atlas) -> f64 {
+    atlas.proto_vertex_stars
+        .iter()
+        .flat_map(|star| star.tiles.iter().flat_map(|tile| tile.points.iter()))
+        .map(Point::norm)
+        .fold(0., f64::max)
+}
+
+// flag returns the isometry taking the positive x-axis onto the edge from the origin to point, with the upper half-plane
+// on the edge's anticlockwise side when orientation is 1 and its clockwise side when orientation is -1
+fn flag(point: &Point, orientation: f64) -> Affine {
+    let Point(x, y) = point.mul(1. / point.norm());
+    Affine([[x, -orientation * y], [y, orientation * x]], [0., 0.])
+}
+
+fn is_translation(affine: &Affine) -> bool {
+    (affine.0[0][0] - 1.).abs() < POINT_MARGIN && affine.0[0][1].abs() < POINT_MARGIN && affine.0[1][0].abs() < POINT_MARGIN
+        && (affine.0[1][1] - 1.).abs() < POINT_MARGIN && Point(affine.1[0], affine.1[1]).norm() > POINT_MARGIN
+}
+
+// rotation_key orders rotations by their angle, then the distance of their center from the origin
+fn rotation_key(rotation: &Affine) -> (f64, f64) {
+    let angle = normalize_angle(rotation.0[1][0].atan2(rotation.0[0][0]));
+    if angle < POINT_MARGIN {
+        return (f64::INFINITY, f64::INFINITY)
+    }
+    // the center c is fixed by the rotation, so (I - R)c = t
+    let complement = Affine([[1. - rotation.0[0][0], -rotation.0[0][1]], [-rotation.0[1][0], 1. - rotation.0[1][1]]], [0., 0.]);
+    (angle, Point(rotation.1[0], rotation.1[1]).transform(&complement.inverse()).norm())
+}
+
+// reflection_key orders reflections and glide reflections by the length of their glide, then the distance of their axis
+// from the origin
+fn reflection_key(reflection: &Affine) -> (f64, f64) {
+    // applying a glide reflection twice translates by twice its glide, the rest of its translation is perpendicular to
+    // its axis and twice the axis' distance from the origin
+    let translation = Point(reflection.1[0], reflection.1[1]);
+    let glide = (&translation + &translation.transform(&Affine(reflection.0, [0., 0.]))).mul(0.5);
+    (glide.norm(), (&translation - &glide).norm() / 2.)
+}
+
+// least returns the affine of those matching filter with the least key, comparing keys up to POINT_MARGIN
+fn least<F: Fn(&Affine) -> bool, K: Fn(&Affine) -> (f64, f64)>(affines: &[Affine], filter: F, key: K) -> Option<Affine> {
+    let cmp = |a: f64, b: f64| if (a - b).abs() < POINT_MARGIN { Ordering::Equal } else { a.partial_cmp(&b).unwrap_or(Ordering::Equal) };
+    affines
+        .iter()
+        .filter(|affine| filter(affine))
+        .map(|affine| (key(affine), affine))
+        .filter(|((primary, _), _)| primary.is_finite())
+        .min_by(|((a0, a1), _), ((b0, b1), _)| cmp(*a0, *b0).then_with(|| cmp(*a1, *b1)))
+        .map(|(_, affine)| *affine)
+}
+
+fn find(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[index] = root;
+    root
+}
+
+fn union(parents: &mut [usize], a: usize, b: usize) {
+    let (a, b) = (find(parents, a), find(parents, b));
+    parents[a.max(b)] = a.min(b);
+}
+
+// number_orbits numbers the sets of parents in order of their first member
+fn number_orbits(parents: &mut [usize]) -> Vec<usize> {
+    let mut numbers: Vec<Option<usize>> = vec![None; parents.len()];
+    let mut count = 0;
+    (0..parents.len())
+        .map(|index| {
+            let root = find(parents, index);
+            *numbers[root].get_or_insert_with(|| { count += 1; count - 1 })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{uniformity, VertexConfiguration};
+    use models::{FullAtlasEdgePost, FullAtlasVertexPost};
+    use std::f64::consts::PI;
+    use tile::Tile;
+
+    fn atlas(notation: &str) -> Atlas {
+        let configuration = VertexConfiguration::parse(notation).unwrap();
+        Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().expect(notation)).unwrap()
+    }
+
+    fn angle(affine: &Affine) -> f64 {
+        normalize_angle(affine.0[1][0].atan2(affine.0[0][0]))
+    }
+
+    #[test]
+    fn test_wallpaper_group() {
+        for (group, name, orbifold) in WALLPAPER_GROUPS.iter() {
+            assert_eq!(Some(*group), WallpaperGroup::parse(name));
+            assert_eq!(Some(*group), WallpaperGroup::from_orbifold(orbifold));
+            assert_eq!(*name, group.to_string());
+            assert_eq!(format!("\"{}\"", name), serde_json::to_string(group).unwrap());
+        }
+        assert_eq!(None, WallpaperGroup::parse("p5"));
+    }
+
+    #[test]
+    fn test_symmetry() {
+        for (notation, group) in [
+            ("4^4", WallpaperGroup::P4m),
+            ("3^6", WallpaperGroup::P6m),
+            ("6^3", WallpaperGroup::P6m),
+            ("3^4.6", WallpaperGroup::P6),
+            ("3^2.4.3.4", WallpaperGroup::P4g),
+            ("3^3.4^2", WallpaperGroup::Cmm),
+            ("3.4.6.4", WallpaperGroup::P6m),
+            ("4.8^2", WallpaperGroup::P4m),
+        ].iter() {
+            assert_eq!(*group, symmetry(&atlas(notation)).expect(notation).group, "{}", notation);
+        }
+
+        // the square tiling's translations are its unit edges, its rotations are quarter turns and it has reflections
+        let square = symmetry(&atlas("4^4")).unwrap();
+        let (translation0, translation1) = square.translations;
+        assert!((translation0.norm() - 1.).abs() < POINT_MARGIN && (translation1.norm() - 1.).abs() < POINT_MARGIN);
+        assert!(translation0.dot(&translation1).abs() < POINT_MARGIN);
+        assert_eq!(2, square.generators.len());
+        assert!((angle(&square.generators[0]) - PI / 2.).abs() < POINT_MARGIN);
+        assert!(square.generators[1].is_flip());
+        assert!(reflection_key(&square.generators[1]).0 < POINT_MARGIN);
+
+        // the snub hexagonal tiling has only rotations, through a sixth of a turn at most
+        let snub_hexagonal = symmetry(&atlas("3^4.6")).unwrap();
+        assert_eq!(1, snub_hexagonal.generators.len());
+        assert!((angle(&snub_hexagonal.generators[0]) - PI / 3.).abs() < POINT_MARGIN);
+
+        // the snub square tiling has quarter turns and reflections as well as glide reflections
+        let snub_square = symmetry(&atlas("3^2.4.3.4")).unwrap();
+        assert!((angle(&snub_square.generators[0]) - PI / 2.).abs() < POINT_MARGIN);
+        assert!(snub_square.generators[1].is_flip());
+        assert!(reflection_key(&snub_square.generators[1]).0 < POINT_MARGIN);
+
+        // each vertex star is in its orbit under every symmetry, and tiles are in the same orbit exactly when they're
+        // the same kind of polygon
+        for notation in ["3.4.6.4", "4.8^2", "[3^6; 3^2.4.3.4]"].iter() {
+            let tiling = atlas(notation);
+            let found = symmetry(&tiling).expect(notation);
+            let count = found.vertex_star_orbits.iter().max().unwrap() + 1;
+            assert_eq!(uniformity(&tiling), Ok(count), "{}", notation);
+            for (star, orbits) in tiling.proto_vertex_stars.iter().zip(found.tile_orbits.iter()) {
+                for (j, tile) in star.tiles.iter().enumerate() {
+                    for (other_star, other_orbits) in tiling.proto_vertex_stars.iter().zip(found.tile_orbits.iter()) {
+                        for (k, other) in other_star.tiles.iter().enumerate() {
+                            if tile.size() != other.size() && orbits[j] == other_orbits[k] {
+                                panic!("{}: tiles of different sizes in orbit {}", notation, orbits[j]);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(3, symmetry(&atlas("3.4.6.4")).unwrap().tile_orbits.iter().flatten().max().unwrap() + 1);
+
+        // a tiling of rectangles has half turns and reflections but no quarter turns
+        let rectangle = Tile::new(vec![Point(0., 0.), Point(2., 0.), Point(2., 1.), Point(0., 1.)]);
+        let vertices = vec![FullAtlasVertexPost {
+            edges: (0..4)
+                .map(|j| FullAtlasEdgePost { polygon_index: 0, point_index: j, neighbor_index: 0, neighbor_edge_index: (j + 2) % 4, parity: false })
+                .collect(),
+        }];
+        let rectangles = symmetry(&Atlas::from_vertex_posts(&[rectangle], &vertices).unwrap()).unwrap();
+        assert_eq!(WallpaperGroup::Pmm, rectangles.group);
+        assert!((angle(&rectangles.generators[0]) - PI).abs() < POINT_MARGIN);
+        let (translation0, translation1) = rectangles.translations;
+        assert!((translation0.norm() - 1.).abs() < POINT_MARGIN && (translation1.norm() - 2.).abs() < POINT_MARGIN);
+        assert_eq!(vec![0], rectangles.vertex_star_orbits);
+        assert_eq!(vec![vec![0, 0, 0, 0]], rectangles.tile_orbits);
+    }
+}
@@ -0,0 +1,304 @@
+use crate::{symmetry, Atlas, NotationError};
+use crate::delaney::labelled_symbol;
+use crate::periodic::{cross, midpoint, PointMap, POINT_MARGIN};
+use crate::symmetry::{chamber_offsets, develop, tile_scale, PlacedStar};
+use geometry::{Affine, Bounds, Edge, Euclid, Point, Spatial, Transformable, ORIGIN};
+use std::collections::VecDeque;
+use tile::Tile;
+
+// UnitCell is a set of tiles whose translates by the tiling's lattice cover the plane, each tile of the tiling being exactly
+// one such translate. It's placed with proto vertex star 0 at the origin as it's placed in the atlas, which is also how a
+// Patch places its seed vertex star.
+#[derive(Clone, Debug)]
+pub struct UnitCell {
+    // translations are the shortest two independent translations of the tiling, which generate its lattice
+    pub translations: (Point, Point),
+    // vertex_stars are the placed proto vertex stars of the vertices with lattice coordinates in [0, 1) x [0, 1)
+    pub vertex_stars: Vec<(usize, Affine)>,
+    // tiles are the tiles with their centroid's lattice coordinates in [0, 1) x [0, 1)
+    pub tiles: Vec<Tile>,
+}
+
+impl UnitCell {
+    // translation returns the lattice translation i * translations.0 + j * translations.1
+    pub fn translation(&self, i: i64, j: i64) -> Point {
+        &self.translations.0.mul(i as f64) + &self.translations.1.mul(j as f64)
+    }
+
+    // lattice_coordinates returns the coordinates of point in the basis of the cell's translations
+    pub fn lattice_coordinates(&self, point: &Point) -> (f64, f64) {
+        let (translation0, translation1) = &self.translations;
+        let determinant = cross(translation0, translation1);
+        (cross(point, translation1) / determinant, cross(translation0, point) / determinant)
+    }
+
+    // tiles_at returns the cell's tiles translated by translation(i, j)
+    pub fn tiles_at(&self, i: i64, j: i64) -> Vec<Tile> {
+        let translate = Euclid::Translate(self.translation(i, j).values());
+        self.tiles.iter().map(|tile| tile.transform(&translate)).collect()
+    }
+
+    // adjacency returns the centroids of the tiles with their centroids in bounds paired with those of each tile sharing
+    // an edge with them, so that pairs of neighbors within bounds appear in both orders, as Patch::get_included_adjacency
+    // lists them for a Patch filled over bounds, without placing any tiles
+    pub fn adjacency(&self, bounds: &Bounds) -> Vec<(Point, Point)> {
+        let reach = self.tiles
+            .iter()
+            .flat_map(|tile| tile.points.iter().map(move |point| (point - &tile.centroid).norm()))
+            .fold(0., f64::max);
+        let margin = bounds.radius + 2. * reach;
+        let indices = [Point(-margin, -margin), Point(margin, -margin), Point(margin, margin), Point(-margin, margin)]
+            .iter()
+            .map(|offset| self.index(&(&bounds.center + offset)))
+            .collect::<Vec<(i64, i64)>>();
+        let (i_min, i_max) = indices.iter().fold((i64::MAX, i64::MIN), |(min, max), (i, _)| (min.min(*i), max.max(*i)));
+        let (j_min, j_max) = indices.iter().fold((i64::MAX, i64::MIN), |(min, max), (_, j)| (min.min(*j), max.max(*j)));
+        let tiles = (i_min..=i_max)
+            .flat_map(|i| (j_min..=j_max).map(move |j| (i, j)))
+            .flat_map(|(i, j)| self.tiles_at(i, j))
+            .collect::<Vec<Tile>>();
+
+        let mut point_tiles: PointMap<Vec<usize>> = PointMap::new();
+        for (k, tile) in tiles.iter().enumerate() {
+            for point in tile.points.iter() {
+                match point_tiles.get_mut(point) {
+                    Some(indices) => indices.push(k),
+                    None => { point_tiles.insert(*point, vec![k]); },
+                }
+            }
+        }
+
+        let mut adjacency: Vec<(Point, Point)> = vec![];
+        for (k, tile) in tiles.iter().enumerate().filter(|(_, tile)| tile.centroid.intersects(bounds)) {
+            let mut neighbor_indices: Vec<usize> = vec![];
+            for Edge(start, end) in Point::edges(&tile.points) {
+                let (start_tiles, end_tiles) = match (point_tiles.get(start), point_tiles.get(end)) {
+                    (Some(start_tiles), Some(end_tiles)) => (start_tiles, end_tiles),
+                    _ => continue,
+                };
+                for neighbor_index in start_tiles.iter().filter(|index| **index != k && end_tiles.contains(index)) {
+                    if !neighbor_indices.contains(neighbor_index) {
+                        neighbor_indices.push(*neighbor_index);
+                    }
+                }
+            }
+            adjacency.extend(neighbor_indices.into_iter().map(|neighbor_index| (tile.centroid, tiles[neighbor_index].centroid)));
+        }
+        adjacency
+    }
+
+    // area returns the area of the cell, which is also the total area of its tiles
+    pub fn area(&self) -> f64 {
+        cross(&self.translations.0, &self.translations.1).abs()
+    }
+
+    // index returns the (i, j) for which the cell translated by translation(i, j) contains point, a point on the cell's
+    // boundary belonging to the translate it's on the lower edge of
+    pub fn index(&self, point: &Point) -> (i64, i64) {
+        let (a, b) = self.lattice_coordinates(point);
+        ((a + POINT_MARGIN).floor() as i64, (b + POINT_MARGIN).floor() as i64)
+    }
+
+    // block_polygon_indices returns the index of the atlas polygon of each tile of a block of m x n translates of the cell,
+    // that of tile k of translate (i, j) being at k + tiles.len() * (i + m * j). There are none if the atlas' polygons
+    // don't repeat over the block, as translations of the tiling needn't map tiles onto copies of the same polygon.
+    pub fn block_polygon_indices(&self, atlas: &Atlas, (m, n): (i64, i64)) -> Result<Option<Vec<usize>>, NotationError> {
+        let radius = self.translation(m, n).norm().max((&self.translation(m, 0) - &self.translation(0, n)).norm()) + 2. * tile_scale(atlas);
+        let (stars, _) = develop(atlas, radius)?;
+
+        let mut polygon_indices: Vec<Option<usize>> = vec![None; self.tiles.len() * (m * n) as usize];
+        for (i, placement) in stars.iter() {
+            let star = &atlas.proto_vertex_stars[*i];
+            for (tile, polygon_index) in star.tiles.iter().zip(star.polygon_indices.iter()) {
+                let centroid = tile.centroid.transform(placement);
+                let (a, b) = self.index(&centroid);
+                let centroid = &centroid - &self.translation(a, b);
+                let k = match self.tiles.iter().position(|tile| tile.centroid == centroid) { None => continue, Some(k) => k };
+                let slot = &mut polygon_indices[k + self.tiles.len() * (a.rem_euclid(m) + m * b.rem_euclid(n)) as usize];
+                match slot {
+                    Some(other) if other != polygon_index => return Ok(None),
+                    _ => *slot = Some(*polygon_index),
+                }
+            }
+        }
+        Ok(polygon_indices.into_iter().collect())
+    }
+}
+
+// FundamentalDomain is a connected region of the plane whose images under the tiling's symmetry group cover the plane,
+// each of its interior points being mapped onto itself by no symmetry but the identity. It's made up of chambers of the
+// tiling's barycentric subdivision, one for each orbit of chambers.
+#[derive(Clone, Debug)]
+pub struct FundamentalDomain {
+    // chambers are triangles [vertex, midpoint of an edge at the vertex, centroid of a tile at the edge]
+    pub chambers: Vec<Tile>,
+}
+
+// unit_cell finds a unit cell of an atlas' tiling spanned by the shortest translations found by symmetry
+pub fn unit_cell(atlas: &Atlas) -> Result<UnitCell, NotationError> {
+    let mut cell = UnitCell {
+        translations: symmetry(atlas)?.translations,
+        vertex_stars: vec![],
+        tiles: vec![],
+    };
+    let (stars, _) = develop(atlas, cell.translations.0.norm() + cell.translations.1.norm() + 2. * tile_scale(atlas))?;
+
+    let mut centroids: PointMap<()> = PointMap::new();
+    for (i, placement) in stars.iter() {
+        if cell.index(&ORIGIN.transform(placement)) == (0, 0) {
+            cell.vertex_stars.push((*i, *placement));
+        }
+        for tile in atlas.proto_vertex_stars[*i].tiles.iter() {
+            let tile = tile.transform(placement);
+            if cell.index(&tile.centroid) == (0, 0) && centroids.insert(tile.centroid, ()) {
+                cell.tiles.push(tile);
+            }
+        }
+    }
+    Ok(cell)
+}
+
+// fundamental_domain finds a fundamental domain of an atlas' tiling by walking outward from the chamber of proto vertex
+// star 0's first edge and tile, keeping each chamber whose orbit under the tiling's symmetries hasn't been kept yet
+pub fn fundamental_domain(atlas: &Atlas) -> Result<FundamentalDomain, NotationError> {
+    let (symbol, labels) = labelled_symbol(atlas)?;
+    let classes = symbol.labelled_classes(&labels);
+    let num_classes = classes.iter().max().map_or(0, |class| class + 1);
+    let offsets = chamber_offsets(atlas);
+
+    // every kept chamber is reached through fewer chambers than there are orbits, each step staying within a tile
+    let (stars, points) = develop(atlas, (num_classes + 1) as f64 * tile_scale(atlas))?;
+
+    let mut seen = vec![false; num_classes];
+    let mut chambers: VecDeque<(usize, usize, usize)> = VecDeque::from(vec![(0, 0, 0)]);
+    seen[classes[0]] = true;
+    let mut domain = FundamentalDomain { chambers: vec![] };
+    while let Some((k, j, side)) = chambers.pop_front() {
+        let triangle = chamber(atlas, &stars[k], j, side);
+        let size = atlas.proto_vertex_stars[stars[k].0].size();
+
+        let mut neighbors = vec![
+            (k, j, 1 - side),
+            if side == 0 { (k, (j + size - 1) % size, 1) } else { (k, (j + 1) % size, 0) },
+        ];
+        if let Some(other) = points.get(&(&triangle[1].mul(2.) - &triangle[0])) {
+            let other_size = atlas.proto_vertex_stars[stars[*other].0].size();
+            neighbors.extend(
+                (0..other_size)
+                    .flat_map(|j| (0..2).map(move |side| (*other, j, side)))
+                    .find(|(other, j, side)| {
+                        let other_triangle = chamber(atlas, &stars[*other], *j, *side);
+                        (&other_triangle[1] - &triangle[1]).norm() < POINT_MARGIN && (&other_triangle[2] - &triangle[2]).norm() < POINT_MARGIN
+                    })
+            );
+        }
+        for (k, j, side) in neighbors.into_iter() {
+            let class = classes[offsets[stars[k].0] + 2 * j + side];
+            if !seen[class] {
+                seen[class] = true;
+                chambers.push_back((k, j, side));
+            }
+        }
+        domain.chambers.push(Tile::new(triangle.to_vec()));
+    }
+    if seen.iter().any(|seen| !seen) {
+        return Err(NotationError::NoTiling)
+    }
+    Ok(domain)
+}
+
+// chamber returns the triangle [vertex, edge midpoint, tile centroid] of the chamber on the given side of edge j of a placed
+// proto vertex star, numbered as by chamber_offsets
+fn chamber(atlas: &Atlas, (i, placement): &PlacedStar, j: usize, side: usize) -> [Point; 3] {
+    let tile = &atlas.proto_vertex_stars[*i].tiles[j];
+    let end = if side == 0 { &tile.points[1] } else { &tile.points[tile.size() - 1] };
+    [ORIGIN.transform(placement), midpoint(&ORIGIN, end).transform(placement), tile.centroid.transform(placement)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VertexConfiguration;
+
+    fn atlas(notation: &str) -> Atlas {
+        let configuration = VertexConfiguration::parse(notation).unwrap();
+        Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().expect(notation)).unwrap()
+    }
+
+    fn area(tile: &Tile) -> f64 {
+        tile.points.iter().zip(tile.points.iter().cycle().skip(1)).map(|(a, b)| cross(a, b)).sum::<f64>().abs() / 2.
+    }
+
+    #[test]
+    fn test_unit_cell() {
+        for (notation, sizes) in [
+            ("4^4", vec![4]),
+            ("3^6", vec![3, 3]),
+            ("6^3", vec![6]),
+            ("3.4.6.4", vec![3, 3, 4, 4, 4, 6]),
+            ("4.8^2", vec![4, 8]),
+            ("3^4.6", vec![3, 3, 3, 3, 3, 3, 3, 3, 6]),
+        ].iter() {
+            let tiling = atlas(notation);
+            let cell = unit_cell(&tiling).expect(notation);
+            let mut found = cell.tiles.iter().map(Tile::size).collect::<Vec<usize>>();
+            found.sort_unstable();
+            assert_eq!(sizes, &found, "{}", notation);
+            assert!((cell.area() - cell.tiles.iter().map(area).sum::<f64>()).abs() < POINT_MARGIN, "{}", notation);
+
+            let vertices = sizes.iter().sum::<usize>() / tiling.proto_vertex_stars[0].size();
+            assert_eq!(vertices, cell.vertex_stars.len(), "{}", notation);
+
+            let shifted = cell.tiles_at(2, -1);
+            let translation = cell.translation(2, -1);
+            for (tile, shifted) in cell.tiles.iter().zip(shifted.iter()) {
+                assert!((&(&tile.centroid + &translation) - &shifted.centroid).norm() < POINT_MARGIN);
+                assert_eq!((2, -1), cell.index(&shifted.centroid), "{}", notation);
+            }
+        }
+    }
+
+    #[test]
+    fn test_adjacency() {
+        let bounds = Bounds { center: Point(0.3, -0.2), radius: 4. };
+        for notation in ["4^4", "3^6", "6^3", "3.4.6.4", "4.8^2", "3^4.6", "3^2.4.3.4", "3.12^2"].iter() {
+            let cell = unit_cell(&atlas(notation)).unwrap();
+            let adjacency = cell.adjacency(&bounds);
+            let tiles = (-20..=20)
+                .flat_map(|i| (-20..=20).map(move |j| (i, j)))
+                .flat_map(|(i, j)| cell.tiles_at(i, j))
+                .filter(|tile| tile.centroid.intersects(&bounds))
+                .collect::<Vec<Tile>>();
+            assert!(!tiles.is_empty(), "{}", notation);
+
+            // each tile of these edge to edge tilings of convex polygons has one neighbor across each of its edges
+            for tile in tiles.iter() {
+                let neighbors = adjacency.iter().filter(|(centroid, _)| *centroid == tile.centroid).collect::<Vec<_>>();
+                assert_eq!(tile.size(), neighbors.len(), "{} at {}", notation, tile.centroid);
+                for (_, neighbor) in neighbors {
+                    assert!((neighbor - &tile.centroid).norm() > POINT_MARGIN, "{}", notation);
+                    if neighbor.intersects(&bounds) {
+                        assert!(adjacency.contains(&(*neighbor, tile.centroid)), "{} at {}", notation, tile.centroid);
+                    }
+                }
+            }
+            assert_eq!(tiles.iter().map(Tile::size).sum::<usize>(), adjacency.len(), "{}", notation);
+        }
+    }
+
+    #[test]
+    fn test_fundamental_domain() {
+        for notation in ["4^4", "3^6", "6^3", "3.4.6.4", "4.8^2", "3^4.6", "3^2.4.3.4", "3^3.4^2"].iter() {
+            let tiling = atlas(notation);
+            let (symbol, labels) = labelled_symbol(&tiling).unwrap();
+            let domain = fundamental_domain(&tiling).expect(notation);
+            assert_eq!(symbol.labelled_minimal(&labels).0.size(), domain.chambers.len(), "{}", notation);
+
+            let order = symmetry(&tiling).unwrap().group.point_group_order();
+            let cell = unit_cell(&tiling).unwrap();
+            let domain_area = domain.chambers.iter().map(area).sum::<f64>();
+            assert!((order as f64 * domain_area - cell.area()).abs() < POINT_MARGIN, "{}", notation);
+        }
+        assert_eq!(1, fundamental_domain(&atlas("4^4")).unwrap().chambers.len());
+    }
+}
@@ -0,0 +1,167 @@
+use crate::NotationError;
+use common::DEFAULT_F64_MARGIN;
+use float_cmp::ApproxEq;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::TAU;
+use tile::Tile;
+
+// VertexCorner is the corner of the polygon at polygon_index found at its point_index
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct VertexCorner {
+    #[serde(rename = "polygonIndex")]
+    pub polygon_index: usize,
+    #[serde(rename = "pointIndex")]
+    pub point_index: usize,
+}
+
+// VertexType is a cyclic sequence of polygon corners whose angles sum to 2π, i.e. the corners of a vertex star
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct VertexType {
+    pub corners: Vec<VertexCorner>,
+}
+
+// vertex_types lists up to limit of the vertex types which can be made from corners of polygons, each up to rotation and
+// reflection. Corners which a symmetry of their polygon maps onto one another are considered the same corner, so a
+// regular polygon has one corner and a star has two, and each is given by its least point index. As polygons with small
+// angles make for very many sequences of corners, VertexTypeLimit is returned if the vertex types aren't all listed, or
+// limit of them found, within max_steps extensions of a sequence.
+pub fn vertex_types(polygons: &[Tile], limit: usize, max_steps: usize) -> Result<Vec<VertexType>, NotationError> {
+    let corners = polygons
+        .iter()
+        .enumerate()
+        .flat_map(|(polygon_index, polygon)| distinct_point_indices(polygon)
+            .into_iter()
+            .map(move |point_index| (VertexCorner { polygon_index, point_index }, polygon.angle(point_index)))
+        )
+        .collect::<Vec<(VertexCorner, f64)>>();
+
+    let mut vertex_types: Vec<VertexType> = vec![];
+    let mut sequence: Vec<usize> = vec![];
+    let mut steps_left = max_steps;
+    extend_vertex_types(&corners, &mut sequence, 0., limit, &mut steps_left, &mut vertex_types)
+        .ok_or(NotationError::VertexTypeLimit { max_steps })?;
+    Ok(vertex_types)
+}
+
+// extend_vertex_types appends each way of closing sequence, a sequence of indices into corners whose angles sum to angle,
+// until there are limit vertex types, spending a step of steps_left on each extension of sequence. None is returned if
+// the steps run out first.
+fn extend_vertex_types(
+    corners: &[(VertexCorner, f64)],
+    sequence: &mut Vec<usize>,
+    angle: f64,
+    limit: usize,
+    steps_left: &mut usize,
+    vertex_types: &mut Vec<VertexType>,
+) -> Option<()> {
+    if vertex_types.len() >= limit {
+        return Some(())
+    }
+    if angle.approx_eq(TAU, DEFAULT_F64_MARGIN) {
+        if is_least(sequence) {
+            vertex_types.push(VertexType { corners: sequence.iter().map(|index| corners[*index].0).collect() });
+        }
+        return Some(())
+    }
+    for (index, (_, corner_angle)) in corners.iter().enumerate() {
+        if angle + corner_angle > TAU + DEFAULT_F64_MARGIN.0 {
+            continue
+        }
+        *steps_left = steps_left.checked_sub(1)?;
+        sequence.push(index);
+        extend_vertex_types(corners, sequence, angle + corner_angle, limit, steps_left, vertex_types)?;
+        sequence.pop();
+    }
+    Some(())
+}
+
+// is_least determines whether sequence is the least of its rotations and reflections
+fn is_least(sequence: &[usize]) -> bool {
+    let n = sequence.len();
+    (0..n).all(|offset| {
+        let rotation = (0..n).map(|i| sequence[(offset + i) % n]);
+        let reflection = (0..n).map(|i| sequence[(offset + n - i) % n]);
+        rotation.ge(sequence.iter().cloned()) && reflection.ge(sequence.iter().cloned())
+    })
+}
+
+// distinct_point_indices lists the point indices of polygon which none of its symmetries map to a lesser point index
+fn distinct_point_indices(polygon: &Tile) -> Vec<usize> {
+    let size = polygon.size();
+    let side = |i: usize| (&polygon.points[(i + 1) % size] - &polygon.points[i % size]).norm();
+    // a symmetry (offset, reflected) maps point i to offset + i, or offset - i if reflected
+    let map = |offset: usize, reflected: bool, i: usize| if reflected { (offset + size - i) % size } else { (offset + i) % size };
+    let symmetries = (0..size)
+        .flat_map(|offset| [(offset, false), (offset, true)])
+        .filter(|(offset, reflected)| (0..size).all(|i| {
+            // a reflection maps the side following point i onto the side preceding its image
+            let image_side = if *reflected { side(map(*offset, true, i) + size - 1) } else { side(map(*offset, false, i)) };
+            polygon.angle(i).approx_eq(polygon.angle(map(*offset, *reflected, i)), DEFAULT_F64_MARGIN)
+                && side(i).approx_eq(image_side, DEFAULT_F64_MARGIN)
+        }))
+        .collect::<Vec<(usize, bool)>>();
+    (0..size)
+        .filter(|i| symmetries.iter().all(|(offset, reflected)| *i <= map(*offset, *reflected, *i)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geometry::Point;
+    use std::f64::consts::PI;
+    use tile::{regular_polygon, star_polygon};
+
+    const MAX_STEPS: usize = 1_000_000;
+
+    fn corner(polygon_index: usize, point_index: usize) -> VertexCorner {
+        VertexCorner { polygon_index, point_index }
+    }
+
+    #[test]
+    fn test_vertex_types() {
+        let polygons = [3, 4].iter().map(|sides| regular_polygon(1., *sides)).collect::<Vec<Tile>>();
+        let sides = vertex_types(&polygons, usize::MAX, MAX_STEPS).unwrap()
+            .iter()
+            .map(|vertex_type| vertex_type.corners.iter().map(|corner| [3, 4][corner.polygon_index]).collect())
+            .collect::<Vec<Vec<usize>>>();
+        assert_eq!(vec![vec![3, 3, 3, 3, 3, 3], vec![3, 3, 3, 4, 4], vec![3, 3, 4, 3, 4], vec![4, 4, 4, 4]], sides);
+        assert_eq!(2, vertex_types(&polygons, 2, MAX_STEPS).unwrap().len());
+
+        let polygons = [3, 4, 6, 8, 12].iter().map(|sides| regular_polygon(1., *sides)).collect::<Vec<Tile>>();
+        assert_eq!(15, vertex_types(&polygons, usize::MAX, MAX_STEPS).unwrap().len());
+        assert!(vertex_types(&[regular_polygon(1., 5)], usize::MAX, MAX_STEPS).unwrap().is_empty());
+
+        // a rectangle's corners are all alike, but a kite's aren't
+        let rectangle = Tile::new(vec![Point(0., 0.), Point(2., 0.), Point(2., 1.), Point(0., 1.)]);
+        assert_eq!(vec![0], distinct_point_indices(&rectangle));
+        let kite = Tile::new(vec![Point(0., 0.), Point(1., -1.), Point(3., 0.), Point(1., 1.)]);
+        assert_eq!(3, distinct_point_indices(&kite).len());
+        let kite_vertex_types = vertex_types(&[kite.clone()], usize::MAX, MAX_STEPS).unwrap();
+        assert!(!kite_vertex_types.is_empty());
+        for vertex_type in kite_vertex_types.iter() {
+            let angle: f64 = vertex_type.corners.iter().map(|corner| kite.angle(corner.point_index)).sum();
+            assert!(angle.approx_eq(TAU, DEFAULT_F64_MARGIN));
+        }
+
+        // a star's tips and dents are separate corners
+        let star = star_polygon(1., 6, PI / 3.);
+        let tip = (0..star.size()).find(|i| star.angle(*i) < PI).unwrap();
+        let dent = (0..star.size()).find(|i| star.angle(*i) > PI).unwrap();
+        let star_vertex_types = vertex_types(&[regular_polygon(1., 3), star], usize::MAX, MAX_STEPS).unwrap();
+        assert!(star_vertex_types.contains(&VertexType { corners: vec![corner(0, 0), corner(0, 0), corner(1, dent)] }));
+        assert!(star_vertex_types.contains(&VertexType { corners: vec![corner(0, 0), corner(0, 0), corner(0, 0), corner(0, 0), corner(0, 0), corner(1, tip)] }));
+    }
+
+    #[test]
+    fn test_vertex_types_limit() {
+        // a triangle with 10° and 20° corners fits around a vertex in too many ways to list
+        let (a, b) = (10_f64.to_radians(), 20_f64.to_radians());
+        let triangle = Tile::new(vec![Point(0., 0.), Point(1., 0.), Point(a.cos(), a.sin()).mul(b.sin() / (a + b).sin())]);
+        assert_eq!(Err(NotationError::VertexTypeLimit { max_steps: MAX_STEPS }), vertex_types(&[triangle.clone()], usize::MAX, MAX_STEPS));
+        assert_eq!(Err(NotationError::VertexTypeLimit { max_steps: 10 }), vertex_types(&[regular_polygon(1., 3), regular_polygon(1., 4)], usize::MAX, 10));
+
+        // but a limit on the vertex types found is reached before the steps run out
+        assert_eq!(1, vertex_types(&[triangle], 1, MAX_STEPS).unwrap().len());
+    }
+}
@@ -0,0 +1,562 @@
+use atlas::{symmetry, Atlas, UnitCell};
+use colourado::{ColorPalette, PaletteType};
+use geometry::{Bounds, Point, Transformable};
+use plotters::style::{RGBColor, BLACK};
+use std::collections::VecDeque;
+use tile::Tile;
+
+// Strategy is how a Coloring assigns each tile a color. Every strategy colors a tile by where it lies in the tiling
+// alone, so tiles keep their colors as the patch grows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Strategy {
+    // Greedy properly colors the tile adjacency graph, giving each tile the least color none of its earlier neighbors have
+    Greedy,
+    // Minimal properly colors the tile adjacency graph with as few colors as it can, found by backtracking
+    Minimal,
+    // Shape colors congruent tiles alike
+    Shape,
+    // Orbit colors tiles alike when a symmetry of the tiling maps one onto the other
+    Orbit,
+}
+
+impl Strategy {
+    // parse returns the strategy with the given name, e.g. minimal
+    pub fn parse(name: &str) -> Option<Strategy> {
+        match name {
+            "greedy" => Some(Strategy::Greedy),
+            "minimal" => Some(Strategy::Minimal),
+            "shape" => Some(Strategy::Shape),
+            "orbit" => Some(Strategy::Orbit),
+            _ => None,
+        }
+    }
+}
+
+// the default strategy is Greedy, which needs a single coloring of each block rather than a search through colorings
+impl Default for Strategy {
+    fn default() -> Strategy {
+        Strategy::Greedy
+    }
+}
+
+// Scheme is what the colors of a stored color scheme are keyed by
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scheme {
+    // Polygon keys colors by the ids of the atlas' polygons
+    Polygon,
+    // Orbit keys colors by the tiles' orbits under the tiling's symmetries
+    Orbit,
+}
+
+// Coloring assigns each tile of a tiling one of the colors of its palette
+pub struct Coloring {
+    // strategy is the strategy the coloring was made with, which is Shape when the tiling's symmetries couldn't be found
+    // for a strategy needing them
+    pub strategy: Strategy,
+    // palette has a color for each class of tiles
+    pub palette: Vec<RGBColor>,
+    classifier: Classifier,
+}
+
+// Classifier numbers the classes of tiles colored alike
+enum Classifier {
+    // Shape numbers a tile by the position of its shape in shapes
+    Shape { shapes: Vec<Vec<(i64, i64)>> },
+    // Periodic numbers a tile by its translate in a block of size.0 x size.1 unit cells, tile k of cell (i, j) of the block
+    // being in class classes[k + cell.tiles.len() * (i + size.0 * j)]
+    Periodic { cell: UnitCell, size: (i64, i64), classes: Vec<usize> },
+}
+
+// sizes of the blocks of unit cells over which periodic proper colorings are searched for, smallest first
+const BLOCK_SIZES: [(i64, i64); 9] = [(1, 1), (1, 2), (2, 1), (2, 2), (1, 3), (3, 1), (2, 3), (3, 2), (3, 3)];
+
+// largest number of colors a minimal coloring is searched for with
+const MAX_COLORS: usize = 6;
+
+// maximum number of colors tried while searching for a coloring of a block with a given number of colors
+const MAX_COLORING_STEPS: usize = 100_000;
+
+// precision with which edge lengths and angles are compared when finding tiles' shapes
+const SHAPE_PRECISION: f64 = 1e4;
+
+impl Coloring {
+    // new colors an atlas' tiling with the given strategy. Strategies other than Shape need the tiling's unit cell, such
+    // as the one a Patch of the tiling finds, and fall back to Shape when there's none or the tiling's symmetries can't be
+    // found. Only Greedy and Minimal find the cell's tile adjacency.
+    pub fn new(atlas: &Atlas, cell: Option<&UnitCell>, strategy: Strategy) -> Coloring {
+        let periodic = match (strategy, cell) {
+            (Strategy::Shape, _) | (_, None) => None,
+            (Strategy::Orbit, Some(cell)) => orbit_classifier(atlas, cell).ok(),
+            (Strategy::Greedy, Some(cell)) | (Strategy::Minimal, Some(cell)) => adjacency_classifier(atlas, cell, strategy == Strategy::Minimal).ok(),
+        };
+        let (strategy, classifier) = match periodic {
+            Some(classifier) => (strategy, classifier),
+            None => (Strategy::Shape, shape_classifier(atlas)),
+        };
+        let num_classes = match &classifier {
+            Classifier::Shape { shapes } => shapes.len(),
+            Classifier::Periodic { classes, .. } => classes.iter().max().map_or(0, |class| class + 1),
+        };
+        Coloring { strategy, palette: random_palette(num_classes), classifier }
+    }
+
+    // from_scheme colors an atlas' tiling with a stored color scheme keyed as by scheme, whose color for each key is
+    // given by color, given the tiling's unit cell and the ids of the atlas' polygons in order of polygon index, so that
+    // congruent polygons can be colored apart. Tiles the scheme has no color for are black.
+    pub fn from_scheme<F>(atlas: &Atlas, cell: &UnitCell, polygon_ids: &[i32], scheme: Scheme, color: F) -> Result<Coloring, String>
+    where F: Fn(i32) -> Option<(u8, u8, u8)>
+    {
+        let (strategy, classifier, keys) = match scheme {
+            Scheme::Polygon => (Strategy::Shape, polygon_classifier(atlas, cell)?, polygon_ids.to_vec()),
+            Scheme::Orbit => {
+                let classifier = orbit_classifier(atlas, cell)?;
+                let num_orbits = match &classifier {
+                    Classifier::Periodic { classes, .. } => classes.iter().max().map_or(0, |class| class + 1),
+                    Classifier::Shape { .. } => 0,
+                };
+                (Strategy::Orbit, classifier, (0..num_orbits as i32).collect::<Vec<i32>>())
+            },
+        };
+        let palette = keys
+            .into_iter()
+            .map(|key| color(key).map_or(BLACK, |(red, green, blue)| RGBColor(red, green, blue)))
+            .collect();
+        Ok(Coloring { strategy, palette, classifier })
+    }
+
+    // get returns the color of tile, which is black if the tile isn't one of the tiling's
+    pub fn get(&self, tile: &Tile) -> &RGBColor {
+        self.class(tile).and_then(|class| self.palette.get(class)).unwrap_or(&BLACK)
+    }
+
+    // class returns the number of the class of tiles colored like tile
+    pub fn class(&self, tile: &Tile) -> Option<usize> {
+        match &self.classifier {
+            Classifier::Shape { shapes } => {
+                let shape = shape(tile);
+                shapes.iter().position(|other| *other == shape)
+            },
+            Classifier::Periodic { cell, size, classes } => {
+                let (k, i, j) = cell_position(cell, &tile.centroid)?;
+                classes.get(k + cell.tiles.len() * (i.rem_euclid(size.0) + size.0 * j.rem_euclid(size.1)) as usize).copied()
+            },
+        }
+    }
+}
+
+// random_palette returns a palette of size random colors
+fn random_palette(size: usize) -> Vec<RGBColor> {
+    ColorPalette::new(size as u32, PaletteType::Random, false).colors
+        .iter()
+        .map(|color| {
+            let rgb = color.to_array().iter().map(|e| (e * 256.) as u8).collect::<Vec<u8>>();
+            RGBColor(rgb[0], rgb[1], rgb[2])
+        })
+        .collect()
+}
+
+// shape_classifier numbers the shapes of an atlas' tiles in order of first appearance
+fn shape_classifier(atlas: &Atlas) -> Classifier {
+    let mut shapes: Vec<Vec<(i64, i64)>> = vec![];
+    for tile in atlas.tiles.iter() {
+        let shape = shape(tile);
+        if !shapes.contains(&shape) {
+            shapes.push(shape);
+        }
+    }
+    Classifier::Shape { shapes }
+}
+
+// shape returns the (angle, edge length) of each corner of tile, the edge being the one leaving the corner, read from
+// the corner and in the direction giving the least sequence, so that congruent tiles have equal shapes
+fn shape(tile: &Tile) -> Vec<(i64, i64)> {
+    let size = tile.size();
+    let quantize = |value: f64| (value * SHAPE_PRECISION).round() as i64;
+    let length = |a: usize, b: usize| quantize((&tile.points[b % size] - &tile.points[a % size]).norm());
+    let angles = (0..size).map(|i| quantize(tile.angle(i))).collect::<Vec<i64>>();
+
+    let forward = (0..size).map(|i| (angles[i], length(i, i + 1))).collect::<Vec<(i64, i64)>>();
+    let backward = (0..size).rev().map(|i| (angles[i], length(i + size - 1, i))).collect::<Vec<(i64, i64)>>();
+    [forward, backward]
+        .iter()
+        .flat_map(|corners| (0..size).map(move |start| corners[start..].iter().chain(corners[..start].iter()).copied().collect::<Vec<(i64, i64)>>()))
+        .min()
+        .unwrap_or_default()
+}
+
+// polygon_classifier numbers the tiles of the first block of an atlas' unit cells over which the atlas' polygons repeat by
+// the indices of their polygons in the atlas
+fn polygon_classifier(atlas: &Atlas, cell: &UnitCell) -> Result<Classifier, String> {
+    for size in BLOCK_SIZES.iter() {
+        if let Some(classes) = cell.block_polygon_indices(atlas, *size).map_err(|err| err.to_string())? {
+            return Ok(Classifier::Periodic { cell: cell.clone(), size: *size, classes })
+        }
+    }
+    Err(String::from("couldn't find a block of unit cells over which the tiling's polygons repeat"))
+}
+
+// orbit_classifier numbers the tiles of an atlas' unit cell by their orbits under the tiling's symmetries
+fn orbit_classifier(atlas: &Atlas, cell: &UnitCell) -> Result<Classifier, String> {
+    let tile_orbits = symmetry(atlas).map_err(|err| err.to_string())?.tile_orbits;
+
+    let mut classes = vec![0; cell.tiles.len()];
+    for (i, placement) in cell.vertex_stars.iter() {
+        for (tile, orbit) in atlas.proto_vertex_stars[*i].tiles.iter().zip(tile_orbits[*i].iter()) {
+            let centroid = tile.centroid.transform(placement);
+            if let Some((k, _, _)) = cell_position(cell, &centroid) {
+                classes[k] = *orbit;
+            }
+        }
+    }
+    Ok(Classifier::Periodic { cell: cell.clone(), size: (1, 1), classes })
+}
+
+// adjacency_classifier properly colors the tile adjacency graph of an atlas' tiling with a coloring which repeats over a
+// block of unit cells. Greedy colorings take the first block whose coloring can be proper, minimal ones the first block
+// coloring with the fewest colors.
+fn adjacency_classifier(atlas: &Atlas, cell: &UnitCell, is_minimal: bool) -> Result<Classifier, String> {
+    let edges = block_edges(cell, &cell.adjacency(&block_bounds(atlas, cell)));
+
+    let found = if is_minimal {
+        (2..=MAX_COLORS)
+            .flat_map(|num_colors| BLOCK_SIZES.iter().map(move |size| (*size, num_colors)))
+            .find_map(|(size, num_colors)| block_graph(cell, &edges, size).and_then(|graph| backtrack_coloring(&graph, num_colors)).map(|classes| (size, classes)))
+    } else {
+        BLOCK_SIZES.iter().find_map(|size| block_graph(cell, &edges, *size).map(|graph| (*size, greedy_coloring(&graph))))
+    };
+    match found {
+        Some((size, classes)) => Ok(Classifier::Periodic { cell: cell.clone(), size, classes }),
+        None => Err(String::from("couldn't find a periodic proper coloring of the tiling")),
+    }
+}
+
+// TilePosition is a tile's (index among the unit cell's tiles, i, j) where it's a translate by translation(i, j) of the tile
+type TilePosition = (usize, i64, i64);
+
+// cell_position returns the position of the tile with the given centroid relative to the unit cell
+fn cell_position(cell: &UnitCell, centroid: &Point) -> Option<TilePosition> {
+    let (i, j) = cell.index(centroid);
+    let centroid = centroid - &cell.translation(i, j);
+    cell.tiles.iter().position(|tile| tile.centroid == centroid).map(|k| (k, i, j))
+}
+
+// block_bounds returns bounds around the largest block of unit cells with enough room around it that each of the block's
+// tiles has all its neighbors in the bounds
+fn block_bounds(atlas: &Atlas, cell: &UnitCell) -> Bounds {
+    let (m, n) = largest_block_size();
+    let center = cell.translation(m, n).mul(0.5);
+    let reach = atlas.tiles
+        .iter()
+        .flat_map(|tile| tile.points.iter().map(move |point| (point - &tile.centroid).norm()))
+        .fold(0., f64::max);
+    let radius = [cell.translation(0, 0), cell.translation(m, 0), cell.translation(0, n), cell.translation(m, n)]
+        .iter()
+        .map(|corner| (corner - &center).norm())
+        .fold(0., f64::max) + 4. * reach;
+    Bounds { center, radius }
+}
+
+// block_edges returns the pairs of positions of neighboring tiles given by adjacency, the centroids of neighboring tiles
+// over block_bounds, the first of each pair being in the largest block of unit cells
+fn block_edges(cell: &UnitCell, adjacency: &[(Point, Point)]) -> Vec<(TilePosition, TilePosition)> {
+    let (m, n) = largest_block_size();
+    adjacency
+        .iter()
+        .filter_map(|(centroid, neighbor_centroid)| {
+            let position = cell_position(cell, centroid).filter(|(_, i, j)| (0..m).contains(i) && (0..n).contains(j))?;
+            cell_position(cell, neighbor_centroid).map(|neighbor_position| (position, neighbor_position))
+        })
+        .collect()
+}
+
+// largest_block_size returns the size of a block of unit cells containing each of BLOCK_SIZES
+fn largest_block_size() -> (i64, i64) {
+    BLOCK_SIZES.iter().fold((0, 0), |(m, n), (i, j)| (m.max(*i), n.max(*j)))
+}
+
+// block_graph returns the adjacency lists of the tiles of a block of unit cells, numbered as by Classifier::Periodic,
+// where a tile next to the block is identified with its translate in the block. There's none if a tile would be its own
+// neighbor, so that no proper coloring repeats over the block.
+fn block_graph(cell: &UnitCell, edges: &[(TilePosition, TilePosition)], (m, n): (i64, i64)) -> Option<Vec<Vec<usize>>> {
+    let index = |(k, i, j): &TilePosition| k + cell.tiles.len() * (i.rem_euclid(m) + m * j.rem_euclid(n)) as usize;
+    let mut graph: Vec<Vec<usize>> = vec![vec![]; cell.tiles.len() * (m * n) as usize];
+    for (position, neighbor_position) in edges.iter().filter(|(position, _)| position.1 < m && position.2 < n) {
+        let (a, b) = (index(position), index(neighbor_position));
+        if a == b {
+            return None
+        }
+        for (a, b) in [(a, b), (b, a)].iter() {
+            if !graph[*a].contains(b) {
+                graph[*a].push(*b);
+            }
+        }
+    }
+    Some(graph)
+}
+
+// breadth_first_order returns the nodes of graph in breadth first order from node 0, followed by any it doesn't reach
+fn breadth_first_order(graph: &[Vec<usize>]) -> Vec<usize> {
+    let mut order: Vec<usize> = Vec::with_capacity(graph.len());
+    let mut seen = vec![false; graph.len()];
+    for root in 0..graph.len() {
+        if seen[root] {
+            continue
+        }
+        seen[root] = true;
+        let mut queue = VecDeque::from(vec![root]);
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for neighbor in graph[node].iter() {
+                if !seen[*neighbor] {
+                    seen[*neighbor] = true;
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+    }
+    order
+}
+
+// greedy_coloring colors the nodes of graph in breadth first order, each with the least color none of its colored
+// neighbors have
+fn greedy_coloring(graph: &[Vec<usize>]) -> Vec<usize> {
+    let mut colors: Vec<Option<usize>> = vec![None; graph.len()];
+    for node in breadth_first_order(graph) {
+        let color = (0..).find(|color| graph[node].iter().all(|neighbor| colors[*neighbor] != Some(*color))).unwrap_or(0);
+        colors[node] = Some(color);
+    }
+    colors.into_iter().map(|color| color.unwrap_or(0)).collect()
+}
+
+// backtrack_coloring searches for a coloring of the nodes of graph with num_colors colors in which no neighbors have the
+// same color, coloring them in breadth first order and never using a color before every lesser one
+fn backtrack_coloring(graph: &[Vec<usize>], num_colors: usize) -> Option<Vec<usize>> {
+    let order = breadth_first_order(graph);
+    let mut colors: Vec<Option<usize>> = vec![None; graph.len()];
+    // next[depth] is the next color to try for order[depth]
+    let mut next = vec![0; graph.len()];
+    let mut depth = 0;
+    let mut steps = 0;
+    while depth < order.len() {
+        let node = order[depth];
+        let num_used = order[..depth].iter().filter_map(|node| colors[*node]).max().map_or(0, |color| color + 1);
+        let color = (next[depth]..num_colors.min(num_used + 1))
+            .find(|color| graph[node].iter().all(|neighbor| colors[*neighbor] != Some(*color)));
+        steps += 1;
+        if steps > MAX_COLORING_STEPS {
+            return None
+        }
+        match color {
+            Some(color) => {
+                colors[node] = Some(color);
+                next[depth] = color + 1;
+                depth += 1;
+            },
+            None => {
+                colors[node] = None;
+                next[depth] = 0;
+                if depth == 0 {
+                    return None
+                }
+                depth -= 1;
+                colors[order[depth]] = None;
+            },
+        }
+    }
+    Some(colors.into_iter().map(|color| color.unwrap_or(0)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atlas::{unit_cell, VertexConfiguration};
+    use geometry::Euclid;
+    use tile::regular_polygon;
+
+    fn atlas(notation: &str) -> Atlas {
+        let configuration = VertexConfiguration::parse(notation).unwrap();
+        Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().expect(notation)).unwrap()
+    }
+
+    // neighbors returns the translates of the tiles of an atlas' unit cell whose centroids are in bounds, each along with
+    // the translates sharing an edge with it
+    fn neighbors(atlas: &Atlas, bounds: &Bounds) -> Vec<(Tile, Vec<Tile>)> {
+        let cell = unit_cell(atlas).unwrap();
+        let reach = atlas.tiles
+            .iter()
+            .flat_map(|tile| tile.points.iter().map(move |point| (point - &tile.centroid).norm()))
+            .fold(0., f64::max);
+        let margin = bounds.radius + 2. * reach;
+        let indices = [(-1., -1.), (-1., 1.), (1., -1.), (1., 1.)]
+            .iter()
+            .map(|(x, y)| cell.index(&(&bounds.center + &Point(x * margin, y * margin))))
+            .collect::<Vec<(i64, i64)>>();
+        let (i_min, i_max) = (indices.iter().map(|(i, _)| *i).min().unwrap(), indices.iter().map(|(i, _)| *i).max().unwrap());
+        let (j_min, j_max) = (indices.iter().map(|(_, j)| *j).min().unwrap(), indices.iter().map(|(_, j)| *j).max().unwrap());
+        let translates = (i_min - 1..=i_max + 1)
+            .flat_map(|i| (j_min - 1..=j_max + 1).map(move |j| (i, j)))
+            .flat_map(|(i, j)| {
+                let translation = cell.translation(i, j);
+                cell.tiles.iter().map(move |tile| tile.transform(&Euclid::Translate((translation.0, translation.1)))).collect::<Vec<Tile>>()
+            })
+            .collect::<Vec<Tile>>();
+
+        let is_neighbor = |tile: &Tile, other: &Tile| {
+            (&tile.centroid - &other.centroid).norm() <= 2. * reach
+                && tile.centroid != other.centroid
+                && Point::edges(&tile.points).iter().any(|edge| other.points.contains(edge.0) && other.points.contains(edge.1))
+        };
+        translates
+            .iter()
+            .filter(|tile| (&tile.centroid - &bounds.center).norm() <= bounds.radius)
+            .map(|tile| (tile.clone(), translates.iter().filter(|other| is_neighbor(tile, other)).cloned().collect()))
+            .collect()
+    }
+
+    // new_coloring colors an atlas' tiling given its unit cell, as a Patch of the tiling finds it
+    fn new_coloring(atlas: &Atlas, strategy: Strategy) -> Coloring {
+        Coloring::new(atlas, unit_cell(atlas).ok().as_ref(), strategy)
+    }
+
+    // included_tiles returns the tiles of an atlas' tiling about the origin along with their neighbors
+    fn included_tiles(atlas: &Atlas) -> Vec<(Tile, Vec<Tile>)> {
+        neighbors(atlas, &Bounds { center: Point(0.3, -0.2), radius: 6. })
+    }
+
+    #[test]
+    fn test_coloring_proper() {
+        for (notation, num_colors) in [
+            ("4^4", Some(2)),
+            ("3^6", Some(2)),
+            ("6^3", Some(3)),
+            ("3.4.6.4", Some(2)),
+            ("4.8^2", Some(3)),
+            ("3.12^2", Some(4)),
+            ("3^4.6", None),
+            ("3^2.4.3.4", None),
+        ].iter() {
+            let tiling = atlas(notation);
+            let minimal = new_coloring(&tiling, Strategy::Minimal);
+            let greedy = new_coloring(&tiling, Strategy::Greedy);
+            assert_eq!(Strategy::Minimal, minimal.strategy, "{}", notation);
+            assert_eq!(Strategy::Greedy, greedy.strategy, "{}", notation);
+            if let Some(num_colors) = num_colors {
+                assert_eq!(*num_colors, minimal.palette.len(), "{}", notation);
+            }
+            assert!(greedy.palette.len() >= minimal.palette.len(), "{}", notation);
+
+            for (tile, neighbors) in included_tiles(&tiling).iter() {
+                for coloring in [&minimal, &greedy].iter() {
+                    let class = coloring.class(tile).expect(notation);
+                    assert!(neighbors.iter().all(|neighbor| coloring.class(neighbor) != Some(class)), "{} at {}", notation, tile.centroid);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_coloring_shape_and_orbit() {
+        // 3.4.6.4's squares are all congruent, and all in one orbit
+        let tiling = atlas("3.4.6.4");
+        let shape = new_coloring(&tiling, Strategy::Shape);
+        let orbit = new_coloring(&tiling, Strategy::Orbit);
+        assert_eq!(3, shape.palette.len());
+        assert_eq!(Strategy::Orbit, orbit.strategy);
+        assert_eq!(3, orbit.palette.len());
+
+        // 3^4.6's triangles are congruent but in two orbits, those sharing an edge with a hexagon and those not
+        let tiling = atlas("3^4.6");
+        assert_eq!(2, new_coloring(&tiling, Strategy::Shape).palette.len());
+        assert_eq!(3, new_coloring(&tiling, Strategy::Orbit).palette.len());
+
+        // strategies needing the unit cell fall back to Shape without one
+        assert_eq!(Strategy::Shape, Coloring::new(&tiling, None, Strategy::Minimal).strategy);
+        assert_eq!(Strategy::Shape, Coloring::new(&tiling, None, Strategy::Orbit).strategy);
+
+        for notation in ["3.4.6.4", "3^4.6", "3^2.4.3.4"].iter() {
+            let tiling = atlas(notation);
+            let tiles = included_tiles(&tiling);
+            let shape = new_coloring(&tiling, Strategy::Shape);
+            let orbit = new_coloring(&tiling, Strategy::Orbit);
+            for (tile, _) in tiles.iter() {
+                // congruent tiles, reflected or not, share a shape, and tiles in one orbit are congruent
+                let reflected = tile.transform(&Euclid::Flip(0.));
+                assert_eq!(shape.class(tile), shape.class(&reflected), "{}", notation);
+                assert!(shape.class(tile).is_some(), "{}", notation);
+                let class = orbit.class(tile).expect(notation);
+                assert!(tiles.iter().filter(|(other, _)| orbit.class(other) == Some(class)).all(|(other, _)| shape.class(other) == shape.class(tile)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_coloring_from_scheme() {
+        // 3.4.6.4's polygons are a triangle, a square and a hexagon, of which the scheme only colors two
+        let configuration = VertexConfiguration::parse("3.4.6.4").unwrap();
+        let tiling = atlas("3.4.6.4");
+        let polygons = configuration.tiles();
+        let polygon_ids = vec![10, 11, 12];
+        let colors = |key| match key { 10 => Some((255, 0, 0)), 12 => Some((0, 0, 255)), _ => None };
+        let coloring = Coloring::from_scheme(&tiling, &unit_cell(&tiling).unwrap(), &polygon_ids, Scheme::Polygon, colors).unwrap();
+        assert_eq!(Strategy::Shape, coloring.strategy);
+        for (tile, _) in included_tiles(&tiling).iter() {
+            let expected = polygons.iter().position(|polygon| polygon.size() == tile.size()).map(|k| [RGBColor(255, 0, 0), BLACK, RGBColor(0, 0, 255)][k]);
+            assert_eq!(expected, Some(*coloring.get(tile)));
+        }
+
+        // a checkerboard of two congruent squares, each its own polygon, is colored by polygon rather than by shape
+        // its two vertices are 4^4's vertex with the squares around it alternating between the polygons
+        let configuration = VertexConfiguration::parse("4^4").unwrap();
+        let mut vertices = (0..2).flat_map(|_| configuration.to_vertex_posts().unwrap().into_iter().take(1)).collect::<Vec<_>>();
+        for (i, vertex) in vertices.iter_mut().enumerate() {
+            for (j, edge) in vertex.edges.iter_mut().enumerate() {
+                edge.polygon_index = (i + j) % 2;
+                edge.point_index = 0;
+                edge.neighbor_index = 1 - i;
+                edge.neighbor_edge_index = j;
+                edge.parity = false;
+            }
+        }
+        let square = regular_polygon(1., 4);
+        let checkerboard = Atlas::from_vertex_posts(&[square.clone(), square], &vertices).unwrap();
+        let colors = |key| match key { 20 => Some((255, 0, 0)), 21 => Some((0, 0, 255)), _ => None };
+        let coloring = Coloring::from_scheme(&checkerboard, &unit_cell(&checkerboard).unwrap(), &[20, 21], Scheme::Polygon, colors).unwrap();
+        assert_eq!(2, coloring.palette.len());
+        for (tile, neighbors) in included_tiles(&checkerboard).iter() {
+            assert!([RGBColor(255, 0, 0), RGBColor(0, 0, 255)].contains(coloring.get(tile)), "{}", tile.centroid);
+            assert!(neighbors.iter().all(|neighbor| coloring.get(neighbor) != coloring.get(tile)), "{}", tile.centroid);
+        }
+
+        // orbit schemes are keyed by the tiles' orbits
+        let colors = |key| match key { 0 => Some((1, 2, 3)), 2 => Some((4, 5, 6)), _ => None };
+        let coloring = Coloring::from_scheme(&tiling, &unit_cell(&tiling).unwrap(), &polygon_ids, Scheme::Orbit, colors).unwrap();
+        let orbit = new_coloring(&tiling, Strategy::Orbit);
+        assert_eq!(orbit.palette.len(), coloring.palette.len());
+        for (tile, _) in included_tiles(&tiling).iter() {
+            let expected = [RGBColor(1, 2, 3), BLACK, RGBColor(4, 5, 6)][orbit.class(tile).unwrap()];
+            assert_eq!(expected, *coloring.get(tile));
+        }
+    }
+
+    #[test]
+    fn test_coloring_stable() {
+        // a tile's color only depends on where it is in the block of unit cells its coloring repeats over, however far from
+        // the origin it is
+        let tiling = atlas("3^2.4.3.4");
+        let coloring = new_coloring(&tiling, Strategy::Minimal);
+        let (cell, (m, n)) = match &coloring.classifier {
+            Classifier::Periodic { cell, size, .. } => (cell, *size),
+            Classifier::Shape { .. } => panic!("3^2.4.3.4 has a unit cell"),
+        };
+        for tile in cell.tiles.iter() {
+            for (i, j) in [(m, 0), (0, n), (-7 * m, 12 * n)].iter() {
+                let translation = cell.translation(*i, *j);
+                let translate = Tile::new(tile.points.iter().map(|point| point + &translation).collect());
+                assert_eq!(coloring.class(tile), coloring.class(&translate));
+            }
+        }
+
+        assert_eq!(Some(Strategy::Orbit), Strategy::parse("orbit"));
+        assert_eq!(None, Strategy::parse("random"));
+    }
+}
@@ -1,3 +1,7 @@
+mod coloring;
+
+pub use self::coloring::*;
+
 use geometry::*;
 use plotters::{
     prelude::*,
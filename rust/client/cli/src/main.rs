@@ -0,0 +1,155 @@
+mod region;
+
+use atlas::Atlas;
+use canvas::{Canvas, Coloring, Strategy, SCALE};
+use geometry::{Affine, Bounds, Point, Transformable};
+use models::FullAtlas;
+use patch::{Patch, PatchTile};
+use plotters::prelude::*;
+use pmr_quad_tree::Config as TreeConfig;
+use region::Region;
+use std::{fs, path::PathBuf};
+use structopt::StructOpt;
+use svg::Stroke;
+
+type State = ();
+
+const TILE_TREE_CONFIG: TreeConfig = TreeConfig {
+    initial_radius: 1000.,
+    max_depth: 50,
+    splitting_threshold: 25,
+};
+
+const VERTEX_STAR_TREE_CONFIG: TreeConfig = TreeConfig {
+    initial_radius: 1000.,
+    max_depth: 70,
+    splitting_threshold: 10,
+};
+
+// Options are the command line arguments to the tilings binary. The output format is
+// determined by the extension of the output path: svg, png or json (a PatchSnapshot).
+#[derive(StructOpt)]
+#[structopt(name = "tilings", about = "Grows a patch of an atlas and writes it to an svg, png or json file.")]
+struct Options {
+    #[structopt(parse(from_os_str), help = "FullAtlas json file, as returned by GET /v1/atlas/<id>")]
+    atlas: PathBuf,
+
+    #[structopt(short, long, parse(from_os_str), help = "output file ending in .svg, .png or .json")]
+    output: PathBuf,
+
+    #[structopt(short, long, help = "grow over a circle of this radius; defaults to 5 if no width and height are given")]
+    radius: Option<f64>,
+
+    #[structopt(long, requires = "height", conflicts_with = "radius", help = "grow over a rectangle of this width")]
+    width: Option<f64>,
+
+    #[structopt(long, requires = "width", conflicts_with = "radius", help = "grow over a rectangle of this height")]
+    height: Option<f64>,
+
+    #[structopt(short, long, default_value = "0", allow_hyphen_values = true, help = "x coordinate of the region's center")]
+    x: f64,
+
+    #[structopt(short, long, default_value = "0", allow_hyphen_values = true, help = "y coordinate of the region's center")]
+    y: f64,
+
+    #[structopt(short, long, default_value = "30", help = "svg units or pixels per unit edge length")]
+    scale: f64,
+
+    #[structopt(long, default_value = "1", help = "width of tile edges in svg output")]
+    stroke_width: f64,
+
+    #[structopt(long, help = "omit tile edges in svg output")]
+    no_stroke: bool,
+
+    #[structopt(long, default_value = "greedy", parse(try_from_str = parse_strategy), help = "coloring strategy: greedy, minimal, shape or orbit")]
+    coloring: Strategy,
+}
+
+fn main() {
+    if let Err(e) = run(Options::from_args()) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let full_atlas_json = fs::read_to_string(&options.atlas)
+        .map_err(|e| format!("couldn't read {}: {}", options.atlas.display(), e))?;
+    let full_atlas: FullAtlas = serde_json::from_str(&full_atlas_json)
+        .map_err(|e| format!("couldn't parse {}: {}", options.atlas.display(), e))?;
+    let atlas = Atlas::new(&full_atlas).map_err(|e| e.to_string())?;
+    let mut patch: Patch<State> = Patch::new(atlas, TILE_TREE_CONFIG.clone(), VERTEX_STAR_TREE_CONFIG.clone()).map_err(|e| e.to_string())?;
+    let cell = patch.get_cell().ok();
+    let coloring = Coloring::new(&patch.atlas, cell.as_deref(), options.coloring);
+
+    let center = Point(options.x, options.y);
+    let region = match (options.width, options.height) {
+        (Some(width), Some(height)) => Region::Rectangle { center, half_width: width / 2., half_height: height / 2. },
+        _ => Region::Circle { center, radius: options.radius.unwrap_or(5.) },
+    };
+
+    region::grow(&mut patch, &region, ())?;
+
+    match options.output.extension().and_then(|extension| extension.to_str()) {
+        Some("svg") => {
+            let config = svg::Config {
+                bounds: Some(region.bounds()),
+                scale: options.scale,
+                stroke: if options.no_stroke { None } else { Some(Stroke { color: BLACK, width: options.stroke_width }) },
+                background: None,
+            };
+            let svg = svg::to_svg(
+                &patch,
+                &config,
+                |patch_tile: &PatchTile<State>| *coloring.get(patch_tile),
+            )?;
+            write(&options.output, svg)
+        },
+        Some("png") => write_png(
+            &options.output,
+            &patch,
+            &region.bounds(),
+            options.scale,
+            |patch_tile: &PatchTile<State>| *coloring.get(patch_tile),
+        ),
+        Some("json") => {
+            let json = patch.to_snapshot(full_atlas.id).and_then(|snapshot| snapshot.to_json()).map_err(|e| e.to_string())?;
+            write(&options.output, json)
+        },
+        _ => Err(format!("unsupported output format {}, expected .svg, .png or .json", options.output.display())),
+    }
+}
+
+fn parse_strategy(name: &str) -> Result<Strategy, String> {
+    Strategy::parse(name).ok_or_else(|| format!("unknown coloring strategy {}", name))
+}
+
+fn write(path: &PathBuf, contents: String) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("couldn't write {}: {}", path.display(), e))
+}
+
+// write_png rasterizes each included Tile within bounds onto a white background. Tiles are
+// mapped into the image's frame before being filled since Canvas draws relative to the origin.
+fn write_png(path: &PathBuf, patch: &Patch<State>, bounds: &Bounds, scale: f64, get_color: impl Fn(&PatchTile<State>) -> RGBColor) -> Result<(), String> {
+    let size = (2. * bounds.radius * scale).round() as u32;
+    let ratio = scale / SCALE;
+    let to_image = Affine(
+        [[ratio, 0.], [0., ratio]],
+        [-(bounds.center.0 - bounds.radius) * ratio, -(bounds.center.1 + bounds.radius) * ratio],
+    );
+
+    let mut canvas = Canvas { backend: BitMapBackend::new(path, (size, size)), bounds: bounds.clone() };
+    canvas.backend
+        .draw_rect((0, 0), (size as i32, size as i32), &WHITE, true)
+        .map_err(|e| format!("couldn't draw png background: {}", e))?;
+
+    for patch_tile_rc in patch.patch_tiles.intersecting(bounds).iter() {
+        let patch_tile = patch_tile_rc.value();
+        if patch_tile.state.is_none() {
+            continue
+        }
+        canvas.fill_tile(&patch_tile.tile.transform(&to_image), &get_color(&patch_tile))?;
+    }
+
+    canvas.backend.present().map_err(|e| format!("couldn't write {}: {}", path.display(), e))
+}
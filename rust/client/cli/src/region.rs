@@ -0,0 +1,102 @@
+use geometry::{Bounds, Point};
+use patch::Patch;
+use std::f64::consts::TAU;
+use tile::Tile;
+
+// number of sides of the polygon approximating a circular Region
+const CIRCLE_SIDES: usize = 64;
+
+pub enum Region {
+    Circle { center: Point, radius: f64 },
+    Rectangle { center: Point, half_width: f64, half_height: f64 },
+}
+
+impl Region {
+    pub fn center(&self) -> Point {
+        match self {
+            Region::Circle { center, .. } => *center,
+            Region::Rectangle { center, .. } => *center,
+        }
+    }
+
+    // bounds returns the smallest Bounds containing this Region
+    pub fn bounds(&self) -> Bounds {
+        match self {
+            Region::Circle { center, radius } => Bounds { center: *center, radius: *radius },
+            Region::Rectangle { center, half_width, half_height } => Bounds { center: *center, radius: half_width.max(*half_height) },
+        }
+    }
+
+    // polygon returns a Tile covering this Region which a Patch can be filled over,
+    // circles are approximated by a regular polygon circumscribing them
+    pub fn polygon(&self) -> Tile {
+        match self {
+            Region::Circle { radius, .. } => {
+                let center = self.center();
+                let circumradius = radius / (TAU / (2 * CIRCLE_SIDES) as f64).cos();
+                Tile::new(
+                    (0..CIRCLE_SIDES)
+                        .map(|i| {
+                            let arg = TAU * i as f64 / CIRCLE_SIDES as f64;
+                            &center + &Point(arg.cos(), arg.sin()).mul(circumradius)
+                        })
+                        .collect()
+                )
+            },
+            Region::Rectangle { center, half_width, half_height } => Tile::new(vec![
+                center + &Point(-half_width, -half_height),
+                center + &Point(*half_width, -half_height),
+                center + &Point(*half_width, *half_height),
+                center + &Point(-half_width, *half_height),
+            ]),
+        }
+    }
+}
+
+// grow includes every Tile overlapping region, filling the polygon covering it with the patch's unit cell
+// where the patch's atlas has one
+pub fn grow<State: Clone>(patch: &mut Patch<State>, region: &Region, state: State) -> Result<(), String> {
+    patch.fill_polygon_periodic(&region.polygon(), state).map(|_| ()).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atlas::{Atlas, VertexConfiguration};
+    use pmr_quad_tree::Config as TreeConfig;
+
+    #[test]
+    fn test_region() {
+        let circle = Region::Circle { center: Point(1., 1.), radius: 2. };
+        let polygon = circle.polygon();
+        assert_eq!(CIRCLE_SIDES, polygon.size());
+        assert_eq!(Point(1., 1.), polygon.centroid);
+        assert!(polygon.contains(&Point(2.4, 2.4)));
+        assert!(!polygon.contains(&Point(2.5, 2.5)));
+        assert_eq!(2., circle.bounds().radius);
+
+        let rectangle = Region::Rectangle { center: Point(0., 0.), half_width: 3., half_height: 1. };
+        let polygon = rectangle.polygon();
+        assert!(polygon.contains(&Point(2.5, -0.9)));
+        assert!(!polygon.contains(&Point(0., 1.5)));
+        assert_eq!(3., rectangle.bounds().radius);
+        assert_eq!(Point(0., 0.), rectangle.center());
+    }
+
+    #[test]
+    fn test_grow() {
+        let configuration = VertexConfiguration::parse("4^4").unwrap();
+        let atlas = Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().unwrap()).unwrap();
+        let tree_config = TreeConfig { initial_radius: 1000., max_depth: 50, splitting_threshold: 25 };
+        let count = |region: &Region| {
+            let mut patch = Patch::new(atlas.clone(), tree_config.clone(), tree_config.clone()).unwrap();
+            grow(&mut patch, region, ()).unwrap();
+            patch.patch_tiles.items.values().filter(|rc_item| rc_item.value().state.is_some()).count()
+        };
+
+        // the squares overlapping a region are included, but not those only touching it
+        assert_eq!(9, count(&Region::Rectangle { center: Point(0.5, 0.5), half_width: 1., half_height: 1. }));
+        assert_eq!(3, count(&Region::Rectangle { center: Point(0.5, 0.5), half_width: 1., half_height: 0.4 }));
+        assert_eq!(1, count(&Region::Circle { center: Point(0.5, 0.5), radius: 0.4 }));
+    }
+}
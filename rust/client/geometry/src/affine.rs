@@ -6,6 +6,7 @@ use std::iter;
 pub const IDENTITY_AFFINE: Affine = Affine([[1., 0.], [0., 1.]], [0., 0.]);
 const DISPLAY_PRECISION: u32 = 3;
 
+#[derive(Debug)]
 pub struct Affine(pub [[f64; 2]; 2], pub [f64; 2]); // (row-major transform matrix, translation vector)
 
 impl Affine {
@@ -13,6 +14,22 @@ impl Affine {
         self.0[0][0] * self.0[1][1] - self.0[0][1] * self.0[1][0] < 0.
     }
 
+    // inverse returns the affine undoing this one, which must have a non-zero determinant
+    pub fn inverse(&self) -> Affine {
+        let determinant = self.0[0][0] * self.0[1][1] - self.0[0][1] * self.0[1][0];
+        let matrix = [
+            [self.0[1][1] / determinant, -self.0[0][1] / determinant],
+            [-self.0[1][0] / determinant, self.0[0][0] / determinant],
+        ];
+        Affine(
+            matrix,
+            [
+                -(matrix[0][0] * self.1[0] + matrix[0][1] * self.1[1]),
+                -(matrix[1][0] * self.1[0] + matrix[1][1] * self.1[1]),
+            ],
+        )
+    }
+
     // mul_0 computes the dot product between the ith row vector of lhs.0 and the jth column vector of rhs.0
     pub(crate) fn mul_0(lhs: &Affine, rhs: &Affine, i: usize, j: usize) -> f64 {
         lhs.0[i][0] * rhs.0[0][j] + lhs.0[i][1] * rhs.0[1][j]
@@ -197,6 +214,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_affine_inverse() {
+        let affine = Affine([[2., 5.], [0., -2.]], [11., 3.]);
+        let identity = affine.inverse().transform(&affine);
+        approx_eq!(f64, 1., identity.0[0][0]);
+        approx_eq!(f64, 0., identity.0[0][1]);
+        approx_eq!(f64, 0., identity.0[1][0]);
+        approx_eq!(f64, 1., identity.0[1][1]);
+        approx_eq!(f64, 0., identity.1[0]);
+        approx_eq!(f64, 0., identity.1[1]);
+
+        let identity = affine.transform(&affine.inverse());
+        approx_eq!(f64, 1., identity.0[0][0]);
+        approx_eq!(f64, 0., identity.0[0][1]);
+        approx_eq!(f64, 0., identity.0[1][0]);
+        approx_eq!(f64, 1., identity.0[1][1]);
+        approx_eq!(f64, 0., identity.1[0]);
+        approx_eq!(f64, 0., identity.1[1]);
+    }
+
     #[test]
     fn test_affine_mul_0() {
         let affine0 = Affine([[1., 2.], [3., 4.]], [5., 6.]);
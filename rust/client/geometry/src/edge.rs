@@ -9,6 +9,37 @@ use std::hash::{Hash, Hasher};
 #[derive(Clone, Debug)]
 pub struct Edge<'a>(pub &'a Point, pub &'a Point);
 
+impl<'a> Edge<'a> {
+    // crosses determines whether this edge and other intersect at a single point interior to both,
+    // edges which only touch at an endpoint or which are parallel don't cross
+    pub fn crosses(&self, other: &Edge) -> bool {
+        match self.intersection_params(other) {
+            None => false,
+            Some((s, t)) => {
+                let margin = DEFAULT_F64_MARGIN.0;
+                s > margin && s < 1. - margin && t > margin && t < 1. - margin
+            },
+        }
+    }
+
+    // intersection_params optionally returns the parameters (s, t) at which the lines through
+    // other and this edge respectively intersect, or None if they're parallel
+    fn intersection_params(&self, other: &Edge) -> Option<(f64, f64)> {
+        let u0 = self.0;
+        let v0 = self.1 - u0;
+        let u1 = other.0;
+        let v1 = other.1 - u1;
+        let w = u0 - u1;
+
+        let determinant = v1.0 * v0.1 - v0.0 * v1.1;
+        if 0_f64.approx_eq(determinant, DEFAULT_F64_MARGIN) {
+            return None
+        }
+
+        Some((w.dot(&Point(v0.1, -v0.0)) / determinant, w.dot(&Point(v1.1, -v1.0)) / determinant))
+    }
+}
+
 impl<'a> Hash for Edge<'a> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.0.hash(state);
@@ -41,23 +72,11 @@ impl<'a> Spatial for Edge<'a> {
 
         let edges = Point::edges(&points);
 
-        let u0 = self.0;
-        let v0 = self.1 - u0;
-
         for edge in edges.into_iter() {
-            let u1 = edge.0;
-            let w = u0 - u1;
-            if w == ORIGIN {
+            if self.0 - edge.0 == ORIGIN {
                 return true
             }
-
-            let v1 = edge.1 - u1;
-            let determinant = v1.0 * v0.1 - v0.0 * v1.1;
-
-            if !0_f64.approx_eq(determinant, DEFAULT_F64_MARGIN) {
-                let s = w.dot(&Point(v0.1, -v0.0)) / determinant;
-                let t = w.dot(&Point(v1.1, -v1.0)) / determinant;
-
+            if let Some((s, t)) = self.intersection_params(&edge) {
                 if s >= 0. && s <= 1. && t >= 0. && t <= 1. {
                     return true
                 }
@@ -8,6 +8,7 @@ use common::{DEFAULT_F64_MARGIN, fmt_float, rad};
 use float_cmp::{ApproxEq, F64Margin};
 use itertools::izip;
 use num_traits::cast::NumCast;
+use serde::Serialize;
 use std::{
     hash::{Hash, Hasher},
     ops::{Add, Neg, Sub},
@@ -17,6 +18,7 @@ pub const ORIGIN: Point = Point(0., 0.);
 
 pub const DISPLAY_PRECISION: u32 = 2;
 
+#[derive(Serialize)]
 pub struct Point(pub f64, pub f64);
 
 impl Point {
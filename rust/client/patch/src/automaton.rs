@@ -0,0 +1,259 @@
+use crate::{Patch, PatchError, TileDiff};
+use geometry::Point;
+use std::collections::HashMap;
+use tile::Tile;
+
+// Rule computes the next state of a tile from its current state and the states of its included neighbors, those sharing
+// an edge with it. The tile itself is passed along as tiles of a tiling needn't all have the same number of neighbors.
+pub trait Rule<CellState> {
+    fn next(&self, tile: &Tile, state: &CellState, neighbors: &[&CellState]) -> CellState;
+}
+
+// any closure with Rule::next's signature is a custom Rule
+impl<CellState, F: Fn(&Tile, &CellState, &[&CellState]) -> CellState> Rule<CellState> for F {
+    fn next(&self, tile: &Tile, state: &CellState, neighbors: &[&CellState]) -> CellState {
+        self(tile, state, neighbors)
+    }
+}
+
+// LifeRule is a Life-like rule over tiles which are dead or alive, under which a dead tile comes alive when its number of
+// live neighbors is in birth and a live tile stays alive when its number is in survival. Tiles with different numbers of
+// edges can be given their own birth and survival counts, as a count which suits hexagons rarely suits triangles.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LifeRule {
+    pub birth: Vec<usize>,
+    pub survival: Vec<usize>,
+    // by_size are the birth and survival counts used in place of birth and survival for tiles with a given number of edges
+    pub by_size: HashMap<usize, (Vec<usize>, Vec<usize>)>,
+}
+
+impl LifeRule {
+    // parse parses a rule in B/S notation, e.g. "B3/S23", optionally followed by birth and survival counts for tiles with a
+    // given number of edges, e.g. "B3/S23;3:B1/S12" for a rule which steps triangles by B1/S12 and all other tiles by
+    // B3/S23. Counts are single digits unless they're separated by commas, e.g. "B3/S2,3,10".
+    pub fn parse(notation: &str) -> Result<LifeRule, PatchError> {
+        let invalid = || PatchError::InvalidRule { rule: String::from(notation) };
+        let mut rule = LifeRule::default();
+        let mut has_default = false;
+        for part in notation.split(';').map(str::trim) {
+            let (size, counts) = match part.split_once(':') {
+                None => (None, part),
+                Some((size, counts)) => (Some(size.trim().parse::<usize>().map_err(|_| invalid())?), counts),
+            };
+            let (birth, survival) = counts.split_once('/').ok_or_else(invalid)?;
+            let birth = parse_counts(birth, 'B').ok_or_else(invalid)?;
+            let survival = parse_counts(survival, 'S').ok_or_else(invalid)?;
+            match size {
+                None if has_default => return Err(invalid()),
+                None => {
+                    has_default = true;
+                    rule.birth = birth;
+                    rule.survival = survival;
+                },
+                Some(size) => if rule.by_size.insert(size, (birth, survival)).is_some() {
+                    return Err(invalid())
+                },
+            }
+        }
+        Ok(rule)
+    }
+}
+
+impl Rule<bool> for LifeRule {
+    fn next(&self, tile: &Tile, state: &bool, neighbors: &[&bool]) -> bool {
+        let (birth, survival) = self.by_size.get(&tile.size()).map_or((&self.birth, &self.survival), |(birth, survival)| (birth, survival));
+        let live_neighbors = neighbors.iter().filter(|neighbor| ***neighbor).count();
+        if *state { survival.contains(&live_neighbors) } else { birth.contains(&live_neighbors) }
+    }
+}
+
+// parse_counts parses the counts following prefix in one half of a B/S rule
+fn parse_counts(counts: &str, prefix: char) -> Option<Vec<usize>> {
+    let counts = counts.trim().strip_prefix(prefix)?;
+    if counts.contains(',') {
+        counts.split(',').map(|count| count.trim().parse::<usize>().ok()).collect()
+    } else {
+        counts.chars().map(|count| count.to_digit(10).map(|count| count as usize)).collect()
+    }
+}
+
+// Automaton is a cellular automaton whose cells are the included tiles of a Patch. Its states are kept by tile centroid
+// apart from the tiles' own states, so that it can be run over any Patch and be replaced without touching it. Every change
+// to a tile's state is recorded on the Patch as a TileDiff::Changed, which a Tiling redraws along with its other diffs.
+pub struct Automaton<CellState> {
+    // generation is the number of steps taken since the automaton was created or cleared
+    pub generation: usize,
+    // default_state is the state of every tile without one in states, e.g. dead
+    pub default_state: CellState,
+    states: HashMap<Point, CellState>,
+    rule: Box<dyn Rule<CellState>>,
+}
+
+impl<CellState: Clone + PartialEq> Automaton<CellState> {
+    pub fn new(rule: impl Rule<CellState> + 'static, default_state: CellState) -> Automaton<CellState> {
+        Automaton {
+            generation: 0,
+            default_state,
+            states: HashMap::default(),
+            rule: Box::new(rule),
+        }
+    }
+
+    // get returns the state of the tile at centroid
+    pub fn get(&self, centroid: &Point) -> &CellState {
+        self.states.get(centroid).unwrap_or(&self.default_state)
+    }
+
+    // set sets the state of the included tile containing point, if there is one
+    pub fn set<State>(&mut self, patch: &mut Patch<State>, point: &Point, state: CellState) -> Result<(), PatchError> {
+        let patch_tile_rc = match patch.get_included_patch_tile_by_point(point) { None => return Ok(()), Some(rc_item) => rc_item };
+        let centroid = patch_tile_rc.value().tile.centroid;
+        if *self.get(&centroid) == state {
+            return Ok(())
+        }
+        if state == self.default_state {
+            self.states.remove(&centroid);
+        } else {
+            self.states.insert(centroid, state);
+        }
+        patch.insert_tile_diff(centroid, TileDiff::Changed(patch_tile_rc.downgrade()))
+    }
+
+    // step advances every included tile of patch to its next state at once and returns the centroids of the tiles whose
+    // state changed. States of tiles which are no longer included are dropped.
+    pub fn step<State>(&mut self, patch: &mut Patch<State>) -> Result<Vec<Point>, PatchError> {
+        let mut states: HashMap<Point, CellState> = HashMap::default();
+        let mut changed = vec![];
+        for rc_item in patch.patch_tiles.items.values() {
+            let patch_tile = rc_item.value();
+            if patch_tile.state.is_none() {
+                continue
+            }
+            let centroid = patch_tile.tile.centroid;
+            let neighbor_centroids = rc_item
+                .neighbors()
+                .values()
+                .filter_map(|weak_item| weak_item.upgrade())
+                .filter(|neighbor_rc_item| neighbor_rc_item.value().state.is_some())
+                .map(|neighbor_rc_item| neighbor_rc_item.value().tile.centroid)
+                .collect::<Vec<Point>>();
+            let neighbor_states = neighbor_centroids.iter().map(|centroid| self.get(centroid)).collect::<Vec<&CellState>>();
+
+            let state = self.rule.next(&patch_tile.tile, self.get(&centroid), &neighbor_states);
+            if state != *self.get(&centroid) {
+                changed.push((centroid, rc_item.downgrade()));
+            }
+            if state != self.default_state {
+                states.insert(centroid, state);
+            }
+        }
+        self.states = states;
+        self.generation += 1;
+
+        let mut centroids = vec![];
+        for (centroid, weak_item) in changed.into_iter() {
+            patch.insert_tile_diff(centroid, TileDiff::Changed(weak_item))?;
+            centroids.push(centroid);
+        }
+        Ok(centroids)
+    }
+
+    // clear returns every tile to the default state and resets the generation
+    pub fn clear<State>(&mut self, patch: &mut Patch<State>) -> Result<(), PatchError> {
+        self.generation = 0;
+        for (centroid, _) in self.states.drain() {
+            if let Some(rc_item) = patch.patch_tiles.get(&centroid) {
+                if rc_item.value().state.is_some() {
+                    patch.insert_tile_diff(centroid, TileDiff::Changed(rc_item.downgrade()))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atlas::{Atlas, VertexConfiguration};
+    use geometry::Bounds;
+    use pmr_quad_tree::Config as TreeConfig;
+
+    fn patch(notation: &str, radius: f64) -> Patch<()> {
+        let configuration = VertexConfiguration::parse(notation).unwrap();
+        let atlas = Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().expect(notation)).unwrap();
+        let tree_config = TreeConfig { initial_radius: 1000., max_depth: 50, splitting_threshold: 25 };
+        let mut patch = Patch::new(atlas, tree_config.clone(), tree_config).unwrap();
+        patch.fill_bounds_periodic(&Bounds { center: Point(0.1, 0.2), radius }, ()).unwrap();
+        patch.drain_tile_diffs();
+        patch
+    }
+
+    fn sorted(mut centroids: Vec<Point>) -> Vec<Point> {
+        centroids.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap().then(a.1.partial_cmp(&b.1).unwrap()));
+        centroids
+    }
+
+    #[test]
+    fn test_life_rule_parse() {
+        let rule = LifeRule::parse("B3/S23").unwrap();
+        assert_eq!((vec![3], vec![2, 3]), (rule.birth, rule.survival));
+
+        let rule = LifeRule::parse("B/S2,3,10; 3:B1/S12").unwrap();
+        assert_eq!((Vec::<usize>::new(), vec![2, 3, 10]), (rule.birth, rule.survival));
+        assert_eq!(Some(&(vec![1], vec![1, 2])), rule.by_size.get(&3));
+
+        for notation in ["", "B3", "S23/B3", "B3/S23;B1/S1", "B3/S23;4:B1/S1;4:B2/S2", "B1,x/S2", "x:B1/S1"].iter() {
+            assert_eq!(Err(PatchError::InvalidRule { rule: String::from(*notation) }), LifeRule::parse(notation), "{}", notation);
+        }
+    }
+
+    #[test]
+    fn test_automaton_step() {
+        // under B1/S a lone live square dies and its four neighbors come alive
+        let mut patch = patch("4^4", 5.);
+        let mut automaton = Automaton::new(LifeRule::parse("B1/S").unwrap(), false);
+        automaton.set(&mut patch, &Point(0.5, 0.5), true).unwrap();
+        assert!(matches!(patch.drain_tile_diffs().as_slice(), [(_, TileDiff::Changed(_))]));
+        assert!(*automaton.get(&Point(0.5, 0.5)));
+
+        let changed = automaton.step(&mut patch).unwrap();
+        let neighbors = vec![Point(-0.5, 0.5), Point(0.5, -0.5), Point(0.5, 0.5), Point(0.5, 1.5), Point(1.5, 0.5)];
+        assert_eq!(neighbors, sorted(changed));
+        assert_eq!(1, automaton.generation);
+        assert!(!*automaton.get(&Point(0.5, 0.5)));
+        assert!(*automaton.get(&Point(1.5, 0.5)));
+
+        let tile_diffs = patch.drain_tile_diffs();
+        assert_eq!(neighbors, sorted(tile_diffs.iter().map(|(centroid, _)| *centroid).collect()));
+        assert!(tile_diffs.iter().all(|(_, tile_diff)| matches!(tile_diff, TileDiff::Changed(_))));
+
+        // clearing kills the four live squares
+        automaton.clear(&mut patch).unwrap();
+        assert_eq!(4, patch.drain_tile_diffs().len());
+        assert_eq!(0, automaton.generation);
+        assert!(automaton.step(&mut patch).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_automaton_custom_rule() {
+        // a tile well within the patch has as many included neighbors as it has edges
+        let mut patch = patch("3.12^2", 8.);
+        let mut automaton = Automaton::new(|_: &Tile, _: &usize, neighbors: &[&usize]| neighbors.len(), 0);
+        automaton.step(&mut patch).unwrap();
+        let mut sizes = vec![];
+        for rc_item in patch.patch_tiles.items.values() {
+            let patch_tile = rc_item.value();
+            if patch_tile.state.is_some() && patch_tile.tile.centroid.norm() < 4. {
+                assert_eq!(patch_tile.tile.size(), *automaton.get(&patch_tile.tile.centroid));
+                sizes.push(patch_tile.tile.size());
+            }
+        }
+        assert!(sizes.contains(&3) && sizes.contains(&12));
+
+        // there's no included tile far from the patch to set
+        patch.drain_tile_diffs();
+        automaton.set(&mut patch, &Point(100., 100.), 7).unwrap();
+        assert!(patch.drain_tile_diffs().is_empty());
+    }
+}
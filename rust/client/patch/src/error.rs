@@ -0,0 +1,66 @@
+use geometry::Point;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub enum PatchError {
+    // there's no VertexStar at point
+    MissingVertexStar { point: Point },
+    // the VertexStar tree has no VertexStar near point
+    NoNearbyVertexStar { point: Point },
+    // link_point isn't in the link of the VertexStar at point
+    NotInLink {
+        point: Point,
+        #[serde(rename = "linkPoint")]
+        link_point: Point,
+    },
+    // index is out of range for the link of the VertexStar at point
+    LinkIndexOutOfRange { point: Point, index: usize },
+    // the neighbor at index of the VertexStar at point couldn't be placed
+    MissingNeighborVertexStar { point: Point, index: usize },
+    // the atlas has no ProtoVertexStar at index
+    MissingProtoVertexStar { index: usize },
+    // there's no Tile at centroid
+    MissingTile { centroid: Point },
+    // the Tile at centroid is already borrowed
+    TileBorrowed { centroid: Point },
+    // the Tile at centroid doesn't have a vertex at point
+    TileNotAtVertexStar { centroid: Point, point: Point },
+    // walking towards point crossed too many Tiles
+    TooFar { point: Point },
+    // no point within the polygon centered at centroid could be found
+    NoInteriorPoint { centroid: Point },
+    // the Patch's atlas doesn't describe a periodic tiling whose symmetries could be found
+    Notation { message: String },
+    // the centroids of the Tiles around the VertexStar at point don't bound a polygon, so the Patch's dual isn't a polygon tiling
+    NonPolygonalDual { point: Point },
+    // rule isn't a Life-like rule in B/S notation
+    InvalidRule { rule: String },
+    UnsupportedSnapshotVersion { version: u32 },
+    Serialization { message: String },
+}
+
+impl std::fmt::Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PatchError::MissingVertexStar { point } => write!(f, "no VertexStar found at {}", point),
+            PatchError::NoNearbyVertexStar { point } => write!(f, "no nearby vertex stars to {}", point),
+            PatchError::NotInLink { point, link_point } => write!(f, "{} is not in the link of VertexStar {}", link_point, point),
+            PatchError::LinkIndexOutOfRange { point, index } => write!(f, "out of bounds index {} in VertexStar {}", index, point),
+            PatchError::MissingNeighborVertexStar { point, index } => write!(f, "unable to create neighbor VertexStar of VertexStar {} for neighbor index {}", point, index),
+            PatchError::MissingProtoVertexStar { index } => write!(f, "missing proto vertex star {}", index),
+            PatchError::MissingTile { centroid } => write!(f, "no tile found at centroid {}", centroid),
+            PatchError::TileBorrowed { centroid } => write!(f, "couldn't get mutable ref to tile at {}", centroid),
+            PatchError::TileNotAtVertexStar { centroid, point } => write!(f, "tile at {} isn't in the VertexStar at {}", centroid, point),
+            PatchError::TooFar { point } => write!(f, "unable to add tile at {} - too far", point),
+            PatchError::NoInteriorPoint { centroid } => write!(f, "couldn't find a point within the polygon at {} to fill", centroid),
+            PatchError::Notation { message } => write!(f, "couldn't find the symmetries of the patch's tiling: {}", message),
+            PatchError::NonPolygonalDual { point } => write!(f, "dual isn't a polygon tiling - the centroids of the tiles around {} don't bound a polygon", point),
+            PatchError::InvalidRule { rule } => write!(f, "invalid Life-like rule {:?}, expected B/S notation e.g. B3/S23", rule),
+            PatchError::UnsupportedSnapshotVersion { version } => write!(f, "unsupported patch snapshot version {}", version),
+            PatchError::Serialization { message } => write!(f, "couldn't (de)serialize patch snapshot: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
@@ -0,0 +1,89 @@
+use geometry::Point;
+use std::collections::VecDeque;
+
+pub const DEFAULT_HISTORY_CAPACITY: usize = 100;
+
+// TileChange records a single included Tile being added to or removed from a Patch, or having
+// its state replaced. Removed and Changed changes own the state the Tile had so that it can be restored.
+#[derive(Debug)]
+pub enum TileChange<State> {
+    Added(Point),
+    Removed(Point, State),
+    Changed(Point, State),
+}
+
+// Edit is the list of TileChanges made by one user-level operation on a Patch
+pub type Edit<State> = Vec<TileChange<State>>;
+
+#[derive(Debug)]
+pub struct History<State> {
+    pub capacity: usize, // maximum number of Edits which can be undone
+    pending: Edit<State>,
+    undo_stack: VecDeque<Edit<State>>,
+    redo_stack: Vec<Edit<State>>,
+}
+
+impl<State> History<State> {
+    pub fn new(capacity: usize) -> History<State> {
+        History {
+            capacity,
+            pending: vec![],
+            undo_stack: VecDeque::default(),
+            redo_stack: vec![],
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.undo_stack.len() > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.redo_stack.len() > 0
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    // record appends a TileChange to the Edit currently being built
+    pub(crate) fn record(&mut self, tile_change: TileChange<State>) {
+        self.pending.push(tile_change);
+    }
+
+    // commit closes the Edit currently being built and pushes it onto the undo stack.
+    // Making a new Edit invalidates anything which was previously undone.
+    pub(crate) fn commit(&mut self) {
+        if let Some(edit) = self.take_pending() {
+            self.push_undo(edit);
+            self.redo_stack.clear();
+        }
+    }
+
+    pub(crate) fn pop_undo(&mut self) -> Option<Edit<State>> {
+        self.undo_stack.pop_back()
+    }
+
+    pub(crate) fn pop_redo(&mut self) -> Option<Edit<State>> {
+        self.redo_stack.pop()
+    }
+
+    pub(crate) fn push_undo(&mut self, edit: Edit<State>) {
+        self.undo_stack.push_back(edit);
+        while self.undo_stack.len() > self.capacity {
+            self.undo_stack.pop_front();
+        }
+    }
+
+    pub(crate) fn push_redo(&mut self, edit: Edit<State>) {
+        self.redo_stack.push(edit);
+    }
+
+    pub(crate) fn take_pending(&mut self) -> Option<Edit<State>> {
+        if self.pending.len() == 0 {
+            return None
+        }
+        Some(self.pending.drain(..).collect())
+    }
+}
@@ -1,12 +1,26 @@
-use atlas::{Atlas, ProtoNeighbor, ProtoVertexStar};
+mod automaton;
+mod error;
+mod history;
+mod snapshot;
+mod uniform;
+
+pub use self::automaton::*;
+pub use self::error::*;
+pub use self::history::*;
+pub use self::snapshot::*;
+pub use self::uniform::*;
+
+use atlas::{Atlas, FundamentalDomain, ProtoNeighbor, ProtoVertexStar, UnitCell};
 use common::*;
-use geometry::{Affine, Bounds, Euclid, Point, Spatial, Transform, Transformable};
+use geometry::{Affine, Bounds, Euclid, Point, Spatial, Transform, Transformable, ORIGIN};
 use itertools::izip;
-use pmr_quad_tree::{Config as TreeConfig, Tree, WeakItem};
+use pmr_quad_tree::{Config as TreeConfig, RcItem, Tree, WeakItem};
 use std::{
-    collections::{HashMap, VecDeque},
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
     iter,
     f64::consts::TAU,
+    rc::Rc,
 };
 use tile::Tile;
 
@@ -22,14 +36,6 @@ pub struct VertexStar {
     pub link_arg_offset: usize,
 }
 
-#[derive(Debug)]
-pub enum VertexStarErr {
-    BadProtoRef,
-    BadRef,
-    ComponentOutOfBounds(usize, Point),
-    ProtoVertexStarErr(String),
-}
-
 impl VertexStar {
     pub fn new(atlas: &Atlas, point: Point, proto_vertex_star_index: usize, parity: bool, rotation: f64) -> VertexStar {
         let proto_vertex_star = atlas.proto_vertex_stars.get(proto_vertex_star_index).unwrap();
@@ -101,7 +107,7 @@ impl VertexStar {
         if !self.parity {
             Some((link_index + self.size() - 1) % self.size())
         } else {
-            Some((link_index + self.size() - 2) % self.size()) // have had trouble with this line in the past, need to test against a vertex star with more than 3 components
+            Some((link_index + 1) % self.size())
         }
     }
 
@@ -145,10 +151,7 @@ impl VertexStar {
     // get_tile creates the Tile situated clockwise of the given point in this VertexStar's link
     pub fn get_tile(&self, atlas: &Atlas, neighbor_point: &Point) -> Option<Tile> {
         let proto_vertex_star = match self.get_proto_vertex_star(atlas) { None => return None, Some(pvs) => pvs };
-        let mut tile_index = match self.link_map.get(neighbor_point) { None => return None, Some(i) => *i };
-        if !self.parity {
-            tile_index = (tile_index + self.size() - 1) % self.size();
-        }
+        let tile_index = self.get_tile_index(neighbor_point)?;
         let tile = match proto_vertex_star.tiles.get(tile_index) { None => return None, Some(pt) => pt };
         let reference_frame = VertexStar::reference_frame(self.parity, self.rotation);
         let mut tile = tile.transform(&reference_frame.transform(&Euclid::Translate(self.point.values())));
@@ -156,6 +159,24 @@ impl VertexStar {
         Some(tile)
     }
 
+    // get_polygon_index optionally returns the index in the atlas' polygons of the Tile situated clockwise of the given
+    // point in this VertexStar's link
+    pub fn get_polygon_index(&self, atlas: &Atlas, neighbor_point: &Point) -> Option<usize> {
+        let proto_vertex_star = self.get_proto_vertex_star(atlas)?;
+        let tile_index = self.get_tile_index(neighbor_point)?;
+        proto_vertex_star.polygon_indices.get(tile_index).copied()
+    }
+
+    // get_tile_index optionally returns the index in the underlying ProtoVertexStar's tiles of the Tile situated
+    // clockwise of the given point in this VertexStar's link
+    fn get_tile_index(&self, neighbor_point: &Point) -> Option<usize> {
+        let tile_index = *self.link_map.get(neighbor_point)?;
+        if !self.parity {
+            return Some((tile_index + self.size() - 1) % self.size())
+        }
+        Some(tile_index)
+    }
+
     // mutual_parity returns the XOR value of this VertexStar's parity with the provided parity.
     // This is useful for computing a new, neighboring VertexStar's parity.
     pub fn mutual_parity(&self, parity: bool) -> bool {
@@ -193,6 +214,8 @@ impl Spatial for VertexStar {
 pub enum TileDiff<State> {
     Added(WeakItem<Point, PatchTile<State>>),
     Removed(WeakItem<Point, PatchTile<State>>),
+    // Changed is an included Tile which stays included but should be redrawn, as its state or its Automaton state changed
+    Changed(WeakItem<Point, PatchTile<State>>),
 }
 
 #[derive(Debug)]
@@ -221,13 +244,26 @@ pub struct Patch<State> {
     pub tile_diffs: HashMap<Point, TileDiff<State>>,
     pub vertex_stars: Tree<Point, VertexStar>,
     pub patch_tiles: Tree<Point, PatchTile<State>>,
+    pub history: History<State>,
+    // cell is the unit cell stamped by fill_polygon_periodic, or why there's none, found the first time it's needed and
+    // shared rather than copied by each fill
+    cell: Option<Result<Rc<UnitCell>, PatchError>>,
 }
 
+const SEED_VERTEX_STAR_POINT: Point = Point(0., 0.);
+
+// maximum number of Tiles crossed when walking from the existing VertexStars towards a new Tile
+const MAX_WALK_LENGTH: usize = 100;
+
+// fraction of the distance from a polygon's point to its centroid at which fill_polygon looks for
+// a point within the polygon to walk towards, in case the centroid isn't within the polygon
+const INTERIOR_POINT_RATIO: f64 = 0.01;
+
 impl<State> Patch<State> {
     // new creates a new Patch and inserts a single VertexStar and its first Tile
-    pub fn new(atlas: Atlas, tile_tree_config: TreeConfig, vertex_star_tree_config: TreeConfig) -> Result<Patch<State>, String> {
+    pub fn new(atlas: Atlas, tile_tree_config: TreeConfig, vertex_star_tree_config: TreeConfig) -> Result<Patch<State>, PatchError> {
         let mut vertex_stars: Tree<Point, VertexStar> = Tree::new(vertex_star_tree_config, false);
-        vertex_stars.insert(VertexStar::new(&atlas, Point(0., 0.), 0, false, 0.));
+        vertex_stars.insert(VertexStar::new(&atlas, SEED_VERTEX_STAR_POINT, 0, false, 0.));
 
         let patch_tiles = Tree::new(tile_tree_config, true);
 
@@ -236,6 +272,8 @@ impl<State> Patch<State> {
             vertex_stars,
             patch_tiles,
             tile_diffs: HashMap::default(),
+            history: History::new(DEFAULT_HISTORY_CAPACITY),
+            cell: None,
         })
     }
 
@@ -243,43 +281,285 @@ impl<State> Patch<State> {
         self.tile_diffs.drain().collect()
     }
 
-    pub fn insert_tile_by_point(&mut self, point: Point, state: Option<State>) -> Result<(), String> {
-        let mut nearest_vertex_star = self.vertex_stars
-            .nearest_neighbor(&point)
-            .map_err(|e| format!("no nearby vertex stars:\n{}\n{:?}\n{:#?}", e, point, self.vertex_stars))?
-            .item
-            .upgrade()
-            .ok_or("vertex star doesn't exist")?;
+    pub fn insert_tile_by_point(&mut self, point: Point, state: Option<State>) -> Result<(), PatchError> {
+        let result = self.insert_tile(point, state);
+        self.history.commit();
+        result
+    }
+
+    // remove_tile_by_point removes the included Tile containing point, if there is one
+    pub fn remove_tile_by_point(&mut self, point: Point) -> Result<(), PatchError> {
+        let patch_tile_rc = match self.get_included_patch_tile_by_point(&point) { None => return Ok(()), Some(rc_item) => rc_item };
+        let centroid = patch_tile_rc.value().tile.centroid.clone();
+        self.remove_tile_by_centroid(&centroid)
+    }
 
-        for _ in 0..100 {
+    // remove_tile_by_centroid clears the state of the Tile at centroid so that it's no longer drawn,
+    // then prunes any of its VertexStars which no longer border an included Tile. The Tile itself
+    // is kept in patch_tiles so that the emitted TileDiff::Removed can still be read.
+    pub fn remove_tile_by_centroid(&mut self, centroid: &Point) -> Result<(), PatchError> {
+        let result = self.remove_tile(centroid);
+        self.history.commit();
+        result
+    }
+
+    // set_tile_state replaces the state of the included Tile containing point, if there is one,
+    // emitting a TileDiff::Changed so that it's redrawn
+    pub fn set_tile_state(&mut self, point: &Point, state: State) -> Result<(), PatchError> {
+        let patch_tile_rc = match self.get_included_patch_tile_by_point(point) { None => return Ok(()), Some(rc_item) => rc_item };
+        let centroid = patch_tile_rc.value().tile.centroid;
+        let result = self.change_tile_state(&centroid, state);
+        self.history.commit();
+        result
+    }
+
+    // undo reverts the most recent Edit made to this Patch
+    pub fn undo(&mut self) -> Result<(), PatchError> {
+        let edit = match self.history.pop_undo() { None => return Ok(()), Some(edit) => edit };
+        let result = self.revert_edit(edit);
+        if let Some(inverse_edit) = self.history.take_pending() {
+            self.history.push_redo(inverse_edit);
+        }
+        result
+    }
+
+    // redo reapplies the most recently undone Edit
+    pub fn redo(&mut self) -> Result<(), PatchError> {
+        let edit = match self.history.pop_redo() { None => return Ok(()), Some(edit) => edit };
+        let result = self.revert_edit(edit);
+        if let Some(inverse_edit) = self.history.take_pending() {
+            self.history.push_undo(inverse_edit);
+        }
+        result
+    }
+
+    fn insert_tile(&mut self, point: Point, state: Option<State>) -> Result<(), PatchError> {
+        let edge = self.walk_to_tile(&point, |tile| tile.contains(&point))?;
+        self.insert_adjacent_tile_by_edge(edge, state)
+    }
+
+    // walk_to_tile steps from the VertexStar nearest to point towards point, inserting each Tile it
+    // crosses as scaffolding, until it reaches a Tile for which is_destination holds. The edge along
+    // which that Tile can be inserted is returned.
+    fn walk_to_tile(&mut self, point: &Point, is_destination: impl Fn(&Tile) -> bool) -> Result<(Point, Point), PatchError> {
+        self.insert_vertex_stars_near(point)?;
+        let mut nearest_vertex_star = self.get_nearest_vertex_star(point)?;
+
+        for _ in 0..MAX_WALK_LENGTH {
             let next_vertex_star = nearest_vertex_star
                 .value()
-                .nearest_neighbor(&self.atlas, &point)
-                .ok_or("failed to find nearest vertex star")?;
+                .nearest_neighbor(&self.atlas, point)
+                .ok_or(PatchError::NoNearbyVertexStar { point: *point })?;
 
             let tile = nearest_vertex_star
                 .value()
                 .get_tile(&self.atlas, &next_vertex_star.point)
-                .ok_or("couldn't get new tile")?;
+                .ok_or(PatchError::NotInLink { point: nearest_vertex_star.value().point, link_point: next_vertex_star.point })?;
 
             let edge = (nearest_vertex_star.value().point.clone(), next_vertex_star.point.clone());
 
-            if tile.contains(&point) {
-                return self.insert_adjacent_tile_by_edge(edge, state);
+            if is_destination(&tile) {
+                return Ok(edge);
             } else {
                 self.insert_adjacent_tile_by_edge(edge, None)?;
                 if !self.vertex_stars.has(&next_vertex_star.point) {
                     self.vertex_stars.insert(next_vertex_star);
                 }
-                nearest_vertex_star = self.vertex_stars
-                    .nearest_neighbor(&point)
-                    .map_err(|e| format!("no nearby vertex stars:\n{}\n{:?}\n{:#?}", e, point, self.vertex_stars))?
-                    .item
-                    .upgrade()
-                    .ok_or("vertex star doesn't exist")?;
+                nearest_vertex_star = self.get_nearest_vertex_star(point)?;
             }
         }
-        Err(format!("unable to add tile - too far"))
+        Err(PatchError::TooFar { point: *point })
+    }
+
+    // insert_vertex_stars_near stamps the VertexStars of the translate of the unit cell containing point when the Patch
+    // has none within the cell's diameter of point, so that walking to point crosses only a few Tiles however far away
+    // it is. A Patch whose atlas has no unit cell is left to walk the whole way.
+    fn insert_vertex_stars_near(&mut self, point: &Point) -> Result<(), PatchError> {
+        let cell = match self.get_cell() { Ok(cell) => cell, Err(_) => return Ok(()) };
+        let nearest_point = self.get_nearest_vertex_star(point)?.value().point;
+        if (&nearest_point - point).norm() <= cell.translations.0.norm() + cell.translations.1.norm() {
+            return Ok(())
+        }
+        let (i, j) = cell.index(point);
+        let translation = cell.translation(i, j);
+        for (_, placement) in cell.vertex_stars.iter() {
+            self.insert_cell_vertex_star(&cell, &(&ORIGIN.transform(placement) + &translation))?;
+        }
+        Ok(())
+    }
+
+    // insert_cell_vertex_star inserts the VertexStar at point, unless there already is one, placing it as the
+    // translate of the cell's VertexStar there
+    fn insert_cell_vertex_star(&mut self, cell: &UnitCell, point: &Point) -> Result<(), PatchError> {
+        if self.vertex_stars.has(point) {
+            return Ok(())
+        }
+        let (i, j) = cell.index(point);
+        let cell_point = point - &cell.translation(i, j);
+        let (proto_vertex_star_index, placement) = cell.vertex_stars
+            .iter()
+            .find(|(_, placement)| ORIGIN.transform(placement) == cell_point)
+            .ok_or(PatchError::MissingVertexStar { point: *point })?;
+        let rotation = rad(placement.0[1][0].atan2(placement.0[0][0]));
+        self.vertex_stars.insert(VertexStar::new(&self.atlas, *point, *proto_vertex_star_index, placement.is_flip(), rotation));
+        Ok(())
+    }
+
+    // get_cell returns the Patch's unit cell, finding it the first time it's needed, so that it's found once for the
+    // Patch and whatever else needs the cell of its tiling
+    pub fn get_cell(&mut self) -> Result<Rc<UnitCell>, PatchError> {
+        match &self.cell {
+            Some(cell) => cell.clone(),
+            None => {
+                let cell = self.unit_cell().map(Rc::new);
+                self.cell = Some(cell.clone());
+                cell
+            },
+        }
+    }
+
+    fn get_nearest_vertex_star(&self, point: &Point) -> Result<RcItem<Point, VertexStar>, PatchError> {
+        self.vertex_stars
+            .nearest_neighbor(point)
+            .ok()
+            .and_then(|neighbor| neighbor.item.upgrade())
+            .ok_or(PatchError::NoNearbyVertexStar { point: *point })
+    }
+
+    fn remove_tile(&mut self, centroid: &Point) -> Result<(), PatchError> {
+        let mut patch_tile_item = self.patch_tiles.get(centroid)
+            .ok_or(PatchError::MissingTile { centroid: *centroid })?;
+
+        let points = patch_tile_item.value().tile.points.clone();
+        let state = patch_tile_item.value_mut().map_err(|_| PatchError::TileBorrowed { centroid: *centroid })?.state.take();
+        let state = match state { None => return Ok(()), Some(state) => state };
+
+        self.insert_tile_diff(centroid.clone(), TileDiff::Removed(patch_tile_item.downgrade()))?;
+        self.history.record(TileChange::Removed(centroid.clone(), state));
+
+        for point in points.iter() {
+            if *point != SEED_VERTEX_STAR_POINT && self.get_included_patch_tiles_by_vertex(point).len() == 0 {
+                self.vertex_stars.remove(point);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn change_tile_state(&mut self, centroid: &Point, state: State) -> Result<(), PatchError> {
+        let mut patch_tile_item = self.patch_tiles.get(centroid)
+            .ok_or(PatchError::MissingTile { centroid: *centroid })?;
+
+        let previous_state = {
+            let mut patch_tile = patch_tile_item.value_mut().map_err(|_| PatchError::TileBorrowed { centroid: *centroid })?;
+            if patch_tile.state.is_none() {
+                return Ok(())
+            }
+            patch_tile.state.replace(state)
+        };
+        if let Some(previous_state) = previous_state {
+            self.history.record(TileChange::Changed(*centroid, previous_state));
+        }
+        self.insert_tile_diff(*centroid, TileDiff::Changed(patch_tile_item.downgrade()))
+    }
+
+    // revert_edit applies the inverse of each TileChange in edit, most recent first. The
+    // TileChanges made while doing so are recorded as usual, forming the inverse Edit.
+    fn revert_edit(&mut self, edit: Edit<State>) -> Result<(), PatchError> {
+        for tile_change in edit.into_iter().rev() {
+            match tile_change {
+                TileChange::Added(centroid) => self.remove_tile(&centroid)?,
+                TileChange::Removed(centroid, state) => self.insert_tile(centroid, Some(state))?,
+                TileChange::Changed(centroid, state) => self.change_tile_state(&centroid, state)?,
+            };
+        }
+        Ok(())
+    }
+
+    // unit_cell returns a unit cell of the Patch's tiling, placed where the Patch places its Tiles
+    pub fn unit_cell(&self) -> Result<UnitCell, PatchError> {
+        atlas::unit_cell(&self.atlas).map_err(|e| PatchError::Notation { message: e.to_string() })
+    }
+
+    // fundamental_domain returns a fundamental domain of the Patch's tiling under its symmetry group, placed
+    // where the Patch places its Tiles
+    pub fn fundamental_domain(&self) -> Result<FundamentalDomain, PatchError> {
+        atlas::fundamental_domain(&self.atlas).map_err(|e| PatchError::Notation { message: e.to_string() })
+    }
+
+    // dual_tiles returns the Tiles of the dual of the included Tiles, one around each VertexStar whose Tiles are all
+    // included, with its points at those Tiles' centroids. They're ordered by centroid so that equal Patches have equal
+    // duals.
+    pub fn dual_tiles(&self) -> Result<Vec<Tile>, PatchError> {
+        let mut dual_tiles: Vec<Tile> = vec![];
+        for vertex_star_rc in self.vertex_stars.items.values() {
+            let vertex_star = vertex_star_rc.value();
+            let mut centroids: Vec<Point> = Vec::with_capacity(vertex_star.size());
+            for link_point in vertex_star.link_vec.iter() {
+                let tile = vertex_star.get_tile(&self.atlas, link_point)
+                    .ok_or(PatchError::NotInLink { point: vertex_star.point, link_point: *link_point })?;
+                match self.patch_tiles.get(&tile.centroid) {
+                    Some(patch_tile_rc) if patch_tile_rc.value().state.is_some() => centroids.push(tile.centroid),
+                    _ => break,
+                }
+            }
+            if centroids.len() < vertex_star.size() {
+                continue
+            }
+            // a reflected VertexStar's link runs clockwise
+            if vertex_star.parity {
+                centroids.reverse();
+            }
+            dual_tiles.push(atlas::dual_tile(centroids).ok_or(PatchError::NonPolygonalDual { point: vertex_star.point })?);
+        }
+        dual_tiles.sort_by(|a, b|
+            a.centroid.0.partial_cmp(&b.centroid.0).unwrap_or(Ordering::Equal)
+                .then(a.centroid.1.partial_cmp(&b.centroid.1).unwrap_or(Ordering::Equal))
+        );
+        Ok(dual_tiles)
+    }
+
+    // get_included_patch_tile_by_point optionally returns the included Tile containing point
+    pub fn get_included_patch_tile_by_point(&self, point: &Point) -> Option<RcItem<Point, PatchTile<State>>> {
+        let nearest_patch_tile = match self.patch_tiles.nearest_neighbor(point).ok() { Some(a) => a, _ => return None };
+        let nearest_patch_tile_rc = match nearest_patch_tile.item.upgrade() { Some(a) => a, _ => return None };
+        if !nearest_patch_tile_rc.value().tile.contains(point) {
+            return None
+        }
+        if let None = &nearest_patch_tile_rc.value().state {
+            return None
+        }
+        Some(nearest_patch_tile_rc)
+    }
+
+    // get_included_patch_tiles_by_vertex returns the included Tiles which have a vertex at point
+    pub fn get_included_patch_tiles_by_vertex(&self, point: &Point) -> Vec<RcItem<Point, PatchTile<State>>> {
+        let vertex_star_rc = match self.vertex_stars.get(point) { None => return vec![], Some(rc_item) => rc_item };
+        let vertex_star = vertex_star_rc.value();
+        vertex_star.link_vec
+            .iter()
+            .filter_map(|link_point| {
+                let tile = match vertex_star.get_tile(&self.atlas, link_point) { None => return None, Some(tile) => tile };
+                let patch_tile_rc = match self.patch_tiles.get(&tile.centroid) { None => return None, Some(rc_item) => rc_item };
+                let is_included = if let Some(_) = &patch_tile_rc.value().state { true } else { false };
+                if is_included { Some(patch_tile_rc) } else { None }
+            })
+            .collect()
+    }
+
+    // get_polygon_index optionally returns the index in the atlas' polygons of tile, a Tile of this Patch, found from the
+    // VertexStar at its first point
+    pub fn get_polygon_index(&self, tile: &Tile) -> Option<usize> {
+        let anchor = tile.points.first()?;
+        let vertex_star_rc = self.vertex_stars.get(anchor)?;
+        let vertex_star = vertex_star_rc.value();
+        vertex_star.link_vec
+            .iter()
+            .find(|link_point| match vertex_star.get_tile(&self.atlas, link_point) {
+                None => false,
+                Some(link_tile) => link_tile.centroid == tile.centroid,
+            })
+            .and_then(|link_point| vertex_star.get_polygon_index(&self.atlas, link_point))
     }
 
     pub fn get_tile_neighbor_centroids(&self, point: &Point) -> Option<Vec<Point>> {
@@ -308,39 +588,54 @@ impl<State> Patch<State> {
         None
     }
 
+    // get_included_adjacency returns the centroids of every included Tile paired with those of each of its included
+    // neighbors, so that each pair of neighbors appears in both orders
+    pub fn get_included_adjacency(&self) -> Vec<(Point, Point)> {
+        self.patch_tiles.items
+            .values()
+            .filter(|patch_tile_rc| patch_tile_rc.value().state.is_some())
+            .flat_map(|patch_tile_rc| {
+                let centroid = patch_tile_rc.value().tile.centroid;
+                patch_tile_rc
+                    .neighbors()
+                    .iter()
+                    .filter(|(_, patch_tile_weak)| patch_tile_weak.upgrade().map_or(false, |neighbor_rc| neighbor_rc.value().state.is_some()))
+                    .map(|(neighbor_centroid, _)| (centroid, *neighbor_centroid))
+                    .collect::<Vec<(Point, Point)>>()
+            })
+            .collect()
+    }
+
     // insert_adjacent_tile_by_edge inserts a new Tile into this Patch
     // given a particular edge along which the Tile shares. In order to succeed,
     // both points in the edge are expected to be points of existing VertexStars
     // in this Patch. If both exist, the new Tile will be added starboard of the
     // edge drawn from start to stop.
-    fn insert_adjacent_tile_by_edge(&mut self, (start, stop): (Point, Point), state: Option<State>) -> Result<(), String> {
+    fn insert_adjacent_tile_by_edge(&mut self, (start, stop): (Point, Point), state: Option<State>) -> Result<(), PatchError> {
         let start_vertex_star_rc = self.vertex_stars.get(&start)
-            .ok_or_else(|| String::from(format!("no VertexStar found at start {}", start)))?;
+            .ok_or(PatchError::MissingVertexStar { point: start })?;
         let start_vertex_star = start_vertex_star_rc.value();
-        let tile = start_vertex_star.get_tile(&self.atlas, &stop).ok_or_else(|| String::from(format!("stop {} is not in the link of start {}", stop, start)))?;
+        let tile = start_vertex_star.get_tile(&self.atlas, &stop).ok_or(PatchError::NotInLink { point: start, link_point: stop })?;
 
         // store info we need after move
         let centroid = tile.centroid.clone();
         let tile_size = tile.size();
         let included = if let Some(_) = &state { true } else { false };
 
-        if let Some(patch_tile_item) = self.patch_tiles.get(&centroid) {
-            if included {
-                if let None = &patch_tile_item.value().state {
-                    self.patch_tiles.insert(PatchTile { tile, state });
-                    self.insert_tile_diff(centroid, TileDiff::Added(
-                        self.patch_tiles
-                            .get(&centroid)
-                            .ok_or_else(|| String::from("unable to retrieve newly inserted tile"))?
-                            .downgrade()
-                    ))?;
-                }
+        if let Some(mut patch_tile_item) = self.patch_tiles.get(&centroid) {
+            if !included {
+                return Ok(())
             }
-            return Ok(())
+            if let Some(_) = &patch_tile_item.value().state {
+                return Ok(())
+            }
+            // the tile was previously removed or only ever inserted as a stepping stone,
+            // fall through so that any of its pruned VertexStars are restored
+            patch_tile_item.value_mut().map_err(|_| PatchError::TileBorrowed { centroid })?.state = state;
+        } else {
+            self.patch_tiles.insert(PatchTile { tile, state });
         }
 
-        self.patch_tiles.insert(PatchTile { tile, state });
-
         let mut link_points: Vec<(usize, Point)> = vec![(0, stop.clone()), (0, start.clone())];
         let mut new_link_points: Vec<(usize, Point)> = vec![];
         let mut reverse = stop.clone();
@@ -348,17 +643,17 @@ impl<State> Patch<State> {
 
         for _ in 0 .. tile_size - 1 {
             let middle_vertex_star_rc = self.vertex_stars.get(&middle)
-                .ok_or_else(|| String::from(format!("missing VertexStar at {}", middle)))?;
+                .ok_or(PatchError::MissingVertexStar { point: middle })?;
             let middle_vertex_star = middle_vertex_star_rc.value();
-            let forward_index = middle_vertex_star.get_clockwise_adjacent_link_index(&reverse).ok_or_else(|| String::from(format!("no link point found clockwise adjacent of {} for VertexStar {}", reverse, middle)))?;
-            let forward = middle_vertex_star.link_vec.get(forward_index).map(|p| p.clone()).ok_or_else(|| String::from(format!("out of bounds index {} in VertexStar {}", forward_index, middle)))?;
+            let forward_index = middle_vertex_star.get_clockwise_adjacent_link_index(&reverse).ok_or(PatchError::NotInLink { point: middle, link_point: reverse })?;
+            let forward = middle_vertex_star.link_vec.get(forward_index).map(|p| p.clone()).ok_or(PatchError::LinkIndexOutOfRange { point: middle, index: forward_index })?;
             link_points.push((forward_index, forward));
             if let Some(forward_vertex_star_rc) = self.vertex_stars.get(&forward) {
                 reverse = middle;
                 middle = forward_vertex_star_rc.value().point.clone();
             } else {
                 let vs = middle_vertex_star.get_neighbor_vertex_star(&self.atlas, forward_index)
-                    .ok_or_else(|| String::from(format!("unable to create neighbor VertexStar of VertexStar {} for neighbor index {} at point {}", middle, forward_index, forward)))?;
+                    .ok_or(PatchError::MissingNeighborVertexStar { point: middle, index: forward_index })?;
                 reverse = middle;
                 middle = vs.point.clone();
                 if !self.vertex_stars.has(&forward) {
@@ -369,10 +664,11 @@ impl<State> Patch<State> {
         }
 
         if included {
+            self.history.record(TileChange::Added(centroid.clone()));
             self.insert_tile_diff(centroid, TileDiff::Added(
                 self.patch_tiles
                     .get(&centroid)
-                    .ok_or_else(|| String::from("unable to retrieve newly inserted tile"))?
+                    .ok_or(PatchError::MissingTile { centroid })?
                     .downgrade()
             ))?;
         }
@@ -380,40 +676,44 @@ impl<State> Patch<State> {
         Ok(())
     }
 
-    fn insert_tile_diff(&mut self, centroid: Point, tile_diff: TileDiff<State>) -> Result<(), String> {
-        if match tile_diff { TileDiff::Added(_) => true, TileDiff::Removed(_) => true } {
-            self.update_neighbors_after_tile_diff(&centroid, &tile_diff).or_else(|e| Err(format!("couldn't update neighbors: {}", e)))?;
+    fn insert_tile_diff(&mut self, centroid: Point, tile_diff: TileDiff<State>) -> Result<(), PatchError> {
+        if match tile_diff { TileDiff::Added(_) => true, TileDiff::Removed(_) => true, TileDiff::Changed(_) => false } {
+            self.update_neighbors_after_tile_diff(&centroid, &tile_diff)?;
         }
         self.tile_diffs.insert(centroid, tile_diff);
         Ok(())
     }
 
-    fn update_neighbors_after_tile_diff(&mut self, tile_centroid: &Point, tile_diff: &TileDiff<State>) -> Result<(), String> {
-        let mut rc_item = self.patch_tiles.get(tile_centroid).ok_or_else(|| String::from("couldn't get patch tile"))?;
+    fn update_neighbors_after_tile_diff(&mut self, tile_centroid: &Point, tile_diff: &TileDiff<State>) -> Result<(), PatchError> {
+        let mut rc_item = self.patch_tiles.get(tile_centroid).ok_or(PatchError::MissingTile { centroid: *tile_centroid })?;
 
-        let neighbor_centroids: Vec<Result<Point, String>> = {
+        // the Tile clockwise of an edge's stop in its start's VertexStar is the Tile itself rather than its neighbor
+        // when the edge runs clockwise around the Tile, as it does for Tiles of reflected VertexStars
+        let get_clockwise_centroid = |start: &Point, stop: &Point| -> Result<Point, PatchError> {
+            let vertex_star_rc = self.vertex_stars.get(start).ok_or(PatchError::MissingVertexStar { point: *start })?;
+            let vertex_star = vertex_star_rc.value();
+            let tile = vertex_star.get_tile(&self.atlas, stop).ok_or(PatchError::NotInLink { point: vertex_star.point, link_point: *stop })?;
+            Ok(tile.centroid)
+        };
+        let neighbor_centroids: Vec<Result<Point, PatchError>> = {
             let value = rc_item.value();
             Point::edges(&value.tile.points)
                 .into_iter()
                 .filter_map(|edge| {
-                    if let Some(vertex_star_rc) = self.vertex_stars.get(edge.0) {
-                        let vertex_star = vertex_star_rc.value();
-                        if let Some(tile) = vertex_star.get_tile(&self.atlas, edge.1) {
-                            if tile.centroid != *tile_centroid {
-                                return Some(Ok(tile.centroid))
-                            }
-                            return None
-                        } else {
-                            return Some(Err(format!("couldn't get tile centroid of vertex star at {} along edge {}", vertex_star.point, edge.1)))
-                        }
-                    } else {
-                        return Some(Err(format!("couldn't get vertex star along edge {}", edge.0)))
+                    match get_clockwise_centroid(edge.0, edge.1) {
+                        Ok(centroid) if centroid != *tile_centroid => Some(Ok(centroid)),
+                        Ok(_) => match get_clockwise_centroid(edge.1, edge.0) {
+                            Ok(centroid) if centroid != *tile_centroid => Some(Ok(centroid)),
+                            Ok(_) => None,
+                            Err(err) => Some(Err(err)),
+                        },
+                        Err(err) => Some(Err(err)),
                     }
                 })
                 .collect()
         };
 
-        let mut item_neighbors = rc_item.neighbors_mut().map_err(|_| String::from("couldn't access mutable ref of patch tile"))?;
+        let mut item_neighbors = rc_item.neighbors_mut().map_err(|_| PatchError::TileBorrowed { centroid: *tile_centroid })?;
 
         for centroid in neighbor_centroids.into_iter() {
             let centroid = centroid?;
@@ -422,15 +722,17 @@ impl<State> Patch<State> {
                 match tile_diff {
                     TileDiff::Added(_) => item_neighbors.insert(centroid, neighbor_rc_item.downgrade()),
                     TileDiff::Removed(_) => item_neighbors.remove(&centroid),
+                    TileDiff::Changed(_) => None,
                 };
 
-                let rc_item = self.patch_tiles.get(tile_centroid).ok_or_else(|| format!("couldn't get neighbor's tile at {}", tile_centroid))?;
-                let mut neighbor_item_neighbors = neighbor_rc_item.neighbors_mut().map_err(|_| format!("couldn't get mutable ref to neighbor at {}", tile_centroid))?;
+                let rc_item = self.patch_tiles.get(tile_centroid).ok_or(PatchError::MissingTile { centroid: *tile_centroid })?;
+                let mut neighbor_item_neighbors = neighbor_rc_item.neighbors_mut().map_err(|_| PatchError::TileBorrowed { centroid })?;
 
                 // update tile's neighbor's neighbors with updated tile
                 match tile_diff {
                     TileDiff::Added(_) => neighbor_item_neighbors.insert(rc_item.value().tile.centroid.clone(), rc_item.downgrade()),
                     TileDiff::Removed(_) => neighbor_item_neighbors.remove(&rc_item.value().tile.centroid),
+                    TileDiff::Changed(_) => None,
                 };
             }
         }
@@ -438,12 +740,241 @@ impl<State> Patch<State> {
     }
 }
 
+impl<State: Clone> Patch<State> {
+    // fill_bounds includes every Tile overlapping bounds, see fill_polygon
+    pub fn fill_bounds(&mut self, bounds: &Bounds, state: State) -> Result<Vec<(Point, TileDiff<State>)>, PatchError> {
+        let Bounds { center, radius } = bounds;
+        self.fill_polygon(
+            &Tile::new(vec![
+                center + &Point(-radius, -radius),
+                center + &Point(*radius, -radius),
+                center + &Point(*radius, *radius),
+                center + &Point(-radius, *radius),
+            ]),
+            state,
+        )
+    }
+
+    // fill_polygon includes every Tile overlapping polygon with the provided state. The Patch first walks
+    // from its nearest VertexStar towards polygon until reaching an overlapping Tile and then grows outward
+    // from there, across the VertexStars of each Tile it includes, for as long as it finds more overlapping
+    // Tiles. The TileDiffs of the Tiles added are taken out of tile_diffs and returned, and the whole fill is
+    // recorded as a single Edit.
+    pub fn fill_polygon(&mut self, polygon: &Tile, state: State) -> Result<Vec<(Point, TileDiff<State>)>, PatchError> {
+        let result = self.fill(polygon, state);
+        self.history.commit();
+        let added_centroids = result?;
+        Ok(
+            added_centroids
+                .into_iter()
+                .filter_map(|centroid| self.tile_diffs.remove(&centroid).map(|tile_diff| (centroid, tile_diff)))
+                .collect()
+        )
+    }
+
+    // fill_bounds_periodic includes every Tile overlapping bounds, see fill_polygon_periodic
+    pub fn fill_bounds_periodic(&mut self, bounds: &Bounds, state: State) -> Result<Vec<(Point, TileDiff<State>)>, PatchError> {
+        let Bounds { center, radius } = bounds;
+        self.fill_polygon_periodic(
+            &Tile::new(vec![
+                center + &Point(-radius, -radius),
+                center + &Point(*radius, -radius),
+                center + &Point(*radius, *radius),
+                center + &Point(-radius, *radius),
+            ]),
+            state,
+        )
+    }
+
+    // fill_polygon_periodic includes every Tile overlapping polygon with the provided state, as fill_polygon does,
+    // but stamps the translates of the Patch's unit cell covering polygon instead of growing outward tile by tile,
+    // which makes filling large polygons far faster. A Patch whose atlas has no unit cell is filled by fill_polygon.
+    pub fn fill_polygon_periodic(&mut self, polygon: &Tile, state: State) -> Result<Vec<(Point, TileDiff<State>)>, PatchError> {
+        let result = match self.get_cell() {
+            Ok(cell) => self.fill_periodic(&cell, polygon, state),
+            Err(_) => self.fill(polygon, state),
+        };
+        self.history.commit();
+        let added_centroids = result?;
+        Ok(
+            added_centroids
+                .into_iter()
+                .filter_map(|centroid| self.tile_diffs.remove(&centroid).map(|tile_diff| (centroid, tile_diff)))
+                .collect()
+        )
+    }
+
+    // fill_periodic returns the centroids of the Tiles included while stamping cell over polygon. Every VertexStar
+    // of a stamped Tile is stamped along with it so that its neighbors are linked as they are when walking.
+    fn fill_periodic(&mut self, cell: &UnitCell, polygon: &Tile, state: State) -> Result<Vec<Point>, PatchError> {
+        // a Tile overlapping polygon has its centroid within reach of one of polygon's points, or within polygon
+        let reach = cell.tiles
+            .iter()
+            .flat_map(|tile| tile.points.iter().map(move |point| (point - &tile.centroid).norm()))
+            .fold(0., f64::max);
+        let indices = polygon.points
+            .iter()
+            .flat_map(|point| {
+                [Point(-reach, -reach), Point(reach, -reach), Point(reach, reach), Point(-reach, reach)]
+                    .iter()
+                    .map(|offset| cell.index(&(point + offset)))
+                    .collect::<Vec<(i64, i64)>>()
+            })
+            .collect::<Vec<(i64, i64)>>();
+        let (i_min, i_max) = indices.iter().fold((i64::MAX, i64::MIN), |(min, max), (i, _)| (min.min(*i), max.max(*i)));
+        let (j_min, j_max) = indices.iter().fold((i64::MAX, i64::MIN), |(min, max), (_, j)| (min.min(*j), max.max(*j)));
+
+        let mut added_centroids: Vec<Point> = vec![];
+        for i in i_min..=i_max {
+            for j in j_min..=j_max {
+                for tile in cell.tiles_at(i, j).into_iter().filter(|tile| tile.overlaps(polygon)) {
+                    for point in tile.points.iter() {
+                        self.insert_cell_vertex_star(cell, point)?;
+                    }
+                    let centroid = tile.centroid;
+                    if self.include_tile(tile, state.clone())? {
+                        added_centroids.push(centroid);
+                    }
+                }
+            }
+        }
+        Ok(added_centroids)
+    }
+
+    // include_tile includes tile with the provided state, inserting it into patch_tiles if it isn't there yet.
+    // It returns whether the Tile wasn't already included.
+    fn include_tile(&mut self, tile: Tile, state: State) -> Result<bool, PatchError> {
+        let centroid = tile.centroid;
+        if let Some(mut patch_tile_item) = self.patch_tiles.get(&centroid) {
+            if patch_tile_item.value().state.is_some() {
+                return Ok(false)
+            }
+            patch_tile_item.value_mut().map_err(|_| PatchError::TileBorrowed { centroid })?.state = Some(state);
+        } else {
+            self.patch_tiles.insert(PatchTile { tile, state: Some(state) });
+        }
+
+        self.history.record(TileChange::Added(centroid));
+        self.insert_tile_diff(centroid, TileDiff::Added(
+            self.patch_tiles
+                .get(&centroid)
+                .ok_or(PatchError::MissingTile { centroid })?
+                .downgrade()
+        ))?;
+        Ok(true)
+    }
+
+    // fill returns the centroids of the Tiles included while filling polygon
+    fn fill(&mut self, polygon: &Tile, state: State) -> Result<Vec<Point>, PatchError> {
+        let interior_point = iter::once(polygon.centroid)
+            .chain(polygon.points.iter().map(|point| point + &(&polygon.centroid - point).mul(INTERIOR_POINT_RATIO)))
+            .find(|point| polygon.contains(point))
+            .ok_or(PatchError::NoInteriorPoint { centroid: polygon.centroid })?;
+
+        let seed_edge = self.walk_to_tile(&interior_point, |tile| tile.overlaps(polygon))?;
+
+        let mut added_centroids: Vec<Point> = vec![];
+        let mut visited_centroids: HashSet<Point> = HashSet::default();
+        let mut queue: VecDeque<(Point, Point)> = VecDeque::new();
+        queue.push_back(seed_edge);
+
+        while let Some((start, stop)) = queue.pop_front() {
+            let tile = self.vertex_stars.get(&start)
+                .ok_or(PatchError::MissingVertexStar { point: start })?
+                .value()
+                .get_tile(&self.atlas, &stop)
+                .ok_or(PatchError::NotInLink { point: start, link_point: stop })?;
+
+            if !visited_centroids.insert(tile.centroid) || !tile.overlaps(polygon) {
+                continue
+            }
+
+            let is_included = match self.patch_tiles.get(&tile.centroid) { None => false, Some(rc_item) => rc_item.value().state.is_some() };
+            self.insert_adjacent_tile_by_edge((start, stop), Some(state.clone()))?;
+            if !is_included {
+                added_centroids.push(tile.centroid);
+            }
+
+            // every point of an inserted Tile has a VertexStar, queue each Tile around them
+            for point in tile.points.iter() {
+                let vertex_star_rc = self.vertex_stars.get(point)
+                    .ok_or(PatchError::MissingVertexStar { point: *point })?;
+                for link_point in vertex_star_rc.value().link_vec.iter() {
+                    queue.push_back((*point, *link_point));
+                }
+            }
+        }
+
+        Ok(added_centroids)
+    }
+
+    // grow_coronas includes the Tile at seed_centroid along with its first k coronas, where the
+    // i-th corona is every Tile sharing a point with the (i-1)-th corona which isn't already in an
+    // earlier one. Tiles meeting a corona at only a vertex belong to the next corona, so that each
+    // corona surrounds those before it, which is why coronas are grown across the VertexStars of
+    // each Tile rather than across its edges alone. The centroids of the Tiles in each corona are
+    // returned, indexed by corona, with the seed Tile as the 0-th corona. The whole growth is
+    // recorded as a single Edit.
+    pub fn grow_coronas(&mut self, seed_centroid: &Point, k: usize, state: State) -> Result<Vec<Vec<Point>>, PatchError> {
+        let result = self.grow(seed_centroid, k, state);
+        self.history.commit();
+        result
+    }
+
+    fn grow(&mut self, seed_centroid: &Point, k: usize, state: State) -> Result<Vec<Vec<Point>>, PatchError> {
+        self.insert_tile(*seed_centroid, Some(state.clone()))?;
+        let seed_tile = match self.get_included_patch_tile_by_point(seed_centroid) {
+            None => return Err(PatchError::MissingTile { centroid: *seed_centroid }),
+            Some(rc_item) => rc_item.value().tile.clone(),
+        };
+
+        let mut visited_centroids: HashSet<Point> = HashSet::default();
+        visited_centroids.insert(seed_tile.centroid);
+
+        let mut coronas: Vec<Vec<Point>> = Vec::with_capacity(k + 1);
+        coronas.push(vec![seed_tile.centroid]);
+
+        let mut corona_tiles: Vec<Tile> = vec![seed_tile];
+        for _ in 0..k {
+            let mut next_corona_tiles: Vec<Tile> = vec![];
+            for tile in corona_tiles.iter() {
+                for point in tile.points.iter() {
+                    let edges_and_tiles = {
+                        let vertex_star_rc = self.vertex_stars.get(point)
+                            .ok_or(PatchError::MissingVertexStar { point: *point })?;
+                        let vertex_star = vertex_star_rc.value();
+                        vertex_star.link_vec
+                            .iter()
+                            .map(|link_point| match vertex_star.get_tile(&self.atlas, link_point) {
+                                None => Err(PatchError::NotInLink { point: *point, link_point: *link_point }),
+                                Some(tile) => Ok(((*point, *link_point), tile)),
+                            })
+                            .collect::<Result<Vec<((Point, Point), Tile)>, PatchError>>()?
+                    };
+                    for (edge, neighbor_tile) in edges_and_tiles.into_iter() {
+                        if visited_centroids.insert(neighbor_tile.centroid) {
+                            self.insert_adjacent_tile_by_edge(edge, Some(state.clone()))?;
+                            next_corona_tiles.push(neighbor_tile);
+                        }
+                    }
+                }
+            }
+            coronas.push(next_corona_tiles.iter().map(|tile| tile.centroid).collect());
+            corona_tiles = next_corona_tiles;
+        }
+
+        Ok(coronas)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use atlas::VertexStarTransform;
+    use atlas::{VertexConfiguration, VertexStarTransform};
+    use float_cmp::ApproxEq;
     use tile::Tile;
     use geometry::Point;
+    use models::{FullAtlasEdgePost, FullAtlasVertexPost};
     use std::f64::consts::{PI, TAU};
 
     const ORIGIN: Point = Point(0., 0.);
@@ -618,6 +1149,7 @@ mod tests {
                             parity: false,
                         },
                     ],
+                    polygon_indices: vec![0, 0, 0, 0, 0, 0],
                     proto_neighbors: vec![
                         ProtoNeighbor {
                             proto_vertex_star_index: 0,
@@ -780,6 +1312,7 @@ mod tests {
                             parity: false,
                         },
                     ],
+                    polygon_indices: vec![0, 0, 0, 0],
                     proto_neighbors: vec![
                         ProtoNeighbor {
                             proto_vertex_star_index: 0,
@@ -912,6 +1445,7 @@ mod tests {
                             parity: false,
                         },
                     ],
+                    polygon_indices: vec![0, 0, 0],
                     proto_neighbors: vec![
                         ProtoNeighbor {
                             proto_vertex_star_index: 0,
@@ -1051,6 +1585,7 @@ mod tests {
                             parity: false,
                         },
                     ],
+                    polygon_indices: vec![0, 1, 1],
                     proto_neighbors: vec![
                         ProtoNeighbor {
                             proto_vertex_star_index: 0,
@@ -1180,6 +1715,7 @@ mod tests {
                             parity: false,
                         },
                     ],
+                    polygon_indices: vec![2, 1, 0],
                     proto_neighbors: vec![
                         ProtoNeighbor {
                             proto_vertex_star_index: 0,
@@ -1375,6 +1911,7 @@ mod tests {
                             parity: false,
                         },
                     ],
+                    polygon_indices: vec![2, 1],
                     proto_neighbors: vec![
                         ProtoNeighbor {
                             proto_vertex_star_index: 1,
@@ -1468,6 +2005,7 @@ mod tests {
                             parity: false,
                         },
                     ],
+                    polygon_indices: vec![1, 0, 1, 2],
                     proto_neighbors: vec![
                         ProtoNeighbor {
                             proto_vertex_star_index: 0,
@@ -1547,6 +2085,7 @@ mod tests {
                             parity: false,
                         },
                     ],
+                    polygon_indices: vec![0, 1],
                     proto_neighbors: vec![
                         ProtoNeighbor {
                             proto_vertex_star_index: 1,
@@ -1745,6 +2284,7 @@ mod tests {
                             parity: false,
                         },
                     ],
+                    polygon_indices: vec![1, 0, 1, 2],
                     proto_neighbors: vec![
                         ProtoNeighbor {
                             proto_vertex_star_index: 1,
@@ -1828,6 +2368,7 @@ mod tests {
                             parity: false,
                         },
                     ],
+                    polygon_indices: vec![2, 1],
                     proto_neighbors: vec![
                         ProtoNeighbor {
                             proto_vertex_star_index: 0,
@@ -1883,6 +2424,7 @@ mod tests {
                             parity: false,
                         },
                     ],
+                    polygon_indices: vec![0, 1],
                     proto_neighbors: vec![
                         ProtoNeighbor {
                             proto_vertex_star_index: 0,
@@ -1912,6 +2454,86 @@ mod tests {
         }
     }
 
+    // vertex_posts reads back vertices an atlas' proto vertex stars could have been built from, taking each of their
+    // tiles as its own polygon placed by its first point, which is at the vertex
+    fn vertex_posts(atlas: &Atlas) -> (Vec<Tile>, Vec<FullAtlasVertexPost>) {
+        let polygons = atlas.proto_vertex_stars.iter().flat_map(|proto_vertex_star| proto_vertex_star.tiles.iter().cloned()).collect();
+        let mut polygon_index = 0;
+        let vertices = atlas.proto_vertex_stars
+            .iter()
+            .map(|proto_vertex_star| FullAtlasVertexPost {
+                edges: proto_vertex_star.proto_neighbors
+                    .iter()
+                    .map(|proto_neighbor| {
+                        polygon_index += 1;
+                        FullAtlasEdgePost {
+                            polygon_index: polygon_index - 1,
+                            point_index: 0,
+                            neighbor_index: proto_neighbor.proto_vertex_star_index,
+                            neighbor_edge_index: proto_neighbor.neighbor_index,
+                            parity: proto_neighbor.transform.parity,
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+        (polygons, vertices)
+    }
+
+    #[test]
+    fn test_atlas_from_vertex_posts_of_test_atlases() {
+        for (name, test_atlas) in [
+            ("3.3.3.3.3.3", get_test_atlas_3_3_3_3_3_3()),
+            ("4.4.4.4", get_test_atlas_4_4_4_4()),
+            ("6.6.6", get_test_atlas_6_6_6()),
+            ("3.12.12", get_test_atlas_3_12_12()),
+            ("4.6.12", get_test_atlas_4_6_12()),
+            ("4.6apio6.6aapio2.6apio6", get_test_atlas_4_6apio6_6aapio2_6apio6()),
+            ("6.4apio6.6.4apio6", get_test_atlas_6_4apio6_6_4apio6()),
+        ].iter() {
+            let (polygons, vertices) = vertex_posts(test_atlas);
+            let atlas = Atlas::from_vertex_posts(&polygons, &vertices).expect(name);
+
+            // the atlas only differs from the test atlas in the rotations of its proto neighbors, each of which now turns
+            // the neighbor's edge back to the vertex star onto the edge to the neighbor
+            assert_eq!(test_atlas.proto_vertex_stars.len(), atlas.proto_vertex_stars.len(), "{}", name);
+            for (test_proto_vertex_star, proto_vertex_star) in test_atlas.proto_vertex_stars.iter().zip(atlas.proto_vertex_stars.iter()) {
+                assert_eq!(test_proto_vertex_star.tiles, proto_vertex_star.tiles, "{}", name);
+                for (test_proto_neighbor, proto_neighbor) in test_proto_vertex_star.proto_neighbors.iter().zip(proto_vertex_star.proto_neighbors.iter()) {
+                    assert_eq!(test_proto_neighbor.proto_vertex_star_index, proto_neighbor.proto_vertex_star_index, "{}", name);
+                    assert_eq!(test_proto_neighbor.neighbor_index, proto_neighbor.neighbor_index, "{}", name);
+                    assert_eq!(test_proto_neighbor.transform.parity, proto_neighbor.transform.parity, "{}", name);
+                    let translate = Point::new(proto_neighbor.transform.translate);
+                    assert_eq!(Point::new(test_proto_neighbor.transform.translate), translate, "{}", name);
+
+                    let neighbor_edge_point = Point::new(
+                        atlas.proto_vertex_stars[proto_neighbor.proto_vertex_star_index].proto_neighbors[proto_neighbor.neighbor_index].transform.translate
+                    );
+                    assert_eq!(-translate, neighbor_edge_point.transform(&Euclid::Rotate(proto_neighbor.transform.rotate)), "{}", name);
+                }
+            }
+
+            // so patches of either build the same vertex stars
+            let fill = |atlas: &Atlas| {
+                let mut patch = Patch::<()>::new(atlas.clone(), get_tile_tree_config(), get_vertex_star_tree_config()).expect(name);
+                patch.fill_bounds(&Bounds { center: ORIGIN, radius: 6. }, ()).expect(name);
+                patch
+            };
+            let (test_patch, patch) = (fill(test_atlas), fill(&atlas));
+            assert!(test_patch.vertex_stars.items.len() > 10, "{}", name);
+            assert_eq!(test_patch.vertex_stars.items.len(), patch.vertex_stars.items.len(), "{}", name);
+            for test_vertex_star_rc in test_patch.vertex_stars.items.values() {
+                let test_vertex_star = test_vertex_star_rc.value();
+                let vertex_star_rc = patch.vertex_stars.get(&test_vertex_star.point).expect(name);
+                let vertex_star = vertex_star_rc.value();
+                assert_eq!(test_vertex_star.proto_vertex_star_index, vertex_star.proto_vertex_star_index, "{} at {}", name, vertex_star.point);
+                assert_eq!(test_vertex_star.parity, vertex_star.parity, "{} at {}", name, vertex_star.point);
+                assert!(test_vertex_star.rotation.approx_eq(vertex_star.rotation, DEFAULT_F64_MARGIN), "{} at {}", name, vertex_star.point);
+                assert_eq!(test_vertex_star.link_vec, vertex_star.link_vec, "{} at {}", name, vertex_star.point);
+            }
+        }
+    }
+
     #[test]
     fn test_atlas_6_4apio6_6_4apio6() {
         let _patch = match Patch::<()>::new(
@@ -2303,6 +2925,27 @@ mod tests {
         }
       }
 
+    #[test]
+    fn test_vertex_star_get_clockwise_adjacent_link_index() {
+        // the clockwise adjacent link point of each link point is the one least far round from it clockwise, which for a
+        // reflected VertexStar is the next in its link rather than the previous one
+        for atlas in [get_test_atlas_4_4_4_4(), get_test_atlas_3_3_3_3_3_3()].iter() {
+            for parity in [false, true].iter() {
+                let vertex_star = VertexStar::new(atlas, Point(1., 2.), 0, *parity, 0.3);
+                let clockwise_arg = |from: &Point, to: &Point| rad((from - &vertex_star.point).arg() - (to - &vertex_star.point).arg());
+                for link_point in vertex_star.link_vec.iter() {
+                    let adjacent = &vertex_star.link_vec[vertex_star.get_clockwise_adjacent_link_index(link_point).unwrap()];
+                    let least_arg = vertex_star.link_vec
+                        .iter()
+                        .filter(|other| *other != link_point)
+                        .map(|other| clockwise_arg(link_point, other))
+                        .fold(TAU, f64::min);
+                    assert!(clockwise_arg(link_point, adjacent).approx_eq(least_arg, DEFAULT_F64_MARGIN), "{} {}", vertex_star.size(), parity);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_vertex_star_mutual_parity() {
         let atlas = get_test_atlas_4_4_4_4();
@@ -2381,4 +3024,556 @@ mod tests {
 
         patch.insert_tile_by_point(Point(4.600000000000001, 7.396666463216146), None).unwrap();
     }
+
+    #[test]
+    fn test_patch_remove_tile_by_point() {
+        let mut patch = Patch::<()>::new(
+            get_test_atlas_4_4_4_4(),
+            get_tile_tree_config(),
+            get_vertex_star_tree_config(),
+        ).expect("");
+
+        let point = Point(0.5, 0.5);
+        let neighbor_point = Point(1.5, 0.5);
+
+        patch.insert_tile_by_point(point, Some(())).unwrap();
+        patch.insert_tile_by_point(neighbor_point, Some(())).unwrap();
+        patch.drain_tile_diffs();
+
+        let centroid = patch.get_included_patch_tile_by_point(&point).expect("").value().tile.centroid.clone();
+        let neighbor_centroid = patch.get_included_patch_tile_by_point(&neighbor_point).expect("").value().tile.centroid.clone();
+        assert_eq!(Some(vec![neighbor_centroid]), patch.get_tile_neighbor_centroids(&point));
+
+        patch.remove_tile_by_point(neighbor_point).unwrap();
+
+        let tile_diffs = patch.drain_tile_diffs();
+        assert_eq!(1, tile_diffs.len());
+        match tile_diffs.get(0) {
+            Some((diff_centroid, TileDiff::Removed(_))) => assert_eq!(neighbor_centroid, *diff_centroid),
+            _ => panic!("expected a single Removed tile diff"),
+        }
+        assert!(patch.get_included_patch_tile_by_point(&neighbor_point).is_none());
+        assert_eq!(Some(vec![]), patch.get_tile_neighbor_centroids(&point));
+
+        // vertex stars only bordering the removed tile are pruned
+        assert!(!patch.vertex_stars.has(&Point(2., 0.)));
+        assert!(!patch.vertex_stars.has(&Point(2., 1.)));
+        assert!(patch.vertex_stars.has(&Point(1., 0.)));
+        assert!(patch.vertex_stars.has(&Point(1., 1.)));
+
+        patch.insert_tile_by_point(neighbor_point, Some(())).unwrap();
+
+        let tile_diffs = patch.drain_tile_diffs();
+        assert_eq!(1, tile_diffs.len());
+        match tile_diffs.get(0) {
+            Some((diff_centroid, TileDiff::Added(_))) => assert_eq!(neighbor_centroid, *diff_centroid),
+            _ => panic!("expected a single Added tile diff"),
+        }
+        assert!(patch.vertex_stars.has(&Point(2., 0.)));
+        assert!(patch.vertex_stars.has(&Point(2., 1.)));
+        assert_eq!(Some(vec![neighbor_centroid]), patch.get_tile_neighbor_centroids(&point));
+
+        patch.remove_tile_by_point(point).unwrap();
+        patch.remove_tile_by_point(neighbor_point).unwrap();
+        assert!(patch.vertex_stars.has(&ORIGIN));
+        assert!(patch.get_included_patch_tile_by_point(&point).is_none());
+        assert!(patch.get_included_patch_tile_by_point(&neighbor_point).is_none());
+        assert!(patch.patch_tiles.has(&centroid));
+    }
+
+    #[test]
+    fn test_patch_get_included_adjacency() {
+        let mut patch = Patch::<()>::new(
+            get_test_atlas_4_4_4_4(),
+            get_tile_tree_config(),
+            get_vertex_star_tree_config(),
+        ).expect("");
+
+        patch.insert_tile_by_point(Point(0.5, 0.5), Some(())).unwrap();
+        patch.insert_tile_by_point(Point(1.5, 0.5), Some(())).unwrap();
+        patch.insert_tile_by_point(Point(0.5, 1.5), None).unwrap();
+
+        // the tile which isn't included has no part in the adjacency
+        let adjacency = patch.get_included_adjacency();
+        assert_eq!(2, adjacency.len());
+        assert!(adjacency.contains(&(Point(0.5, 0.5), Point(1.5, 0.5))));
+        assert!(adjacency.contains(&(Point(1.5, 0.5), Point(0.5, 0.5))));
+    }
+
+    #[test]
+    fn test_patch_get_included_adjacency_matches_cell() {
+        // the unit cell's adjacency is that of a patch filled over the same bounds, without filling one
+        let bounds = Bounds { center: Point(0.3, -0.2), radius: 3. };
+        for notation in ["4^4", "3.12^2", "3.4.6.4", "3^4.6", "[3^6; 3^2.4.3.4]"].iter() {
+            let configuration = VertexConfiguration::parse(notation).unwrap();
+            let atlas = Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().unwrap()).unwrap();
+            let mut patch = Patch::<()>::new(atlas, get_tile_tree_config(), get_vertex_star_tree_config()).expect(notation);
+            let cell = patch.get_cell().expect(notation);
+            patch.fill_bounds_periodic(&bounds.mul(3.), ()).expect(notation);
+
+            let expected = patch.get_included_adjacency()
+                .into_iter()
+                .filter(|(centroid, _)| centroid.intersects(&bounds))
+                .collect::<Vec<(Point, Point)>>();
+            let adjacency = cell.adjacency(&bounds);
+            assert!(!adjacency.is_empty(), "{}", notation);
+            assert_eq!(expected.len(), adjacency.len(), "{}", notation);
+            assert!(expected.iter().all(|pair| adjacency.contains(pair)), "{}", notation);
+        }
+    }
+
+    #[test]
+    fn test_patch_errors() {
+        let mut patch = Patch::<()>::new(
+            get_test_atlas_4_4_4_4(),
+            get_tile_tree_config(),
+            get_vertex_star_tree_config(),
+        ).expect("");
+
+        // the VertexStars near a distant point are stamped from the unit cell before walking to it
+        let far_point = Point(500.5, 0.5);
+        assert_eq!(Ok(()), patch.insert_tile_by_point(far_point, Some(())));
+        assert!(patch.get_included_patch_tile_by_point(&far_point).is_some());
+
+        // this atlas' proto neighbors don't pair up into a Delaney symbol, so it has no unit cell and is walked across
+        let mut patch = Patch::<()>::new(get_test_atlas_6_6_6(), get_tile_tree_config(), get_vertex_star_tree_config()).expect("");
+        assert_eq!(Err(PatchError::TooFar { point: far_point }), patch.insert_tile_by_point(far_point, Some(())));
+
+        let centroid = Point(10.5, 10.5);
+        assert_eq!(Err(PatchError::MissingTile { centroid }), patch.remove_tile_by_centroid(&centroid));
+    }
+
+    #[test]
+    fn test_patch_undo_redo() {
+        let mut patch = Patch::<usize>::new(
+            get_test_atlas_4_4_4_4(),
+            get_tile_tree_config(),
+            get_vertex_star_tree_config(),
+        ).expect("");
+
+        let point = Point(0.5, 0.5);
+        let neighbor_point = Point(1.5, 0.5);
+
+        let get_state = |patch: &Patch<usize>, point: &Point| patch.get_included_patch_tile_by_point(point).and_then(|rc_item| rc_item.value().state.clone());
+
+        patch.insert_tile_by_point(point, Some(1)).unwrap();
+        patch.insert_tile_by_point(neighbor_point, Some(2)).unwrap();
+        patch.remove_tile_by_point(point).unwrap();
+        patch.drain_tile_diffs();
+
+        assert_eq!(None, get_state(&patch, &point));
+        assert_eq!(Some(2), get_state(&patch, &neighbor_point));
+
+        // undo the removal, restoring the removed tile's state
+        patch.undo().unwrap();
+        assert_eq!(Some(1), get_state(&patch, &point));
+        match patch.drain_tile_diffs().get(0) {
+            Some((_, TileDiff::Added(_))) => {},
+            _ => panic!("expected undoing a removal to add a tile"),
+        }
+
+        // undo the second insert
+        patch.undo().unwrap();
+        assert_eq!(Some(1), get_state(&patch, &point));
+        assert_eq!(None, get_state(&patch, &neighbor_point));
+
+        patch.redo().unwrap();
+        assert_eq!(Some(2), get_state(&patch, &neighbor_point));
+
+        patch.redo().unwrap();
+        assert_eq!(None, get_state(&patch, &point));
+        assert!(!patch.history.can_redo());
+
+        // a new edit clears whatever could be redone
+        patch.undo().unwrap();
+        assert!(patch.history.can_redo());
+        patch.insert_tile_by_point(Point(-0.5, 0.5), Some(3)).unwrap();
+        assert!(!patch.history.can_redo());
+
+        // history is bounded by its capacity
+        patch.history.capacity = 1;
+        patch.insert_tile_by_point(Point(-1.5, 0.5), Some(4)).unwrap();
+        patch.undo().unwrap();
+        assert!(!patch.history.can_undo());
+        assert_eq!(Some(3), get_state(&patch, &Point(-0.5, 0.5)));
+        assert_eq!(None, get_state(&patch, &Point(-1.5, 0.5)));
+    }
+
+    #[test]
+    fn test_patch_set_tile_state() {
+        let mut patch = Patch::<usize>::new(
+            get_test_atlas_4_4_4_4(),
+            get_tile_tree_config(),
+            get_vertex_star_tree_config(),
+        ).expect("");
+
+        let point = Point(0.5, 0.5);
+        let get_state = |patch: &Patch<usize>, point: &Point| patch.get_included_patch_tile_by_point(point).and_then(|rc_item| rc_item.value().state);
+
+        patch.insert_tile_by_point(point, Some(1)).unwrap();
+        patch.drain_tile_diffs();
+
+        patch.set_tile_state(&Point(0.2, 0.7), 5).unwrap();
+        assert_eq!(Some(5), get_state(&patch, &point));
+        match patch.drain_tile_diffs().as_slice() {
+            [(centroid, TileDiff::Changed(_))] => assert_eq!(point, *centroid),
+            _ => panic!("expected setting a tile's state to change the tile"),
+        }
+
+        // tiles which aren't included have no state to set
+        patch.set_tile_state(&Point(1.5, 0.5), 6).unwrap();
+        assert_eq!(None, get_state(&patch, &Point(1.5, 0.5)));
+        assert!(patch.drain_tile_diffs().is_empty());
+
+        patch.undo().unwrap();
+        assert_eq!(Some(1), get_state(&patch, &point));
+        patch.redo().unwrap();
+        assert_eq!(Some(5), get_state(&patch, &point));
+    }
+
+    #[test]
+    fn test_patch_fill_bounds() {
+        let mut patch = Patch::<usize>::new(
+            get_test_atlas_4_4_4_4(),
+            get_tile_tree_config(),
+            get_vertex_star_tree_config(),
+        ).expect("");
+
+        patch.insert_tile_by_point(Point(0.5, 0.5), Some(0)).unwrap();
+        patch.drain_tile_diffs();
+
+        let tile_diffs = patch.fill_bounds(&Bounds { center: ORIGIN, radius: 2. }, 1).unwrap();
+        assert_eq!(15, tile_diffs.len());
+        assert!(tile_diffs.iter().all(|(_, tile_diff)| matches!(tile_diff, TileDiff::Added(_))));
+        assert!(patch.drain_tile_diffs().is_empty());
+
+        let get_state = |patch: &Patch<usize>, point: &Point| patch.get_included_patch_tile_by_point(point).and_then(|rc_item| rc_item.value().state);
+        assert_eq!(Some(0), get_state(&patch, &Point(0.5, 0.5)));
+        assert_eq!(Some(1), get_state(&patch, &Point(-1.5, -1.5)));
+        assert_eq!(Some(1), get_state(&patch, &Point(1.5, 1.5)));
+        assert_eq!(None, get_state(&patch, &Point(2.5, 0.5)));
+
+        // bounds away from the existing vertex stars are walked to
+        let tile_diffs = patch.fill_bounds(&Bounds { center: Point(10.5, 0.), radius: 1. }, 2).unwrap();
+        assert_eq!(6, tile_diffs.len());
+        assert_eq!(Some(2), get_state(&patch, &Point(9.5, -0.5)));
+        assert_eq!(Some(2), get_state(&patch, &Point(11.5, 0.5)));
+        assert_eq!(None, get_state(&patch, &Point(8.5, 0.5)));
+
+        // each fill is undone as a single edit
+        patch.undo().unwrap();
+        assert_eq!(None, get_state(&patch, &Point(9.5, -0.5)));
+        assert_eq!(Some(1), get_state(&patch, &Point(1.5, 1.5)));
+        patch.undo().unwrap();
+        assert_eq!(None, get_state(&patch, &Point(1.5, 1.5)));
+        assert_eq!(Some(0), get_state(&patch, &Point(0.5, 0.5)));
+    }
+
+    #[test]
+    fn test_patch_get_polygon_index() {
+        let configuration = VertexConfiguration::parse("3.4.6.4").unwrap();
+        let atlases = vec![
+            get_test_atlas_4_6_12(),
+            get_test_atlas_4_6apio6_6aapio2_6apio6(),
+            Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().unwrap()).unwrap(),
+        ];
+        for atlas in atlases.into_iter() {
+            let mut polygons: HashMap<usize, Tile> = HashMap::new();
+            for proto_vertex_star in atlas.proto_vertex_stars.iter() {
+                for (tile, polygon_index) in proto_vertex_star.tiles.iter().zip(proto_vertex_star.polygon_indices.iter()) {
+                    polygons.insert(*polygon_index, tile.clone());
+                }
+            }
+            let mut patch = Patch::<()>::new(atlas, get_tile_tree_config(), get_vertex_star_tree_config()).expect("");
+            patch.fill_bounds(&Bounds { center: ORIGIN, radius: 4. }, ()).unwrap();
+
+            // each included Tile is congruent to the polygon at its index, whichever VertexStar it's found from
+            let patch_tiles = patch.patch_tiles.items.values().filter(|patch_tile_rc| patch_tile_rc.value().state.is_some()).collect::<Vec<_>>();
+            assert!(!patch_tiles.is_empty());
+            for patch_tile_rc in patch_tiles.into_iter() {
+                let tile = &patch_tile_rc.value().tile;
+                let polygon_index = patch.get_polygon_index(tile).unwrap();
+                let polygon = &polygons[&polygon_index];
+                assert_eq!(polygon.size(), tile.size());
+                assert!((0..tile.size()).all(|i| (0..polygon.size()).any(|j| polygon.angle(j).approx_eq(tile.angle(i), DEFAULT_F64_MARGIN))));
+            }
+        }
+        assert_eq!(None, Patch::<()>::new(get_test_atlas_4_4_4_4(), get_tile_tree_config(), get_vertex_star_tree_config()).expect("").get_polygon_index(&Tile::new(vec![Point(10., 10.), Point(11., 10.), Point(11., 11.)])));
+    }
+
+    #[test]
+    fn test_patch_fill_polygon() {
+        let polygon = Tile::new(vec![Point(-3.1, -2.3), Point(4.2, -1.7), Point(0.3, 3.9)]);
+
+        for get_test_atlas in [get_test_atlas_4_4_4_4, get_test_atlas_3_12_12, get_test_atlas_4_6_12].iter() {
+            let mut patch = Patch::<()>::new(get_test_atlas(), get_tile_tree_config(), get_vertex_star_tree_config()).expect("");
+
+            let tile_diffs = patch.fill_polygon(&polygon, ()).unwrap();
+            assert_eq!(tile_diffs.len(), patch.patch_tiles.items.values().filter(|rc_item| rc_item.value().state.is_some()).count());
+
+            for patch_tile_rc in patch.patch_tiles.items.values() {
+                let patch_tile = patch_tile_rc.value();
+                assert_eq!(patch_tile.tile.overlaps(&polygon), patch_tile.state.is_some());
+            }
+
+            for i in 0..40 {
+                for j in 0..40 {
+                    let point = Point(-3.1 + 0.1837 * i as f64, -2.3 + 0.1571 * j as f64);
+                    if polygon.contains(&point) {
+                        assert!(patch.get_included_patch_tile_by_point(&point).is_some(), "no tile included at {}", point);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_patch_neighbors_of_reflected_vertex_stars() {
+        // walking across these tilings inserts Tiles from reflected VertexStars, whose edges run clockwise around them,
+        // and each Tile's neighbors must still be the included Tiles it shares an edge with
+        let polygon = Tile::new(vec![Point(-3.1, -2.3), Point(4.2, -1.7), Point(0.3, 3.9)]);
+        for notation in ["3.4.6.4", "3^2.4.3.4", "3^4.6"].iter() {
+            let configuration = VertexConfiguration::parse(notation).unwrap();
+            let atlas = Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().unwrap()).unwrap();
+            let mut patch = Patch::<()>::new(atlas, get_tile_tree_config(), get_vertex_star_tree_config()).expect("");
+            patch.fill_polygon(&polygon, ()).expect(notation);
+
+            let tiles = patch.patch_tiles.items
+                .values()
+                .filter(|rc_item| rc_item.value().state.is_some())
+                .map(|rc_item| rc_item.value().tile.clone())
+                .collect::<Vec<Tile>>();
+            let shares_edge = |a: &Tile, b: &Tile| a.points.iter().filter(|point| b.points.iter().any(|other| other.approx_eq(point, DEFAULT_F64_MARGIN))).count() == 2;
+            for tile in tiles.iter() {
+                let neighbors = patch.get_tile_neighbor_centroids(&tile.centroid).expect(notation);
+                let expected = tiles.iter().filter(|other| shares_edge(tile, other)).map(|other| other.centroid).collect::<Vec<Point>>();
+                assert_eq!(expected.len(), neighbors.len(), "{} at {}", notation, tile.centroid);
+                assert!(expected.iter().all(|centroid| neighbors.contains(centroid)), "{} at {}", notation, tile.centroid);
+            }
+        }
+    }
+
+    #[test]
+    fn test_patch_neighbors_of_reflected_vertex_stars_4_6_12() {
+        let mut patch = Patch::<()>::new(
+            get_test_atlas_4_6_12(),
+            get_tile_tree_config(),
+            get_vertex_star_tree_config(),
+        ).expect("");
+
+        // a square with hexagons either side of it and dodecagons above and below it, each of the square's other corners
+        // being a reflection of the VertexStar at the origin
+        let (r, h) = (3_f64.sqrt() / 2., 1. + 3_f64.sqrt() / 2.);
+        let square = Point(0.5, -0.5);
+        let (left_hexagon, right_hexagon) = (Point(-r, -0.5), Point(1. + r, -0.5));
+        let (upper_dodecagon, lower_dodecagon) = (Point(0.5, h), Point(0.5, -1. - h));
+        for point in [square, left_hexagon, right_hexagon, upper_dodecagon, lower_dodecagon].iter() {
+            patch.insert_tile_by_point(*point, Some(())).unwrap();
+        }
+        let origin_parity = patch.vertex_stars.get(&ORIGIN).expect("").value().parity;
+        for point in [Point(1., 0.), Point(0., -1.)].iter() {
+            assert_ne!(origin_parity, patch.vertex_stars.get(point).expect("").value().parity, "{}", point);
+        }
+
+        let assert_neighbors = |patch: &Patch<()>, expected_neighbors: &[(Point, Vec<Point>)]| {
+            for (centroid, expected) in expected_neighbors.iter() {
+                let neighbors = patch.get_tile_neighbor_centroids(centroid).expect("");
+                assert_eq!(expected.len(), neighbors.len(), "{}", centroid);
+                assert!(expected.iter().all(|neighbor| neighbors.contains(neighbor)), "{}", centroid);
+            }
+        };
+        assert_neighbors(&patch, &[
+            (square, vec![left_hexagon, right_hexagon, upper_dodecagon, lower_dodecagon]),
+            (left_hexagon, vec![square, upper_dodecagon, lower_dodecagon]),
+            (right_hexagon, vec![square, upper_dodecagon, lower_dodecagon]),
+            (upper_dodecagon, vec![square, left_hexagon, right_hexagon]),
+            (lower_dodecagon, vec![square, left_hexagon, right_hexagon]),
+        ]);
+
+        // removing the right hexagon, all of whose corners off the square are reflected VertexStars, drops it from the
+        // others' neighbors
+        patch.remove_tile_by_point(right_hexagon).unwrap();
+        assert_neighbors(&patch, &[
+            (square, vec![left_hexagon, upper_dodecagon, lower_dodecagon]),
+            (upper_dodecagon, vec![square, left_hexagon]),
+            (lower_dodecagon, vec![square, left_hexagon]),
+        ]);
+    }
+
+    #[test]
+    fn test_patch_fill_polygon_periodic() {
+        let polygon = Tile::new(vec![Point(-3.1, -2.3), Point(4.2, -1.7), Point(0.3, 3.9)]);
+        let get_included_centroids = |patch: &Patch<()>| {
+            patch.patch_tiles.items
+                .values()
+                .filter(|rc_item| rc_item.value().state.is_some())
+                .map(|rc_item| rc_item.value().tile.centroid)
+                .collect::<Vec<Point>>()
+        };
+        let is_same = |a: &Vec<Point>, b: &Vec<Point>| a.len() == b.len() && a.iter().all(|point| b.contains(point));
+
+        for notation in ["4^4", "3.12^2", "3.4.6.4", "3^4.6", "3^2.4.3.4", "[3^6; 3^2.4.3.4]"].iter() {
+            let get_atlas = || {
+                let configuration = VertexConfiguration::parse(notation).unwrap();
+                Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().unwrap()).unwrap()
+            };
+            let mut walked_patch = Patch::<()>::new(get_atlas(), get_tile_tree_config(), get_vertex_star_tree_config()).expect("");
+            let mut stamped_patch = Patch::<()>::new(get_atlas(), get_tile_tree_config(), get_vertex_star_tree_config()).expect("");
+
+            walked_patch.fill_polygon(&polygon, ()).expect(notation);
+            let tile_diffs = stamped_patch.fill_polygon_periodic(&polygon, ()).expect(notation);
+            assert!(tile_diffs.iter().all(|(_, tile_diff)| matches!(tile_diff, TileDiff::Added(_))));
+
+            // the same Tiles are included with the same neighbors
+            let centroids = get_included_centroids(&walked_patch);
+            assert!(is_same(&centroids, &get_included_centroids(&stamped_patch)), "{}", notation);
+            assert_eq!(centroids.len(), tile_diffs.len());
+            for centroid in centroids.iter() {
+                let walked_neighbors = walked_patch.get_tile_neighbor_centroids(centroid).expect(notation);
+                let stamped_neighbors = stamped_patch.get_tile_neighbor_centroids(centroid).expect(notation);
+                assert!(is_same(&walked_neighbors, &stamped_neighbors), "{} at {}", notation, centroid);
+            }
+
+            // tiles can be added next to stamped ones by walking as usual, and the fill is undone as a single edit
+            stamped_patch.insert_tile_by_point(Point(6.1, 0.2), Some(())).expect(notation);
+            stamped_patch.undo().unwrap();
+            stamped_patch.undo().unwrap();
+            assert!(get_included_centroids(&stamped_patch).is_empty());
+        }
+
+        // far away polygons are stamped without walking to them
+        let mut patch = Patch::<()>::new(get_test_atlas_4_4_4_4(), get_tile_tree_config(), get_vertex_star_tree_config()).expect("");
+        let tile_diffs = patch.fill_bounds_periodic(&Bounds { center: Point(300.5, -200.5), radius: 10. }, ()).unwrap();
+        assert_eq!(441, tile_diffs.len());
+        assert!(patch.get_included_patch_tile_by_point(&Point(290.7, -210.3)).is_some());
+        assert!(patch.get_included_patch_tile_by_point(&Point(0.5, 0.5)).is_none());
+    }
+
+    #[test]
+    fn test_patch_grow_coronas() {
+        for (get_test_atlas, seed_point, corona_sizes) in [
+            (get_test_atlas_4_4_4_4 as fn() -> Atlas, Point(0.5, 0.5), vec![1, 8, 16, 24]),
+            (get_test_atlas_3_3_3_3_3_3, Point(0.5, 0.2), vec![1, 12, 24, 36]),
+            (get_test_atlas_6_6_6, Point(0.5, 0.5), vec![1, 6, 12, 18]),
+        ].iter() {
+            let mut patch = Patch::<()>::new(get_test_atlas(), get_tile_tree_config(), get_vertex_star_tree_config()).expect("");
+            patch.insert_tile_by_point(*seed_point, Some(())).unwrap();
+            patch.drain_tile_diffs();
+
+            let seed_centroid = patch.get_included_patch_tile_by_point(seed_point).expect("").value().tile.centroid;
+            let coronas = patch.grow_coronas(&seed_centroid, 3, ()).unwrap();
+            assert_eq!(*corona_sizes, coronas.iter().map(|corona| corona.len()).collect::<Vec<usize>>());
+            assert_eq!(vec![seed_centroid], coronas[0]);
+
+            // the seed was already included, every other tile is added
+            assert_eq!(corona_sizes.iter().sum::<usize>() - 1, patch.drain_tile_diffs().len());
+            for centroid in coronas.iter().flatten() {
+                assert!(patch.get_included_patch_tile_by_point(centroid).is_some());
+            }
+
+            // each tile in a corona neighbors a tile in the previous one
+            for (corona, previous_corona) in coronas.iter().skip(2).zip(coronas.iter().skip(1)) {
+                for centroid in corona.iter() {
+                    let neighbor_centroids = patch.get_tile_neighbor_centroids(centroid).expect("");
+                    assert!(neighbor_centroids.iter().all(|neighbor_centroid| !coronas[0].contains(neighbor_centroid)));
+                    assert!(corona.iter().chain(previous_corona.iter()).any(|c| neighbor_centroids.contains(c)));
+                }
+            }
+
+            patch.undo().unwrap();
+            assert!(patch.get_included_patch_tile_by_point(&coronas[1][0]).is_none());
+            assert!(patch.get_included_patch_tile_by_point(&seed_centroid).is_some());
+        }
+    }
+
+    #[test]
+    fn test_patch_unit_cell() {
+        for (notation, num_tiles) in [("4^4", 1), ("3^6", 2), ("6^3", 1), ("3.12^2", 3), ("4.6.12", 6), ("3.4.6.4", 6)].iter() {
+            let configuration = VertexConfiguration::parse(notation).unwrap();
+            let atlas = Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().unwrap()).unwrap();
+            let mut patch = Patch::<()>::new(atlas, get_tile_tree_config(), get_vertex_star_tree_config()).expect("");
+            let cell = patch.unit_cell().expect(notation);
+            assert_eq!(*num_tiles, cell.tiles.len(), "{}", notation);
+
+            // the cell's tiles, and their translates, are where the Patch places them
+            for tile in cell.tiles.iter().chain(cell.tiles_at(1, -2).iter()) {
+                patch.insert_tile_by_point(tile.centroid, Some(())).unwrap();
+                let patch_tile_rc = patch.get_included_patch_tile_by_point(&tile.centroid).expect(notation);
+                assert_eq!(tile.size(), patch_tile_rc.value().tile.size());
+                assert!(tile.points.iter().all(|point| patch_tile_rc.value().tile.points.iter().any(|other| other.approx_eq(point, DEFAULT_F64_MARGIN))));
+            }
+
+            let domain = patch.fundamental_domain().expect(notation);
+            assert!(!domain.chambers.is_empty());
+            assert!(domain.chambers.iter().all(|chamber| chamber.size() == 3));
+        }
+    }
+
+    #[test]
+    fn test_patch_dual_tiles() {
+        let get_atlas = |notation: &str| {
+            let configuration = VertexConfiguration::parse(notation).unwrap();
+            Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().unwrap()).unwrap()
+        };
+
+        // only the 3 x 3 vertices inside a 4 x 4 block of squares have all their squares included
+        let mut patch = Patch::<()>::new(get_atlas("4^4"), get_tile_tree_config(), get_vertex_star_tree_config()).expect("");
+        patch.fill_polygon_periodic(&Tile::new(vec![Point(-1.5, -1.5), Point(1.5, -1.5), Point(1.5, 1.5), Point(-1.5, 1.5)]), ()).unwrap();
+        let dual_tiles = patch.dual_tiles().unwrap();
+        assert_eq!(9, dual_tiles.len());
+        assert!(dual_tiles.iter().all(|tile| tile.size() == 4 && tile.points.iter().all(|point| point.0.fract().abs().approx_eq(0.5, DEFAULT_F64_MARGIN) && point.1.fract().abs().approx_eq(0.5, DEFAULT_F64_MARGIN))));
+
+        let polygon = Tile::new(vec![Point(-3.1, -2.3), Point(4.2, -1.7), Point(0.3, 3.9)]);
+        for notation in ["3^6", "6^3", "3.4.6.4", "4.6.12", "3^4.6"].iter() {
+            let atlas = get_atlas(notation);
+            let mut sizes = atlas::dual(&atlas).expect(notation).polygons.iter().map(Tile::size).collect::<Vec<usize>>();
+            sizes.dedup();
+
+            let mut patch = Patch::<()>::new(atlas, get_tile_tree_config(), get_vertex_star_tree_config()).expect("");
+            patch.fill_polygon_periodic(&polygon, ()).expect(notation);
+            let dual_tiles = patch.dual_tiles().expect(notation);
+            assert!(!dual_tiles.is_empty(), "{}", notation);
+
+            // each dual Tile is a translate of one of the atlas' dual's polygons, with its points at included Tiles' centroids
+            for tile in dual_tiles.iter() {
+                assert!(sizes.contains(&tile.size()), "{}", notation);
+                assert!(tile.points.iter().all(|point| patch.get_included_patch_tile_by_point(point).is_some()), "{}", notation);
+            }
+        }
+
+        let star = "[4.6apio6.6aapio2.6apio6; 4.6aapio6; 6apio2.6aapio6]";
+        let mut patch = Patch::<()>::new(get_atlas(star), get_tile_tree_config(), get_vertex_star_tree_config()).expect("");
+        patch.fill_polygon_periodic(&polygon, ()).unwrap();
+        assert!(matches!(patch.dual_tiles(), Err(PatchError::NonPolygonalDual { .. })));
+    }
+
+    #[test]
+    fn test_patch_snapshot() {
+        let points = vec![Point(0.5, 0.5), Point(1.2, -0.3), Point(-2.5, 1.5), Point(4.4, 3.8), Point(-3.1, -5.2)];
+
+        for get_test_atlas in [get_test_atlas_4_4_4_4, get_test_atlas_3_12_12, get_test_atlas_4_6_12].iter() {
+            let mut patch = Patch::<usize>::new(get_test_atlas(), get_tile_tree_config(), get_vertex_star_tree_config()).expect("");
+            for (i, point) in points.iter().enumerate() {
+                patch.insert_tile_by_point(point.clone(), Some(i)).unwrap();
+            }
+            patch.remove_tile_by_point(points[1]).unwrap();
+
+            let snapshot = patch.to_snapshot(7).unwrap();
+            assert_eq!(7, snapshot.atlas_id);
+            assert_eq!(4, snapshot.tiles.len());
+
+            assert_eq!(snapshot, PatchSnapshot::from_json(&snapshot.to_json().unwrap()).unwrap());
+            assert_eq!(snapshot, PatchSnapshot::from_bytes(&snapshot.to_bytes().unwrap()).unwrap());
+
+            let mut restored = Patch::from_snapshot(snapshot.clone(), get_test_atlas(), get_tile_tree_config(), get_vertex_star_tree_config()).unwrap();
+            assert_eq!(4, restored.drain_tile_diffs().len());
+            assert!(!restored.history.can_undo());
+            assert_eq!(snapshot, restored.to_snapshot(7).unwrap());
+
+            for (i, point) in points.iter().enumerate() {
+                let state = restored.get_included_patch_tile_by_point(point).and_then(|rc_item| rc_item.value().state.clone());
+                assert_eq!(if i == 1 { None } else { Some(i) }, state);
+            }
+        }
+    }
 }
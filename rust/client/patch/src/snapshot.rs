@@ -0,0 +1,135 @@
+use crate::{Patch, PatchError, VertexStar};
+use atlas::Atlas;
+use geometry::Point;
+use pmr_quad_tree::Config as TreeConfig;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::cmp::Ordering;
+
+pub const PATCH_SNAPSHOT_VERSION: u32 = 1;
+
+// PatchSnapshot is the serializable form of a Patch's included Tiles. Each Tile is
+// anchored to one of its VertexStars, from which the rest of the Patch can be regrown.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PatchSnapshot<State> {
+    pub version: u32,
+    #[serde(rename = "atlasId")]
+    pub atlas_id: i32,
+    pub tiles: Vec<PatchTileSnapshot<State>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PatchTileSnapshot<State> {
+    #[serde(rename = "protoVertexStarIndex")]
+    pub proto_vertex_star_index: usize,
+    pub parity: bool,
+    pub rotation: f64,
+    pub anchor: (f64, f64), // point of the VertexStar the Tile is anchored to
+    #[serde(rename = "linkIndex")]
+    pub link_index: usize, // the Tile sits clockwise of this index in the anchor VertexStar's link
+    pub state: State,
+}
+
+impl<State: Serialize + DeserializeOwned> PatchSnapshot<State> {
+    pub fn from_json(json: &str) -> Result<PatchSnapshot<State>, PatchError> {
+        serde_json::from_str(json).map_err(|e| PatchError::Serialization { message: e.to_string() })
+    }
+
+    pub fn to_json(&self) -> Result<String, PatchError> {
+        serde_json::to_string(self).map_err(|e| PatchError::Serialization { message: e.to_string() })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<PatchSnapshot<State>, PatchError> {
+        rmp_serde::from_read_ref(bytes).map_err(|e| PatchError::Serialization { message: e.to_string() })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PatchError> {
+        rmp_serde::to_vec(self).map_err(|e| PatchError::Serialization { message: e.to_string() })
+    }
+}
+
+impl<State> Patch<State> {
+    // from_snapshot rebuilds a Patch over atlas containing each of the snapshot's Tiles.
+    // The rebuilt Tiles are left in tile_diffs so that they can be drawn, but aren't undoable.
+    pub fn from_snapshot(snapshot: PatchSnapshot<State>, atlas: Atlas, tile_tree_config: TreeConfig, vertex_star_tree_config: TreeConfig) -> Result<Patch<State>, PatchError> {
+        if snapshot.version != PATCH_SNAPSHOT_VERSION {
+            return Err(PatchError::UnsupportedSnapshotVersion { version: snapshot.version })
+        }
+
+        let mut patch = Patch::new(atlas, tile_tree_config, vertex_star_tree_config)?;
+
+        for tile_snapshot in snapshot.tiles.into_iter() {
+            if tile_snapshot.proto_vertex_star_index >= patch.atlas.proto_vertex_stars.len() {
+                return Err(PatchError::MissingProtoVertexStar { index: tile_snapshot.proto_vertex_star_index })
+            }
+
+            let anchor = Point::new(tile_snapshot.anchor);
+            let vertex_star = VertexStar::new(
+                &patch.atlas,
+                anchor,
+                tile_snapshot.proto_vertex_star_index,
+                tile_snapshot.parity,
+                tile_snapshot.rotation,
+            );
+            let stop = vertex_star.link_vec
+                .get(tile_snapshot.link_index)
+                .map(|point| point.clone())
+                .ok_or(PatchError::LinkIndexOutOfRange { point: anchor, index: tile_snapshot.link_index })?;
+
+            if !patch.vertex_stars.has(&anchor) {
+                patch.vertex_stars.insert(vertex_star);
+            }
+
+            patch.insert_adjacent_tile_by_edge((anchor, stop), Some(tile_snapshot.state))?;
+        }
+
+        patch.history.clear();
+
+        Ok(patch)
+    }
+}
+
+impl<State: Clone> Patch<State> {
+    // to_snapshot captures every included Tile in this Patch, ordered by anchor so that
+    // equal Patches produce equal snapshots
+    pub fn to_snapshot(&self, atlas_id: i32) -> Result<PatchSnapshot<State>, PatchError> {
+        let mut tiles: Vec<PatchTileSnapshot<State>> = vec![];
+
+        for patch_tile_rc in self.patch_tiles.items.values() {
+            let patch_tile = patch_tile_rc.value();
+            let state = match &patch_tile.state { None => continue, Some(state) => state.clone() };
+
+            let anchor = patch_tile.tile.points.get(0).ok_or(PatchError::MissingTile { centroid: patch_tile.tile.centroid })?;
+            let vertex_star_rc = self.vertex_stars.get(anchor).ok_or(PatchError::MissingVertexStar { point: *anchor })?;
+            let vertex_star = vertex_star_rc.value();
+
+            let link_index = vertex_star.link_vec
+                .iter()
+                .position(|link_point| match vertex_star.get_tile(&self.atlas, link_point) {
+                    None => false,
+                    Some(tile) => tile.centroid == patch_tile.tile.centroid,
+                })
+                .ok_or(PatchError::TileNotAtVertexStar { centroid: patch_tile.tile.centroid, point: *anchor })?;
+
+            tiles.push(PatchTileSnapshot {
+                proto_vertex_star_index: vertex_star.proto_vertex_star_index,
+                parity: vertex_star.parity,
+                rotation: vertex_star.rotation,
+                anchor: vertex_star.point.values(),
+                link_index,
+                state,
+            });
+        }
+
+        tiles.sort_by(|a, b|
+            a.anchor.0.partial_cmp(&b.anchor.0).unwrap_or(Ordering::Equal)
+                .then(a.anchor.1.partial_cmp(&b.anchor.1).unwrap_or(Ordering::Equal))
+                .then(a.link_index.cmp(&b.link_index))
+        );
+
+        Ok(PatchSnapshot {
+            version: PATCH_SNAPSHOT_VERSION,
+            atlas_id,
+            tiles,
+        })
+    }
+}
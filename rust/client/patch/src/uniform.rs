@@ -0,0 +1,360 @@
+use crate::{VertexStar, SEED_VERTEX_STAR_POINT};
+use atlas::{canonical_key, uniformity, validate, Atlas, Corner, CornerKind, NotationError, NotationPolygon, VertexConfiguration};
+use common::DEFAULT_F64_MARGIN;
+use float_cmp::ApproxEq;
+use geometry::Point;
+use models::{FullAtlasEdgePost, FullAtlasPost, FullAtlasVertexPost, FullSubTilingPost};
+use std::{collections::{HashMap, HashSet, VecDeque}, f64::consts::PI};
+use tile::Tile;
+
+// radius about the seed vertex star within which an enumerated tiling's vertex stars must agree with one another
+const CONSISTENCY_RADIUS: f64 = 8.;
+// upper bounds on the tilings searched for per set of vertex types, and on the pairings of edges tried finding them
+const MAX_ATLASES_PER_CONFIGURATION: usize = 16;
+const MAX_SEARCH_STEPS: usize = 2_000_000;
+// upper bound on the extensions of a sequence of corners tried listing the vertex types of the polygons
+const MAX_VERTEX_TYPE_STEPS: usize = 1_000_000;
+
+// UniformTilings are the atlases found by uniform_tilings. The search for them is bounded, so truncated is whether it
+// stopped short of ruling out any more tilings, by reaching limit, MAX_ATLASES_PER_CONFIGURATION or MAX_SEARCH_STEPS.
+#[derive(Debug)]
+pub struct UniformTilings {
+    pub atlases: Vec<FullAtlasPost>,
+    pub truncated: bool,
+}
+
+// uniform_tilings enumerates up to limit edge-to-edge k-uniform tilings by polygons, for k from 1 up to max_k, where
+// polygons are regular polygons or stars with unit sides and polygon_ids are their ids in order. Each tiling is returned
+// as an atlas which has been validated and whose vertex stars have been checked to fit together around the seed
+// vertex star, titled by its vertex configuration. Tilings are listed in order of the number of vertex types they use.
+pub fn uniform_tilings(polygons: &[Tile], polygon_ids: &[i32], max_k: usize, limit: usize) -> Result<UniformTilings, NotationError> {
+    if polygon_ids.len() != polygons.len() {
+        return Err(NotationError::PolygonIdCount { expected: polygons.len(), received: polygon_ids.len() })
+    }
+    let notation_polygons = polygons
+        .iter()
+        .enumerate()
+        .map(|(polygon_index, polygon)| NotationPolygon::from_tile(polygon).ok_or(NotationError::UnsupportedPolygon { polygon_index }))
+        .collect::<Result<Vec<NotationPolygon>, NotationError>>()?;
+
+    let vertex_types = vertex_types(polygons, &notation_polygons)?;
+    // canonical keys of the tilings found so far, whether or not they were kept
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut titles: HashMap<String, usize> = HashMap::new();
+    let mut atlases: Vec<FullAtlasPost> = vec![];
+    let mut truncated = false;
+
+    for type_count in 1..=max_k {
+        for type_indices in combinations(vertex_types.len(), type_count) {
+            // the configuration only includes the polygons its vertex types use, in order of first use
+            let mut used: Vec<usize> = vec![];
+            let vertices = type_indices
+                .iter()
+                .map(|type_index| vertex_types[*type_index]
+                    .iter()
+                    .map(|corner| {
+                        let polygon_index = used.iter().position(|used| *used == corner.polygon_index).unwrap_or_else(|| {
+                            used.push(corner.polygon_index);
+                            used.len() - 1
+                        });
+                        Corner { polygon_index, kind: corner.kind }
+                    })
+                    .collect()
+                )
+                .collect::<Vec<Vec<Corner>>>();
+            let configuration = VertexConfiguration {
+                polygons: used.iter().map(|polygon_index| notation_polygons[*polygon_index].clone()).collect(),
+                vertices,
+            };
+            if !is_connectable(&configuration) {
+                continue
+            }
+
+            let tiles = used.iter().map(|polygon_index| polygons[*polygon_index].clone()).collect::<Vec<Tile>>();
+            let configuration_tiles = configuration.tiles();
+            let found = configuration.enumerate_vertex_posts(
+                MAX_ATLASES_PER_CONFIGURATION.min(limit - atlases.len()),
+                MAX_SEARCH_STEPS,
+                // tilings already seen are skipped first, as most atlases found repeat one
+                |vertices| match uniform_key(&tiles, &with_point_indices(&configuration_tiles, &tiles, vertices)) {
+                    Some((key, atlas)) => seen.insert(key) && matches!(uniformity(&atlas), Ok(k) if k <= max_k) && is_consistent(&atlas, CONSISTENCY_RADIUS),
+                    None => false,
+                },
+            );
+            let found = match found {
+                Err(NotationError::NoTiling) => continue,
+                Err(NotationError::SearchLimit { .. }) => {
+                    truncated = true;
+                    continue
+                },
+                Err(err) => return Err(err),
+                Ok((found, is_exhausted)) => {
+                    truncated |= !is_exhausted;
+                    found
+                },
+            };
+
+            let title = configuration.to_string();
+            for vertices in found {
+                let count = titles.entry(title.clone()).or_insert(0);
+                *count += 1;
+                atlases.push(FullAtlasPost {
+                    tiling: FullSubTilingPost {
+                        title: if *count == 1 { title.clone() } else { format!("{} #{}", title, count) },
+                        label_ids: None,
+                    },
+                    polygon_ids: used.iter().map(|polygon_index| polygon_ids[*polygon_index]).collect(),
+                    vertices: with_point_indices(&configuration_tiles, &tiles, &vertices),
+                    owner_id: None,
+                });
+            }
+            if atlases.len() >= limit {
+                return Ok(UniformTilings { atlases, truncated: true })
+            }
+        }
+    }
+
+    Ok(UniformTilings { atlases, truncated })
+}
+
+// vertex_types lists the vertex types which can be made from corners of polygons, as corners of notation_polygons
+fn vertex_types(polygons: &[Tile], notation_polygons: &[NotationPolygon]) -> Result<Vec<Vec<Corner>>, NotationError> {
+    Ok(atlas::vertex_types(polygons, usize::MAX, MAX_VERTEX_TYPE_STEPS)?
+        .into_iter()
+        .map(|vertex_type| vertex_type.corners
+            .iter()
+            .map(|corner| Corner {
+                polygon_index: corner.polygon_index,
+                kind: match notation_polygons[corner.polygon_index] {
+                    NotationPolygon::Regular { .. } => CornerKind::Regular,
+                    NotationPolygon::Star { .. } if polygons[corner.polygon_index].angle(corner.point_index) < PI => CornerKind::Tip,
+                    NotationPolygon::Star { .. } => CornerKind::Dent,
+                },
+            })
+            .collect()
+        )
+        .collect())
+}
+
+// combinations lists the ways of choosing count of n indices, in lexicographic order
+fn combinations(n: usize, count: usize) -> Vec<Vec<usize>> {
+    if count > n {
+        return vec![]
+    }
+    let mut combinations: Vec<Vec<usize>> = vec![];
+    let mut combination = (0..count).collect::<Vec<usize>>();
+    loop {
+        combinations.push(combination.clone());
+        let i = match (0..count).rev().find(|i| combination[*i] < n - count + i) { None => break, Some(i) => i };
+        combination[i] += 1;
+        for j in i + 1..count {
+            combination[j] = combination[j - 1] + 1;
+        }
+    }
+    combinations
+}
+
+// is_connectable rules out configurations before searching for their tilings if they have an edge, given by the corners
+// on either side of it, which no edge of any of the configuration's vertices can be paired with, or if their vertices
+// can't all be reached from one another by way of such pairings
+fn is_connectable(configuration: &VertexConfiguration) -> bool {
+    // edges[i] lists the corners on either side of each of vertex i's edges, and the corners
+    // an edge paired with it has on either side, in either order
+    let edges = configuration.vertices
+        .iter()
+        .map(|corners| (0..corners.len())
+            .map(|j| {
+                let (before, after) = (corners[(j + corners.len() - 1) % corners.len()], corners[j]);
+                let (next_before, next_after) = (
+                    Corner { polygon_index: before.polygon_index, kind: before.kind.next() },
+                    Corner { polygon_index: after.polygon_index, kind: after.kind.next() },
+                );
+                ((before, after), [(next_after, next_before), (next_before, next_after)])
+            })
+            .collect()
+        )
+        .collect::<Vec<Vec<((Corner, Corner), [(Corner, Corner); 2])>>>();
+    let is_paired = |i: usize, k: usize| edges[i]
+        .iter()
+        .any(|(_, pairs)| edges[k].iter().any(|(edge, _)| pairs.contains(edge)));
+
+    let is_every_edge_paired = edges
+        .iter()
+        .flatten()
+        .all(|(_, pairs)| edges.iter().flatten().any(|(edge, _)| pairs.contains(edge)));
+    if !is_every_edge_paired {
+        return false
+    }
+
+    let mut reached = vec![false; edges.len()];
+    let mut queue: VecDeque<usize> = VecDeque::from(vec![0]);
+    reached[0] = true;
+    while let Some(i) = queue.pop_front() {
+        for (k, reached) in reached.iter_mut().enumerate() {
+            if !*reached && is_paired(i, k) {
+                *reached = true;
+                queue.push_back(k);
+            }
+        }
+    }
+    reached.into_iter().all(|reached| reached)
+}
+
+// with_point_indices moves vertices' point indices from the points of the configuration's tiles to the matching
+// corners of tiles, the same polygons as given to uniform_tilings
+fn with_point_indices(configuration_tiles: &[Tile], tiles: &[Tile], vertices: &[FullAtlasVertexPost]) -> Vec<FullAtlasVertexPost> {
+    vertices
+        .iter()
+        .map(|vertex| FullAtlasVertexPost {
+            edges: vertex.edges
+                .iter()
+                .map(|edge| {
+                    let angle = configuration_tiles[edge.polygon_index].angle(edge.point_index);
+                    let tile = &tiles[edge.polygon_index];
+                    FullAtlasEdgePost {
+                        point_index: (0..tile.size()).find(|point_index| tile.angle(*point_index).approx_eq(angle, DEFAULT_F64_MARGIN)).unwrap_or(0),
+                        ..*edge
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+// uniform_key returns the canonical key of the atlas given by vertices, if it's valid
+fn uniform_key(tiles: &[Tile], vertices: &[FullAtlasVertexPost]) -> Option<(String, Atlas)> {
+    if !validate(tiles, vertices).valid {
+        return None
+    }
+    let atlas = Atlas::from_vertex_posts(tiles, vertices).ok()?;
+    Some((canonical_key(&atlas).ok()?, atlas))
+}
+
+// is_consistent places atlas' vertex stars within radius of the seed vertex star by way of their neighbors, as a Patch
+// does, and determines whether every vertex star is placed with the same link whichever neighbor it's reached from
+fn is_consistent(atlas: &Atlas, radius: f64) -> bool {
+    let seed = VertexStar::new(atlas, SEED_VERTEX_STAR_POINT, 0, false, 0.);
+    let mut vertex_stars: HashMap<Point, VertexStar> = HashMap::new();
+    let mut queue: VecDeque<VertexStar> = VecDeque::new();
+    vertex_stars.insert(seed.point, seed.clone());
+    queue.push_back(seed);
+
+    while let Some(vertex_star) = queue.pop_front() {
+        for index in 0..vertex_star.size() {
+            let neighbor = match vertex_star.get_neighbor_vertex_star(atlas, index) { None => return false, Some(neighbor) => neighbor };
+            if (&neighbor.point - &SEED_VERTEX_STAR_POINT).norm() > radius {
+                continue
+            }
+            match vertex_stars.get(&neighbor.point) {
+                Some(other) => {
+                    let is_same = other.proto_vertex_star_index == neighbor.proto_vertex_star_index
+                        && neighbor.link_vec.iter().all(|link_point| other.link_map.contains_key(link_point));
+                    if !is_same {
+                        return false
+                    }
+                },
+                None => {
+                    vertex_stars.insert(neighbor.point, neighbor.clone());
+                    queue.push_back(neighbor);
+                },
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tile::regular_polygon;
+
+    fn titles(polygons: &[Tile], max_k: usize, limit: usize) -> Vec<String> {
+        let polygon_ids = (1..=polygons.len() as i32).collect::<Vec<i32>>();
+        uniform_tilings(polygons, &polygon_ids, max_k, limit)
+            .unwrap()
+            .atlases
+            .into_iter()
+            .map(|atlas| atlas.tiling.title)
+            .collect()
+    }
+
+    #[test]
+    fn test_uniform_tilings() {
+        let polygons = [3, 4, 8, 12].iter().map(|sides| regular_polygon(1., *sides)).collect::<Vec<Tile>>();
+        let (triangle, square, octagon, dodecagon) = (&polygons[0], &polygons[1], &polygons[2], &polygons[3]);
+
+        assert_eq!(vec!["4^4", "4.8^2"], titles(&[square.clone(), octagon.clone()], 1, usize::MAX));
+        assert_eq!(vec!["4^4"], titles(&[square.clone(), octagon.clone()], 1, 1));
+        // 3^6 and 3.12^2 can't be joined edge to edge, so there's no 2-uniform tiling of triangles and dodecagons
+        assert_eq!(vec!["3^6", "3.12^2"], titles(&[triangle.clone(), dodecagon.clone()], 2, usize::MAX));
+
+        // vertex types follow the order of the polygons, and atlases only use the polygons their vertex configuration does
+        let atlases = uniform_tilings(&[octagon.clone(), square.clone()], &[10, 20], 1, usize::MAX).unwrap().atlases;
+        assert_eq!(vec!["8^2.4", "4^4"], atlases.iter().map(|atlas| atlas.tiling.title.as_str()).collect::<Vec<&str>>());
+        assert_eq!(vec![vec![10, 20], vec![20]], atlases.iter().map(|atlas| atlas.polygon_ids.clone()).collect::<Vec<Vec<i32>>>());
+        for atlas in atlases.iter() {
+            let tiles = atlas.polygon_ids.iter().map(|id| if *id == 10 { octagon.clone() } else { square.clone() }).collect::<Vec<Tile>>();
+            assert!(validate(&tiles, &atlas.vertices).valid);
+        }
+
+        assert_eq!(Err(NotationError::PolygonIdCount { expected: 2, received: 1 }), uniform_tilings(&polygons[..2], &[1], 1, 1).map(|_| ()));
+        let rectangle = Tile::new(vec![Point(0., 0.), Point(2., 0.), Point(2., 1.), Point(0., 1.)]);
+        assert_eq!(
+            Err(NotationError::UnsupportedPolygon { polygon_index: 1 }),
+            uniform_tilings(&[triangle.clone(), rectangle], &[1, 2], 1, 1).map(|_| ()),
+        );
+    }
+
+    #[test]
+    fn test_uniform_tilings_truncated() {
+        let (square, hexagon, octagon) = (regular_polygon(1., 4), regular_polygon(1., 6), regular_polygon(1., 8));
+
+        // every pairing of the hexagonal tiling's edges is searched through
+        let tilings = uniform_tilings(&[hexagon], &[1], 1, usize::MAX).unwrap();
+        assert_eq!(1, tilings.atlases.len());
+        assert!(!tilings.truncated);
+
+        // but the square tiling has too many pairings to search through
+        let tilings = uniform_tilings(&[square], &[1], 1, usize::MAX).unwrap();
+        assert_eq!(1, tilings.atlases.len());
+        assert!(tilings.truncated);
+
+        // stopping at limit truncates the tilings too
+        assert!(uniform_tilings(&[regular_polygon(1., 4), octagon], &[1, 2], 1, 1).unwrap().truncated);
+    }
+
+    #[test]
+    fn test_uniform_tilings_2_uniform() {
+        // the first 2-uniform tiling of triangles and squares follows their four 1-uniform ones
+        let polygons = [3, 4].iter().map(|sides| regular_polygon(1., *sides)).collect::<Vec<Tile>>();
+        assert_eq!(vec!["3^6", "3^3.4^2", "3^2.4.3.4", "4^4", "[3^6; 3^3.4^2]"], titles(&polygons, 2, 5));
+    }
+
+    #[test]
+    fn test_uniform_tilings_star() {
+        // hexagrams with 60° tips tile the plane with triangles filling their dents
+        let (triangle, star) = (regular_polygon(1., 3), tile::star_polygon(1., 6, PI / 3.));
+        let tilings = uniform_tilings(&[triangle.clone(), star.clone()], &[1, 2], 1, usize::MAX).unwrap();
+        assert_eq!(
+            vec!["3^6", "3.6apio3.6aapio3"],
+            tilings.atlases.iter().map(|atlas| atlas.tiling.title.as_str()).collect::<Vec<&str>>(),
+        );
+        let atlas = &tilings.atlases[1];
+        assert_eq!(vec![1, 2], atlas.polygon_ids);
+        assert!(validate(&[triangle, star], &atlas.vertices).valid);
+    }
+
+    #[test]
+    fn test_is_connectable() {
+        for (notation, expected) in [
+            ("[3^6; 3^3.4^2]", true),
+            ("[3.4^2.6; 3.6.3.6]", true),
+            ("[4^4; 6^3]", false),
+            ("[3^6; 3.4.6.4]", false),
+            ("4.6apio6.6aapio2.6apio6", false),
+            ("[4.6apio6.6aapio2.6apio6; 4.6aapio6; 6apio2.6aapio6]", true),
+        ].iter() {
+            assert_eq!(*expected, is_connectable(&VertexConfiguration::parse(notation).unwrap()), "{}", notation);
+        }
+    }
+}
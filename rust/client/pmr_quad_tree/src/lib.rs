@@ -210,6 +210,11 @@ impl<K: Eq + Hash, S: Spatial<Hashed = K>> Leaf<K, S> {
         Some(&self)
     }
 
+    fn remove(&mut self, item: &RcItem<K, S>) {
+        let weak = item.downgrade();
+        self.items.retain(|leaf_item| leaf_item.0.upgrade().is_some() && !leaf_item.0.ptr_eq(&weak.0));
+    }
+
     fn nearest_neighbor<'a>(&'a self, point: &Point) -> Option<Neighbor<'a, K, S>> {
         let mut min_distance = std::f64::MAX;
         let mut arg_min: Option<Neighbor<K, S>> = None;
@@ -298,6 +303,31 @@ impl<K: Eq + Hash, S: Spatial<Hashed = K>> Node<K, S> {
         }
     }
 
+    fn remove(&mut self, item: &RcItem<K, S>) {
+        if !item.value().intersects(self.bounds()) { return }
+        match &mut self.node {
+            NodeType::InnerNode(inner_node) => {
+                (*inner_node).ne.remove(item);
+                (*inner_node).nw.remove(item);
+                (*inner_node).se.remove(item);
+                (*inner_node).sw.remove(item);
+            },
+            NodeType::Leaf(leaf) => leaf.remove(item),
+        };
+    }
+
+    fn find_intersecting_leaves<'b>(&'b self, bounds: &Bounds, leaves: &mut Vec<&'b Leaf<K, S>>) {
+        if !self.bounds().intersects(bounds) { return }
+        match &self.node {
+            NodeType::InnerNode(inner_node) => {
+                for child in (*inner_node).children().iter() {
+                    child.find_intersecting_leaves(bounds, leaves);
+                }
+            },
+            NodeType::Leaf(leaf) => leaves.push(leaf),
+        };
+    }
+
     fn find_nearest_neighbor_candidate_leaves<'b>(&'b self, point: &Point, candidate_radius: f64, candidates: &mut Vec<&'b Leaf<K, S>>) {
         match &self.node {
             NodeType::InnerNode(inner_node) => (*inner_node).find_nearest_neighbor_candidate_leaves(point, candidate_radius, candidates),
@@ -418,6 +448,32 @@ impl<K: Eq + Hash, S: Spatial<Hashed = K>> Tree<K, S> {
         None
     }
 
+    // intersecting returns every item in the tree which intersects the provided bounds
+    pub fn intersecting(&self, bounds: &Bounds) -> Vec<RcItem<K, S>> {
+        let mut leaves: Vec<&Leaf<K, S>> = vec![];
+        self.root.find_intersecting_leaves(bounds, &mut leaves);
+
+        let mut intersecting: HashMap<K, RcItem<K, S>> = HashMap::default();
+        for leaf in leaves.into_iter() {
+            for item in leaf.items.iter() {
+                if let Some(rc_item) = item.upgrade() {
+                    if rc_item.value().intersects(bounds) {
+                        let key = rc_item.value().key();
+                        intersecting.entry(key).or_insert(rc_item.clone());
+                    }
+                }
+            }
+        }
+        intersecting.into_iter().map(|(_, rc_item)| rc_item).collect()
+    }
+
+    // remove drops the item stored under key from the tree, returning it if it existed
+    pub fn remove(&mut self, key: &K) -> Option<RcItem<K, S>> {
+        let rc_item = match self.items.remove(key) { None => return None, Some(rc_item) => rc_item };
+        self.root.remove(&rc_item);
+        Some(rc_item)
+    }
+
     // https://www.cs.umd.edu/~hjs/pubs/ssd91.pdf - Section 5
     pub fn nearest_neighbor<'b>(&'b self, point: &Point) -> Result<Neighbor<'b, K, S>, String> {
         let bounding_leaf = match self.root.bounding_leaf(point) {
@@ -1748,4 +1804,33 @@ mod tests {
 
         tree.nearest_neighbor(&Point(0.5, 0.5)).expect("foo");
     }
+
+    #[test]
+    pub fn test_remove() {
+        let mut tree = Tree::new(Config {
+            initial_radius: 100.,
+            max_depth: 40,
+            splitting_threshold: 10,
+        }, false);
+
+        let square = Tile::new(vec![Point(0., 0.), Point(1., 0.), Point(1., 1.), Point(0., 1.)]);
+        let removed = square.transform(&Euclid::Translate((0., 0.)));
+        let remaining = square.transform(&Euclid::Translate((2., 2.)));
+
+        tree.insert(removed.clone());
+        tree.insert(remaining.clone());
+
+        assert!(tree.remove(&removed.key()).is_some());
+        assert!(tree.remove(&removed.key()).is_none());
+        assert!(!tree.has(&removed.key()));
+
+        let neighbor = tree.nearest_neighbor(&Point(0.5, 0.5)).expect("foo");
+        assert_eq!(remaining.centroid, neighbor.item.upgrade().expect("foo").value().centroid);
+
+        let intersecting = tree.intersecting(&Bounds { center: Point(0.5, 0.5), radius: 1. });
+        assert_eq!(0, intersecting.len());
+
+        let intersecting = tree.intersecting(&Bounds { center: Point(2.5, 2.5), radius: 1. });
+        assert_eq!(1, intersecting.len());
+    }
 }
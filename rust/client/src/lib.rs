@@ -2,30 +2,39 @@ extern crate console_error_panic_hook;
 #[macro_use] extern crate lazy_static;
 extern crate serde_json;
 
-mod coloring;
 mod routes;
+mod tile_state;
+mod types;
 
 pub use self::routes::*;
+pub use self::tile_state::*;
+pub use self::types::*;
 
 use atlas::Atlas;
 use canvas::*;
-use coloring::Coloring;
-use patch::PatchTile;
-use plotters::prelude::*;
+use geometry::Bounds;
+use models::{ColorSchemeData, FullAtlas};
+use patch::{Automaton, LifeRule, Patch, PatchSnapshot, PatchTile};
+use plotters::style::RGBColor;
 use pmr_quad_tree::Config as TreeConfig;
 use std::{collections::HashMap, panic};
-use tiling::{Config as TilingConfig, Tiling};
+use tiling::{Config as TilingConfig, Tiling, TilingError};
 use wasm_bindgen::prelude::*;
 
-type State = ();
+type State = TileState;
 
 struct Global {
+    atlas_id: i32,
+    automaton: Option<Automaton<bool>>,
     coloring: Coloring,
     tiling: Tiling<State>,
 }
 
 static CANVAS_RADIUS: f64 = 4.;
 
+// color of the tiles which are alive in the global's automaton, in place of their coloring's color
+static LIVE_TILE_COLOR: RGBColor = RGBColor(34, 34, 34);
+
 static TILE_TREE_CONFIG: TreeConfig = TreeConfig {
     initial_radius: 1000.,
     max_depth: 50,
@@ -49,35 +58,106 @@ pub fn init() {
     }
 }
 
+// setTiling replaces the global's tiling with an empty patch of tiling_id's tiling, colored by the
+// color scheme with color_scheme_id if there is one, or else by the named coloring strategy, which
+// defaults to greedy
 #[wasm_bindgen]
 #[allow(non_snake_case)]
-pub async fn setTiling(global_id: i32, tiling_id: i32) -> Result<(), JsValue> {
+pub async fn setTiling(global_id: i32, tiling_id: i32, coloring: Option<String>, color_scheme_id: Option<i32>) -> Result<(), JsValue> {
     panic::set_hook(Box::new(console_error_panic_hook::hook));
 
     let db_atlas = get_atlas_by_tiling_id(tiling_id).await?;
-    let atlas = Atlas::new(&db_atlas).map_err(|e| JsValue::from_str(&e))?;
-    let coloring = Coloring::new(&atlas);
+    let atlas = Atlas::new(&db_atlas).map_err(ClientError::from)?;
+    let mut tiling = Tiling::new(get_tiling_config(global_id, atlas)).map_err(ClientError::from)?;
 
-    let tiling = Tiling::new(TilingConfig {
-        atlas,
-        canvas_radius: CANVAS_RADIUS,
-        id: global_id,
-        tile_tree_config: TILE_TREE_CONFIG.clone(),
-        vertex_star_tree_config: VERTEX_STAR_TREE_CONFIG.clone(),
-    }).map_err(|e| JsValue::from_str(&e))?;
+    let coloring = get_coloring(&db_atlas, &mut tiling.patch, coloring, color_scheme_id).await?;
 
-    let new_global = Global { coloring, tiling };
+    set_global(global_id, Global { atlas_id: db_atlas.id, automaton: None, coloring, tiling });
+    Ok(())
+}
 
-    let globals = unsafe { GLOBALS.as_mut().unwrap() };
-    if let None = globals.get(&global_id) {
-        globals.insert(global_id, new_global);
-    } else {
-        let global = globals.get_mut(&global_id).unwrap();
-        *global = new_global;
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn getPatchSnapshot(global_id: i32) -> Result<String, JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    unsafe {
+        let global = GLOBALS
+            .as_ref()
+            .unwrap()
+            .get(&global_id)
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        global
+            .tiling
+            .patch
+            .to_snapshot(global.atlas_id)
+            .and_then(|snapshot| snapshot.to_json())
+            .map_err(|e| ClientError::from(e).into())
+    }
+}
+
+// setPatchSnapshot replaces the global's tiling with the patch stored in snapshot,
+// which is expected to be a JSON serialized PatchSnapshot for tiling_id's atlas, colored as by setTiling
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub async fn setPatchSnapshot(global_id: i32, tiling_id: i32, snapshot: String, coloring: Option<String>, color_scheme_id: Option<i32>) -> Result<(), JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    let snapshot = PatchSnapshot::<State>::from_json(&snapshot).map_err(ClientError::from)?;
+
+    let db_atlas = get_atlas_by_tiling_id(tiling_id).await?;
+    if db_atlas.id != snapshot.atlas_id {
+        return Err(ClientError::new(
+            ErrorCode::InvalidArgument,
+            format!("patch snapshot is for atlas {} but tiling {} uses atlas {}", snapshot.atlas_id, tiling_id, db_atlas.id),
+        ).into());
+    }
+    let atlas = Atlas::new(&db_atlas).map_err(ClientError::from)?;
+    let mut tiling = Tiling::from_snapshot(get_tiling_config(global_id, atlas), snapshot).map_err(ClientError::from)?;
+
+    let coloring = get_coloring(&db_atlas, &mut tiling.patch, coloring, color_scheme_id).await?;
+
+    set_global(global_id, Global { atlas_id: db_atlas.id, automaton: None, coloring, tiling });
+
+    unsafe {
+        let global = GLOBALS.as_mut().unwrap().get_mut(&global_id).unwrap();
+
+        let (coloring, automaton) = (&global.coloring, &global.automaton);
+
+        global
+            .tiling
+            .draw_tile_diffs(|patch_tile: &PatchTile<State>| tile_color(coloring, automaton, patch_tile))
+            .map_err(ClientError::from)?;
     }
+
     Ok(())
 }
 
+// getPatchSvg renders the global's included tiles as an svg document fit to their bounds
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn getPatchSvg(global_id: i32) -> Result<String, JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    unsafe {
+        let global = GLOBALS
+            .as_ref()
+            .unwrap()
+            .get(&global_id)
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        let (coloring, automaton) = (&global.coloring, &global.automaton);
+
+        svg::to_svg(
+            &global.tiling.patch,
+            &svg::Config::default(),
+            |patch_tile: &PatchTile<State>| tile_color(coloring, automaton, patch_tile),
+        )
+            .map_err(|e| ClientError::from(TilingError::from(e)).into())
+    }
+}
+
 #[wasm_bindgen]
 #[allow(non_snake_case)]
 pub fn removeTiling(global_id: i32) {
@@ -96,18 +176,18 @@ pub fn insertTileByPoint(global_id: i32, x: f64, y: f64) -> Result<(), JsValue>
             .as_mut()
             .unwrap()
             .get_mut(&global_id)
-            .ok_or_else(|| JsValue::from_str(&format!("no global found with id {}", global_id)))?;
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
 
-        let coloring = &global.coloring;
+        let (coloring, automaton) = (&global.coloring, &global.automaton);
 
         global
             .tiling
             .insert_tile_by_point(
                 from_canvas_point(x, y),
-                Some(()),
-                |patch_tile: &PatchTile<State>| coloring.0.get(&patch_tile.size()).unwrap_or(&BLACK),
+                Some(TileState::default()),
+                |patch_tile: &PatchTile<State>| tile_color(coloring, automaton, patch_tile),
             )
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))?;
+            .map_err(ClientError::from)?;
     }
 
     Ok(())
@@ -115,20 +195,410 @@ pub fn insertTileByPoint(global_id: i32, x: f64, y: f64) -> Result<(), JsValue>
 
 #[wasm_bindgen]
 #[allow(non_snake_case)]
-pub fn getNeighbors(global_id: i32, x: f64, y: f64) -> Result<JsValue, JsValue> {
+pub fn removeTileByPoint(global_id: i32, x: f64, y: f64) -> Result<(), JsValue> {
     panic::set_hook(Box::new(console_error_panic_hook::hook));
 
     unsafe {
-        GLOBALS
+        let global = GLOBALS
+            .as_mut()
+            .unwrap()
+            .get_mut(&global_id)
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        let (coloring, automaton) = (&global.coloring, &global.automaton);
+
+        global
+            .tiling
+            .remove_tile_by_point(
+                from_canvas_point(x, y),
+                |patch_tile: &PatchTile<State>| tile_color(coloring, automaton, patch_tile),
+            )
+            .map_err(ClientError::from)?;
+    }
+
+    Ok(())
+}
+
+// fillBounds includes every tile overlapping the square centered on (x, y) with the provided
+// radius, all in canvas coordinates
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn fillBounds(global_id: i32, x: f64, y: f64, radius: f64) -> Result<(), JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    unsafe {
+        let global = GLOBALS
+            .as_mut()
+            .unwrap()
+            .get_mut(&global_id)
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        let (coloring, automaton) = (&global.coloring, &global.automaton);
+
+        global
+            .tiling
+            .fill_bounds(
+                &Bounds { center: from_canvas_point(x, y), radius: radius / SCALE },
+                TileState::default(),
+                |patch_tile: &PatchTile<State>| tile_color(coloring, automaton, patch_tile),
+            )
+            .map_err(ClientError::from)?;
+    }
+
+    Ok(())
+}
+
+// growCoronas includes the tile at (x, y), in canvas coordinates, along with its first k coronas and
+// returns the centroids of the tiles in each corona, the 0-th being the tile itself
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn growCoronas(global_id: i32, x: f64, y: f64, k: usize) -> Result<Coronas, JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    unsafe {
+        let global = GLOBALS
+            .as_mut()
+            .unwrap()
+            .get_mut(&global_id)
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        let (coloring, automaton) = (&global.coloring, &global.automaton);
+
+        let coronas = global
+            .tiling
+            .grow_coronas(
+                from_canvas_point(x, y),
+                k,
+                TileState::default(),
+                |patch_tile: &PatchTile<State>| tile_color(coloring, automaton, patch_tile),
+            )
+            .map_err(ClientError::from)?;
+        Ok(to_js(&to_canvas_coronas(&coronas))?)
+    }
+}
+
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn undo(global_id: i32) -> Result<(), JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    unsafe {
+        let global = GLOBALS
+            .as_mut()
+            .unwrap()
+            .get_mut(&global_id)
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        let (coloring, automaton) = (&global.coloring, &global.automaton);
+
+        global
+            .tiling
+            .undo(|patch_tile: &PatchTile<State>| tile_color(coloring, automaton, patch_tile))
+            .map_err(ClientError::from)?;
+    }
+
+    Ok(())
+}
+
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn redo(global_id: i32) -> Result<(), JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    unsafe {
+        let global = GLOBALS
+            .as_mut()
+            .unwrap()
+            .get_mut(&global_id)
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        let (coloring, automaton) = (&global.coloring, &global.automaton);
+
+        global
+            .tiling
+            .redo(|patch_tile: &PatchTile<State>| tile_color(coloring, automaton, patch_tile))
+            .map_err(ClientError::from)?;
+    }
+
+    Ok(())
+}
+
+// setTileState replaces the state of the included tile at (x, y), in canvas coordinates, with
+// value, a TileState e.g. { color: [255, 0, 0], label: "a", value: 1 } or null for a plain tile,
+// and redraws it. Setting a tile's state can be undone like inserting it.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn setTileState(global_id: i32, x: f64, y: f64, value: JsValue) -> Result<(), JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    let state = value.into_serde::<TileState>().map_err(|e| ClientError::new(ErrorCode::InvalidArgument, format!("invalid tile state: {}", e)))?;
+
+    unsafe {
+        let global = GLOBALS
+            .as_mut()
+            .unwrap()
+            .get_mut(&global_id)
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        let (coloring, automaton) = (&global.coloring, &global.automaton);
+
+        global
+            .tiling
+            .set_tile_state(
+                from_canvas_point(x, y),
+                state,
+                |patch_tile: &PatchTile<State>| tile_color(coloring, automaton, patch_tile),
+            )
+            .map_err(ClientError::from)?;
+    }
+
+    Ok(())
+}
+
+// getTileState returns the state of the included tile at (x, y), in canvas coordinates, or null
+// if there's no included tile there
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn getTileState(global_id: i32, x: f64, y: f64) -> Result<TileStateOrNull, JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    unsafe {
+        let global = GLOBALS
             .as_ref()
             .unwrap()
             .get(&global_id)
-            .ok_or_else(|| JsValue::from_str(&format!("no canvas found with id {}", global_id)))?
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        let state = global.tiling.patch
+            .get_included_patch_tile_by_point(&from_canvas_point(x, y))
+            .map(|patch_tile_rc| patch_tile_rc.value().state.clone().unwrap_or_default());
+        Ok(to_js(&state)?)
+    }
+}
+
+// setAutomaton replaces the global's automaton with one stepping its included tiles by a Life-like
+// rule in B/S notation, e.g. "B3/S23" or "B2/S34;3:B1/S12", see LifeRule::parse. Every tile
+// starts out dead, and there's no automaton if there's no rule.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn setAutomaton(global_id: i32, rule: Option<String>) -> Result<(), JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    let automaton = match rule {
+        None => None,
+        Some(rule) => Some(Automaton::new(LifeRule::parse(&rule).map_err(ClientError::from)?, false)),
+    };
+
+    unsafe {
+        let global = GLOBALS
+            .as_mut()
+            .unwrap()
+            .get_mut(&global_id)
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        if let Some(automaton) = global.automaton.as_mut() {
+            automaton.clear(&mut global.tiling.patch).map_err(ClientError::from)?;
+        }
+        global.automaton = automaton;
+
+        let (coloring, automaton) = (&global.coloring, &global.automaton);
+
+        global
             .tiling
-            .patch
-            .get_tile_neighbor_centroids(&from_canvas_point(x, y))
-            .map(|centroids| JsValue::from_str(&format!("{:?}", centroids)))
-            .ok_or_else(|| JsValue::FALSE)
+            .draw_tile_diffs(|patch_tile: &PatchTile<State>| tile_color(coloring, automaton, patch_tile))
+            .map_err(ClientError::from)?;
+    }
+
+    Ok(())
+}
+
+// setTileAlive brings the tile at (x, y), in canvas coordinates, to life in the global's automaton
+// or kills it
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn setTileAlive(global_id: i32, x: f64, y: f64, alive: bool) -> Result<(), JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    unsafe {
+        let global = GLOBALS
+            .as_mut()
+            .unwrap()
+            .get_mut(&global_id)
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        global.automaton
+            .as_mut()
+            .ok_or_else(|| ClientError::missing_automaton(global_id))?
+            .set(&mut global.tiling.patch, &from_canvas_point(x, y), alive)
+            .map_err(ClientError::from)?;
+
+        let (coloring, automaton) = (&global.coloring, &global.automaton);
+
+        global
+            .tiling
+            .draw_tile_diffs(|patch_tile: &PatchTile<State>| tile_color(coloring, automaton, patch_tile))
+            .map_err(ClientError::from)?;
+    }
+
+    Ok(())
+}
+
+// stepAutomaton steps the global's automaton once, redraws the tiles whose state changed and
+// returns how many there were
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn stepAutomaton(global_id: i32) -> Result<usize, JsValue> {
+    runAutomaton(global_id, 1)
+}
+
+// runAutomaton steps the global's automaton up to steps times, stopping early once a step changes
+// nothing, and redraws the tiles whose state changed. It returns the number of tiles changed by
+// the last step.
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn runAutomaton(global_id: i32, steps: usize) -> Result<usize, JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    unsafe {
+        let global = GLOBALS
+            .as_mut()
+            .unwrap()
+            .get_mut(&global_id)
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        let automaton = global.automaton
+            .as_mut()
+            .ok_or_else(|| ClientError::missing_automaton(global_id))?;
+
+        let mut num_changed = 0;
+        for _ in 0..steps {
+            num_changed = automaton.step(&mut global.tiling.patch).map_err(ClientError::from)?.len();
+            if num_changed == 0 {
+                break
+            }
+        }
+
+        let (coloring, automaton) = (&global.coloring, &global.automaton);
+
+        global
+            .tiling
+            .draw_tile_diffs(|patch_tile: &PatchTile<State>| tile_color(coloring, automaton, patch_tile))
+            .map_err(ClientError::from)?;
+
+        Ok(num_changed)
+    }
+}
+
+// getTile describes the included tile at (x, y), in canvas coordinates, or returns null if there's
+// no included tile there
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn getTile(global_id: i32, x: f64, y: f64) -> Result<TileDescriptorOrNull, JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    unsafe {
+        let global = GLOBALS
+            .as_ref()
+            .unwrap()
+            .get(&global_id)
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        let descriptor = global.tiling.patch
+            .get_included_patch_tile_by_point(&from_canvas_point(x, y))
+            .map(|patch_tile_rc| TileDescriptor::new(&global.tiling.patch, &patch_tile_rc.value()));
+        Ok(to_js(&descriptor)?)
+    }
+}
+
+// getNeighbors describes the included tiles sharing an edge with the included tile at (x, y), in
+// canvas coordinates, or returns null if there's no included tile there
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn getNeighbors(global_id: i32, x: f64, y: f64) -> Result<TileDescriptorsOrNull, JsValue> {
+    panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    unsafe {
+        let global = GLOBALS
+            .as_ref()
+            .unwrap()
+            .get(&global_id)
+            .ok_or_else(|| ClientError::missing_global(global_id))?;
+
+        let descriptors = global.tiling.patch
+            .get_included_patch_tile_by_point(&from_canvas_point(x, y))
+            .map(|patch_tile_rc| patch_tile_rc
+                .neighbors()
+                .values()
+                .filter_map(|weak_item| weak_item.upgrade())
+                .filter(|neighbor_rc_item| neighbor_rc_item.value().state.is_some())
+                .map(|neighbor_rc_item| TileDescriptor::new(&global.tiling.patch, &neighbor_rc_item.value()))
+                .collect::<Vec<TileDescriptor>>()
+            );
+        Ok(to_js(&descriptors)?)
+    }
+}
+
+fn get_tiling_config(global_id: i32, atlas: Atlas) -> TilingConfig {
+    TilingConfig {
+        atlas,
+        canvas_radius: CANVAS_RADIUS,
+        id: global_id,
+        tile_tree_config: TILE_TREE_CONFIG.clone(),
+        vertex_star_tree_config: VERTEX_STAR_TREE_CONFIG.clone(),
+    }
+}
+
+// get_coloring colors patch's tiling with the color scheme with color_scheme_id if there is one, which must belong
+// to db_atlas' tiling, or else with the named coloring strategy, or the default one if there's no name. Either uses
+// the unit cell patch finds to fill itself rather than finding another.
+async fn get_coloring(db_atlas: &FullAtlas, patch: &mut Patch<State>, name: Option<String>, color_scheme_id: Option<i32>) -> Result<Coloring, JsValue> {
+    if let Some(color_scheme_id) = color_scheme_id {
+        let color_scheme = get_color_scheme(color_scheme_id).await?;
+        if color_scheme.tiling_id != db_atlas.tiling.tiling.id {
+            return Err(ClientError::new(
+                ErrorCode::InvalidArgument,
+                format!("color scheme {} is for tiling {} but atlas {} is for tiling {}", color_scheme_id, color_scheme.tiling_id, db_atlas.id, db_atlas.tiling.tiling.id),
+            ).into());
+        }
+        let data = color_scheme.parse_data().map_err(|e| ClientError::new(ErrorCode::Serialization, e.to_string()))?;
+        let polygon_ids = db_atlas.polygons.iter().map(|polygon| polygon.polygon.id).collect::<Vec<i32>>();
+        let scheme = match data {
+            ColorSchemeData::Polygon { .. } => Scheme::Polygon,
+            ColorSchemeData::Orbit { .. } => Scheme::Orbit,
+        };
+        let cell = patch.get_cell().map_err(ClientError::from)?;
+        return Coloring::from_scheme(&patch.atlas, &cell, &polygon_ids, scheme, |key| data.color(key))
+            .map_err(|message| ClientError::new(ErrorCode::Tiling, message).into())
+    }
+    let strategy = match name {
+        None => Strategy::default(),
+        Some(name) => Strategy::parse(&name).ok_or_else(|| ClientError::new(ErrorCode::InvalidArgument, format!("unknown coloring strategy {:?}", name)))?,
+    };
+    let cell = patch.get_cell().ok();
+    Ok(Coloring::new(&patch.atlas, cell.as_deref(), strategy))
+}
+
+// tile_color is the color a tile is drawn in, which is LIVE_TILE_COLOR if it's alive in automaton,
+// or else the color set in its state, if there is one, in place of its coloring's color
+fn tile_color(coloring: &Coloring, automaton: &Option<Automaton<bool>>, patch_tile: &PatchTile<State>) -> RGBColor {
+    if let Some(automaton) = automaton {
+        if *automaton.get(&patch_tile.tile.centroid) {
+            return LIVE_TILE_COLOR
+        }
+    }
+    match patch_tile.state.as_ref().and_then(|state| state.color) {
+        Some((red, green, blue)) => RGBColor(red, green, blue),
+        None => *coloring.get(patch_tile),
+    }
+}
+
+fn set_global(global_id: i32, new_global: Global) {
+    let globals = unsafe { GLOBALS.as_mut().unwrap() };
+    if let None = globals.get(&global_id) {
+        globals.insert(global_id, new_global);
+    } else {
+        let global = globals.get_mut(&global_id).unwrap();
+        *global = new_global;
     }
 }
 
@@ -1,3 +1,4 @@
+use crate::{ClientError, ErrorCode, to_js};
 use models;
 use paste::paste;
 use percent_encoding::{self, NON_ALPHANUMERIC};
@@ -30,36 +31,47 @@ struct ResponseDetails {
     #[serde(rename = "statusText")]
     status_text: String,
     url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-enum Error {
-    Default,
-    Serde,
-    Query,
-    Network(ResponseDetails),
-    Url,
+// request_error is the ClientError of a response which isn't ok, whose details are the response's along with the
+// server's error body, if it sent one, and whose message is the body's if it has one
+async fn request_error(response: &Response) -> ClientError {
+    let body: Option<serde_json::Value> = match response.json() {
+        Ok(json) => JsFuture::from(json).await.ok().and_then(|json| json.into_serde().ok()),
+        Err(_) => None,
+    };
+    let message = match body.as_ref().and_then(|body| body.get("message")).and_then(|message| message.as_str()) {
+        Some(message) => String::from(message),
+        None => format!("request to {} failed with status {}", response.url(), response.status()),
+    };
+    let details = ResponseDetails {
+        ok: response.ok(),
+        status: response.status(),
+        status_text: response.status_text(),
+        url: response.url(),
+        body,
+    };
+    ClientError {
+        code: ErrorCode::Request,
+        message,
+        details: serde_json::to_value(&details).ok(),
+    }
 }
 
-impl From<&Response> for Error {
-    fn from(res: &Response) -> Error {
-        Error::Network(ResponseDetails {
-            ok: res.ok(),
-            status: res.status(),
-            status_text: res.status_text(),
-            url: res.url(),
-        })
-    }
+// fetch_error is the ClientError of a request which couldn't be made or whose response couldn't be read
+fn fetch_error(err: JsValue) -> ClientError {
+    ClientError::new(ErrorCode::Request, format!("request failed: {:?}", err))
 }
 
-pub fn percent_encode(query: String) -> String {
-    percent_encoding::percent_encode(query.as_bytes(), NON_ALPHANUMERIC).to_string()
+// invalid_argument is the ClientError of an argument of an export which couldn't be parsed
+fn invalid_argument(name: &str) -> ClientError {
+    ClientError::new(ErrorCode::InvalidArgument, format!("invalid {}", name))
 }
 
-impl Error {
-    fn js_value(&self) -> JsValue {
-        JsValue::from_serde(&self).unwrap()
-    }
+pub fn percent_encode(query: String) -> String {
+    percent_encoding::percent_encode(query.as_bytes(), NON_ALPHANUMERIC).to_string()
 }
 
 fn url(path: &str) -> String {
@@ -69,7 +81,7 @@ fn url(path: &str) -> String {
     format!("{}{}", *BASE_URL, path)
 }
 
-fn clean_query(url: String) -> Result<String, JsValue> {
+fn clean_query(url: String) -> Result<String, ClientError> {
     let split_url = url.split("?").collect::<Vec<&str>>();
 
     let mut new_query = String::from("");
@@ -98,7 +110,7 @@ fn clean_query(url: String) -> Result<String, JsValue> {
         );
     }
 
-    let base_url = String::from(*split_url.get(0).ok_or(Error::Query.js_value())?);
+    let base_url = String::from(*split_url.get(0).ok_or_else(|| invalid_argument("query"))?);
 
     if new_query.len() == 0 {
         Ok(base_url)
@@ -126,16 +138,16 @@ macro_rules! get_delete {
                     $($arg_name: JsValue,)*
                 ) -> Result<JsValue, JsValue> {
                     let value = $fn_name(
-                        $($param_name.into_serde().or_else(|_| Err(JsValue::from_str(stringify!($param_name))))?,)*
-                        $($arg_name.into_serde().or_else(|_| Err(JsValue::from_str(stringify!($arg_name))))?,)*
+                        $($param_name.into_serde().map_err(|_| invalid_argument(stringify!($param_name)))?,)*
+                        $($arg_name.into_serde().map_err(|_| invalid_argument(stringify!($arg_name)))?,)*
                     ).await?;
-                    Ok(JsValue::from_serde(&value).or_else(|_| Err(Error::Serde.js_value()))?)
+                    Ok(to_js::<JsValue>(&value)?)
                 }
 
                 pub async fn $fn_name(
                     $($param_name: $param_type,)*
                     $($arg_name: Option<$arg_type>,)*
-                ) -> Result<$return_type, JsValue> {
+                ) -> Result<$return_type, ClientError> {
                     panic::set_hook(Box::new(console_error_panic_hook::hook));
 
                     let window = web_sys::window().unwrap();
@@ -147,12 +159,12 @@ macro_rules! get_delete {
                     )))?;
 
                     let request = Request::new_with_str(&url)
-                        .or_else(|_| Err(Error::Url.js_value()))?;
+                        .map_err(|_| invalid_argument("url"))?;
 
                     request
                         .headers()
                         .set("Accept", "application/json")
-                        .or_else(|_| Err(Error::Url.js_value()))?;
+                        .map_err(|_| invalid_argument("url"))?;
 
                     let mut opts = RequestInit::new();
 
@@ -161,19 +173,21 @@ macro_rules! get_delete {
                     opts.credentials(RequestCredentials::Include);
 
                     let response: Response = JsFuture::from(window.fetch_with_request_and_init(&request, &opts))
-                        .await?
-                        .dyn_into()
-                        .unwrap();
+                        .await
+                        .and_then(JsCast::dyn_into)
+                        .map_err(fetch_error)?;
 
                     if !response.ok() {
-                        return Err(Error::from(&response).js_value());
+                        return Err(request_error(&response).await);
                     }
 
                     // Convert this other `Promise` into a rust `Future`.
-                    let json = JsFuture::from(response.json()?).await?;
+                    let json = JsFuture::from(response.json().map_err(fetch_error)?).await.map_err(fetch_error)?;
 
                     // Use serde to parse the JSON into a struct.
-                    let value: $return_type = json.into_serde().or_else(|_| Err(Error::from(&response).js_value()))?;
+                    let value: $return_type = json
+                        .into_serde()
+                        .map_err(|err| ClientError::new(ErrorCode::Serialization, err.to_string()))?;
 
                     Ok(value)
                 }
@@ -203,18 +217,18 @@ macro_rules! post_patch {
                     $($arg_name: JsValue,)*
                 ) -> Result<JsValue, JsValue> {
                     let value = $fn_name(
-                        $($data_name.into_serde().or_else(|_| Err(JsValue::from_str(stringify!($data_name))))?,)?
-                        $($param_name.into_serde().or_else(|_| Err(JsValue::from_str(stringify!($param_name))))?,)*
-                        $($arg_name.into_serde().or_else(|_| Err(JsValue::from_str(stringify!($arg_name))))?,)*
+                        $($data_name.into_serde().map_err(|_| invalid_argument(stringify!($data_name)))?,)?
+                        $($param_name.into_serde().map_err(|_| invalid_argument(stringify!($param_name)))?,)*
+                        $($arg_name.into_serde().map_err(|_| invalid_argument(stringify!($arg_name)))?,)*
                     ).await?;
-                    Ok(JsValue::from_serde(&value).or_else(|_| Err(Error::Serde.js_value()))?)
+                    Ok(to_js::<JsValue>(&value)?)
                 }
 
                 pub async fn $fn_name(
                     $($data_name: $data_type,)?
                     $($param_name: $param_type,)*
                     $($arg_name: Option<$arg_type>,)*
-                ) -> Result<$return_type, JsValue> {
+                ) -> Result<$return_type, ClientError> {
                     panic::set_hook(Box::new(console_error_panic_hook::hook));
 
                     let window = web_sys::window().unwrap();
@@ -226,12 +240,12 @@ macro_rules! post_patch {
                     )))?;
 
                     let request = Request::new_with_str(&url)
-                        .or_else(|_| Err(Error::Url.js_value()))?;
+                        .map_err(|_| invalid_argument("url"))?;
 
                     request
                         .headers()
                         .set("Accept", "application/json")
-                        .or_else(|_| Err(Error::Url.js_value()))?;
+                        .map_err(|_| invalid_argument("url"))?;
 
                     let mut opts = RequestInit::new();
 
@@ -242,24 +256,26 @@ macro_rules! post_patch {
                     $(
                         opts.body(Some(&JsValue::from_str(
                             &serde_json::to_string(&$data_name)
-                                .or_else(|_| Err(Error::Serde.js_value()))?
+                                .map_err(|err| ClientError::new(ErrorCode::Serialization, err.to_string()))?
                         )));
                     )?
 
                     let response: Response = JsFuture::from(window.fetch_with_request_and_init(&request, &opts))
-                        .await?
-                        .dyn_into()
-                        .unwrap();
+                        .await
+                        .and_then(JsCast::dyn_into)
+                        .map_err(fetch_error)?;
 
                     if !response.ok() {
-                        return Err(Error::from(&response).js_value());
+                        return Err(request_error(&response).await);
                     }
 
                     // Convert this other `Promise` into a rust `Future`.
-                    let json = JsFuture::from(response.json()?).await?;
+                    let json = JsFuture::from(response.json().map_err(fetch_error)?).await.map_err(fetch_error)?;
 
                     // Use serde to parse the JSON into a struct.
-                    let value: $return_type = json.into_serde().or_else(|_| Err(Error::from(&response).js_value()))?;
+                    let value: $return_type = json
+                        .into_serde()
+                        .map_err(|err| ClientError::new(ErrorCode::Serialization, err.to_string()))?;
 
                     Ok(value)
                 }
@@ -279,6 +295,11 @@ get_delete! {
     Params {},
     Query {},
 
+    "GET", "/api/tilings/v1/account-patches", get_account_patches, getAccountPatches,
+    Vec<models::Patch>,
+    Params {},
+    Query {},
+
     "GET", "/api/tilings/v1/atlas/{}", get_atlas, getAtlas,
     models::FullAtlas,
     Params {
@@ -305,6 +326,27 @@ get_delete! {
     },
     Query {},
 
+    "GET", "/api/tilings/v1/colorscheme/{}", get_color_scheme, getColorScheme,
+    models::ColorScheme,
+    Params {
+        id: i32,
+    },
+    Query {},
+
+    "DELETE", "/api/tilings/v1/colorscheme/{}", delete_color_scheme, deleteColorScheme,
+    usize,
+    Params {
+        id: i32,
+    },
+    Query {},
+
+    "GET", "/api/tilings/v1/colorschemes-by-tiling-id/{}", get_color_schemes_by_tiling_id, getColorSchemesByTilingId,
+    Vec<models::ColorScheme>,
+    Params {
+        tiling_id: i32,
+    },
+    Query {},
+
     "GET", "/api/tilings/v1/check-display-name/{}", check_display_name, checkDisplayName,
     bool,
     Params {
@@ -356,6 +398,20 @@ get_delete! {
         query: String,
     },
 
+    "GET", "/api/tilings/v1/patch/{}", get_patch, getPatch,
+    models::Patch,
+    Params {
+        id: i32,
+    },
+    Query {},
+
+    "DELETE", "/api/tilings/v1/patch/{}", delete_patch, deletePatch,
+    usize,
+    Params {
+        id: i32,
+    },
+    Query {},
+
     "GET", "/api/tilings/v1/polygon/{}", get_polygon, getPolygon,
     models::FullPolygon,
     Params {
@@ -444,12 +500,36 @@ post_patch! {
     Query {},
     Data atlas_post: models::FullAtlasPost,
 
+    "POST", "/api/tilings/v1/atlas/validate", validate_atlas, validateAtlas,
+    models::client::AtlasValidation,
+    Params {},
+    Query {},
+    Data atlas_post: models::FullAtlasPost,
+
+    "POST", "/api/tilings/v1/atlas/vertex-types", suggest_vertex_types, suggestVertexTypes,
+    Vec<atlas::VertexType>,
+    Params {},
+    Query {},
+    Data vertex_types_post: models::VertexTypesPost,
+
     "PATCH", "/api/tilings/v1/atlas", update_atlas, updateAtlas,
     models::FullAtlas,
     Params {},
     Query {},
     Data atlas_post: models::FullAtlasPatch,
 
+    "POST", "/api/tilings/v1/colorscheme", create_color_scheme, createColorScheme,
+    models::ColorScheme,
+    Params {},
+    Query {},
+    Data color_scheme_post: models::ColorSchemePost,
+
+    "PATCH", "/api/tilings/v1/colorscheme", update_color_scheme, updateColorScheme,
+    models::ColorScheme,
+    Params {},
+    Query {},
+    Data color_scheme_patch: models::ColorSchemePatch,
+
     "POST", "/api/tilings/v1/create-polygon", create_polygon, createPolygon,
     models::FullPolygon,
     Params {},
@@ -480,6 +560,18 @@ post_patch! {
     Query {},
     Data,
 
+    "POST", "/api/tilings/v1/patch", create_patch, createPatch,
+    models::Patch,
+    Params {},
+    Query {},
+    Data patch_post: models::PatchPost,
+
+    "PATCH", "/api/tilings/v1/patch", update_patch, updatePatch,
+    models::Patch,
+    Params {},
+    Query {},
+    Data patch_patch: models::PatchPatch,
+
     "POST", "/api/tilings/v1/resend-verification-code-email", resend_verification_code_email, resendVerificationCodeEmail,
     (),
     Params {},
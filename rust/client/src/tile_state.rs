@@ -0,0 +1,45 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+// TileState is the state of an included tile, set from js with setTileState and saved along with
+// it in patch snapshots. Every part of it is optional, a default TileState being a plain tile.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct TileState {
+    // color overrides the tile's color in the global's coloring, as [red, green, blue]
+    pub color: Option<(u8, u8, u8)>,
+    pub label: Option<String>,
+    pub value: Option<f64>,
+}
+
+// TileStateFields are the fields of a serialized TileState, any of which may be missing
+#[derive(Deserialize)]
+struct TileStateFields {
+    color: Option<(u8, u8, u8)>,
+    label: Option<String>,
+    value: Option<f64>,
+}
+
+// a null TileState is a default one, as stored in place of a TileState by patch snapshots
+// saved before tiles had state
+impl<'de> Deserialize<'de> for TileState {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<TileState, D::Error> {
+        Ok(Option::<TileStateFields>::deserialize(deserializer)?
+            .map_or_else(TileState::default, |TileStateFields { color, label, value }| TileState { color, label, value }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_state_serde() {
+        let state = TileState { color: Some((255, 0, 10)), label: Some(String::from("a")), value: Some(1.5) };
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(r#"{"color":[255,0,10],"label":"a","value":1.5}"#, json);
+        assert_eq!(state, serde_json::from_str::<TileState>(&json).unwrap());
+
+        assert_eq!(TileState { value: Some(2.), ..TileState::default() }, serde_json::from_str::<TileState>(r#"{"value":2}"#).unwrap());
+        assert_eq!(TileState::default(), serde_json::from_str::<TileState>("null").unwrap());
+        assert!(serde_json::from_str::<TileState>(r#"{"color":[256,0,0]}"#).is_err());
+    }
+}
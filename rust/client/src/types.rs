@@ -0,0 +1,241 @@
+use crate::TileState;
+use atlas::AtlasError;
+use canvas::TO_CANVAS_AFFINE;
+use geometry::{Point, Transformable};
+use patch::{Patch, PatchError, PatchTile};
+use serde::Serialize;
+use tiling::TilingError;
+use wasm_bindgen::prelude::*;
+
+// typescript definitions of the values passed to and from the exports, which wasm-bindgen would
+// otherwise type as any
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &'static str = r#"
+export type Point = [number, number];
+
+export interface TileState {
+  color?: [number, number, number] | null;
+  label?: string | null;
+  value?: number | null;
+}
+
+export interface TileDescriptor {
+  centroid: Point;
+  points: Point[];
+  polygonIndex: number | null;
+  state: TileState;
+}
+
+export type ErrorCode =
+  | "MissingGlobal"
+  | "MissingAutomaton"
+  | "InvalidArgument"
+  | "Tiling"
+  | "Serialization"
+  | "Request";
+
+export interface ClientError {
+  code: ErrorCode;
+  message: string;
+  details?: any;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "TileDescriptor | null")]
+    pub type TileDescriptorOrNull;
+
+    #[wasm_bindgen(typescript_type = "TileDescriptor[] | null")]
+    pub type TileDescriptorsOrNull;
+
+    #[wasm_bindgen(typescript_type = "TileState | null")]
+    pub type TileStateOrNull;
+
+    #[wasm_bindgen(typescript_type = "Point[][]")]
+    pub type Coronas;
+}
+
+// TileDescriptor describes an included tile to js, with points in canvas coordinates
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TileDescriptor {
+    pub centroid: Point,
+    pub points: Vec<Point>,
+    // polygon_index is the index of the tile's polygon in its atlas
+    #[serde(rename = "polygonIndex")]
+    pub polygon_index: Option<usize>,
+    pub state: TileState,
+}
+
+impl TileDescriptor {
+    // new describes patch_tile, a tile of patch
+    pub fn new(patch: &Patch<TileState>, patch_tile: &PatchTile<TileState>) -> TileDescriptor {
+        TileDescriptor {
+            centroid: patch_tile.tile.centroid.transform(&TO_CANVAS_AFFINE),
+            points: patch_tile.tile.points.iter().map(|point| point.transform(&TO_CANVAS_AFFINE)).collect(),
+            polygon_index: patch.get_polygon_index(&patch_tile.tile),
+            state: patch_tile.state.clone().unwrap_or_default(),
+        }
+    }
+}
+
+// to_canvas_coronas maps the centroids of each corona, as returned by Patch::grow_coronas, to canvas coordinates
+pub fn to_canvas_coronas(coronas: &[Vec<Point>]) -> Vec<Vec<Point>> {
+    coronas
+        .iter()
+        .map(|corona| corona.iter().map(|centroid| centroid.transform(&TO_CANVAS_AFFINE)).collect())
+        .collect()
+}
+
+// ErrorCode is the kind of a ClientError, which js callers can branch on
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum ErrorCode {
+    // there's no global with the given id, e.g. as setTiling hasn't been called for it
+    MissingGlobal,
+    // the global has no automaton, as setAutomaton hasn't been called for it
+    MissingAutomaton,
+    // an argument couldn't be parsed or doesn't fit the global
+    InvalidArgument,
+    // the tiling couldn't be built, changed or drawn, details being the TilingError
+    Tiling,
+    // a value couldn't be (de)serialized
+    Serialization,
+    // a request to the server failed, details being the response and its error body when there is one
+    Request,
+}
+
+// ClientError is thrown by every export which fails
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ClientError {
+    pub code: ErrorCode,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl ClientError {
+    pub fn new(code: ErrorCode, message: String) -> ClientError {
+        ClientError { code, message, details: None }
+    }
+
+    pub fn missing_global(global_id: i32) -> ClientError {
+        ClientError::new(ErrorCode::MissingGlobal, format!("no global found with id {}", global_id))
+    }
+
+    pub fn missing_automaton(global_id: i32) -> ClientError {
+        ClientError::new(ErrorCode::MissingAutomaton, format!("no automaton set for global {}", global_id))
+    }
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<TilingError> for ClientError {
+    fn from(err: TilingError) -> ClientError {
+        ClientError {
+            code: ErrorCode::Tiling,
+            message: err.to_string(),
+            details: serde_json::to_value(&err).ok(),
+        }
+    }
+}
+
+impl From<PatchError> for ClientError {
+    fn from(err: PatchError) -> ClientError {
+        ClientError::from(TilingError::from(err))
+    }
+}
+
+impl From<AtlasError> for ClientError {
+    fn from(err: AtlasError) -> ClientError {
+        ClientError::from(TilingError::from(err))
+    }
+}
+
+impl From<ClientError> for JsValue {
+    fn from(err: ClientError) -> JsValue {
+        JsValue::from_serde(&err).unwrap_or_else(|_| JsValue::from_str(&err.message))
+    }
+}
+
+// to_js serializes value as a T, one of the extern types given a typescript type above
+pub fn to_js<T: JsCast>(value: &impl Serialize) -> Result<T, ClientError> {
+    JsValue::from_serde(value)
+        .map(JsCast::unchecked_into)
+        .map_err(|e| ClientError::new(ErrorCode::Serialization, e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use patch::Patch;
+
+    #[test]
+    fn test_tile_descriptor() {
+        let configuration = atlas::VertexConfiguration::parse("3.4.6.4").unwrap();
+        let polygons = configuration.tiles();
+        let atlas = atlas::Atlas::from_vertex_posts(&polygons, &configuration.to_vertex_posts().unwrap()).unwrap();
+        let tree_config = pmr_quad_tree::Config { initial_radius: 1000., max_depth: 50, splitting_threshold: 25 };
+        let mut patch: Patch<TileState> = Patch::new(atlas, tree_config.clone(), tree_config).unwrap();
+        let state = TileState { label: Some(String::from("a")), ..TileState::default() };
+        patch.fill_bounds(&geometry::Bounds { center: geometry::ORIGIN, radius: 3. }, state.clone()).unwrap();
+
+        let mut sizes = vec![];
+        for patch_tile_rc in patch.patch_tiles.items.values() {
+            let patch_tile = patch_tile_rc.value();
+            let descriptor = TileDescriptor::new(&patch, &patch_tile);
+            assert_eq!(polygons[descriptor.polygon_index.unwrap()].size(), patch_tile.tile.size());
+            assert_eq!(state, descriptor.state);
+            assert_eq!(patch_tile.tile.centroid.transform(&TO_CANVAS_AFFINE), descriptor.centroid);
+            assert_eq!(canvas::from_canvas_point(descriptor.points[2].0, descriptor.points[2].1), patch_tile.tile.points[2]);
+            sizes.push(patch_tile.tile.size());
+
+            let json = serde_json::to_value(&descriptor).unwrap();
+            assert_eq!(Some(&serde_json::json!(descriptor.polygon_index.unwrap())), json.get("polygonIndex"));
+            assert_eq!(patch_tile.tile.size(), json["points"].as_array().unwrap().len());
+        }
+        sizes.sort();
+        sizes.dedup();
+        assert_eq!(vec![3, 4, 6], sizes);
+    }
+
+    #[test]
+    fn test_to_canvas_coronas() {
+        let configuration = atlas::VertexConfiguration::parse("4^4").unwrap();
+        let atlas = atlas::Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().unwrap()).unwrap();
+        let tree_config = pmr_quad_tree::Config { initial_radius: 1000., max_depth: 50, splitting_threshold: 25 };
+        let mut patch: Patch<TileState> = Patch::new(atlas, tree_config.clone(), tree_config).unwrap();
+        let coronas = to_canvas_coronas(&patch.grow_coronas(&Point(0.5, 0.5), 2, TileState::default()).unwrap());
+
+        // the coronas of a square grow the patch to 1, 9 and then 25 squares
+        assert_eq!(vec![1, 8, 16], coronas.iter().map(Vec::len).collect::<Vec<usize>>());
+        assert_eq!(vec![Point(0.5, 0.5).transform(&TO_CANVAS_AFFINE)], coronas[0]);
+        for centroid in coronas.iter().flatten() {
+            let Point(x, y) = *centroid;
+            assert!(patch.get_included_patch_tile_by_point(&canvas::from_canvas_point(x, y)).is_some());
+        }
+    }
+
+    #[test]
+    fn test_client_error() {
+        let err = ClientError::from(TilingError::from(PatchError::MissingTile { centroid: Point(1., 2.) }));
+        assert_eq!(ErrorCode::Tiling, err.code);
+        assert_eq!(
+            serde_json::json!({
+                "code": "Tiling",
+                "message": "no tile found at centroid (1.00,2.00)",
+                "details": { "kind": "MissingTile", "centroid": [1., 2.] },
+            }),
+            serde_json::to_value(&err).unwrap(),
+        );
+        assert_eq!(
+            serde_json::json!({ "code": "MissingGlobal", "message": "no global found with id 3" }),
+            serde_json::to_value(&ClientError::missing_global(3)).unwrap(),
+        );
+    }
+}
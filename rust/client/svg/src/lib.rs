@@ -0,0 +1,204 @@
+use canvas::SCALE;
+use geometry::{Bounds, Point, Spatial};
+use patch::{Patch, PatchTile};
+use plotters::style::{BLACK, RGBColor};
+use std::{cell::Ref, cmp::Ordering};
+use tile::Tile;
+
+pub struct Stroke {
+    pub color: RGBColor,
+    pub width: f64, // in svg units
+}
+
+pub struct Config {
+    pub bounds: Option<Bounds>, // viewport in patch coordinates, fit to the included Tiles if None
+    pub scale: f64, // svg units per patch unit
+    pub stroke: Option<Stroke>,
+    pub background: Option<RGBColor>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            bounds: None,
+            scale: SCALE,
+            stroke: Some(Stroke { color: BLACK, width: 1. }),
+            background: None,
+        }
+    }
+}
+
+// to_svg renders each included Tile in patch which falls within the viewport as an svg polygon filled with
+// the color get_color returns for it, which is returned by value so that it can be computed per Tile.
+// Tiles are written in order of their centroids so that equal Patches produce equal svgs.
+pub fn to_svg<State>(patch: &Patch<State>, config: &Config, get_color: impl Fn(&PatchTile<State>) -> RGBColor) -> Result<String, String> {
+    let mut patch_tiles: Vec<Ref<PatchTile<State>>> = patch.patch_tiles.items
+        .values()
+        .map(|patch_tile_rc| patch_tile_rc.value())
+        .filter(|patch_tile| patch_tile.state.is_some())
+        .collect();
+
+    let bounds = match &config.bounds {
+        Some(bounds) => bounds.clone(),
+        None => get_fitted_bounds(patch_tiles.iter().map(|patch_tile| &patch_tile.tile))
+            .ok_or_else(|| String::from("can't fit svg viewport to a patch without included tiles"))?,
+    };
+
+    patch_tiles.retain(|patch_tile| is_in_viewport(&patch_tile.tile, &bounds));
+    patch_tiles.sort_by(|a, b| cmp_points(&a.tile.centroid, &b.tile.centroid));
+
+    Ok(tiles_to_svg(
+        patch_tiles.iter().map(|patch_tile| (&patch_tile.tile, get_color(&**patch_tile))),
+        &bounds,
+        config,
+    ))
+}
+
+// tiles_to_svg writes an svg document containing each of tiles, filled with its paired color, which is
+// owned rather than borrowed as colors computed per Tile have nowhere to be borrowed from.
+// Patch coordinates are scaled by config.scale and flipped vertically so that y points up.
+pub fn tiles_to_svg<'a>(tiles: impl IntoIterator<Item = (&'a Tile, RGBColor)>, bounds: &Bounds, config: &Config) -> String {
+    let size = format_number(2. * bounds.radius * config.scale);
+    let min = to_svg_point(&Point(bounds.center.0 - bounds.radius, bounds.center.1 + bounds.radius), config.scale);
+
+    let mut lines = vec![format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+        size, size, min.0, min.1, size, size,
+    )];
+
+    if let Some(background) = &config.background {
+        lines.push(format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            min.0, min.1, size, size, format_color(background),
+        ));
+    }
+
+    lines.push(match &config.stroke {
+        None => String::from(r#"<g stroke="none">"#),
+        Some(stroke) => format!(
+            r#"<g stroke="{}" stroke-width="{}" stroke-linejoin="round">"#,
+            format_color(&stroke.color),
+            format_number(stroke.width),
+        ),
+    });
+
+    for (tile, color) in tiles {
+        let points = tile.points
+            .iter()
+            .map(|point| {
+                let svg_point = to_svg_point(point, config.scale);
+                format!("{},{}", svg_point.0, svg_point.1)
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+        lines.push(format!(r#"<polygon points="{}" fill="{}"/>"#, points, format_color(&color)));
+    }
+
+    lines.push(String::from("</g>"));
+    lines.push(String::from("</svg>"));
+    lines.push(String::from(""));
+
+    lines.join("\n")
+}
+
+// get_fitted_bounds returns the smallest Bounds centered on tiles which contains all of their points
+pub fn get_fitted_bounds<'a>(tiles: impl IntoIterator<Item = &'a Tile>) -> Option<Bounds> {
+    let mut min: Option<Point> = None;
+    let mut max: Option<Point> = None;
+    for point in tiles.into_iter().flat_map(|tile| tile.points.iter()) {
+        min = Some(match min { None => *point, Some(min) => Point(min.0.min(point.0), min.1.min(point.1)) });
+        max = Some(match max { None => *point, Some(max) => Point(max.0.max(point.0), max.1.max(point.1)) });
+    }
+    let (min, max) = match (min, max) { (Some(min), Some(max)) => (min, max), _ => return None };
+    Some(Bounds {
+        center: Point((min.0 + max.0) / 2., (min.1 + max.1) / 2.),
+        radius: ((max.0 - min.0) / 2.).max((max.1 - min.1) / 2.),
+    })
+}
+
+fn is_in_viewport(tile: &Tile, bounds: &Bounds) -> bool {
+    bounds.distance(&tile.centroid) == 0. || tile.intersects(bounds) || tile.contains(&bounds.center)
+}
+
+fn cmp_points(a: &Point, b: &Point) -> Ordering {
+    a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal)
+        .then(a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+}
+
+fn to_svg_point(point: &Point, scale: f64) -> (String, String) {
+    (format_number(point.0 * scale), format_number(-point.1 * scale))
+}
+
+fn format_color(color: &RGBColor) -> String {
+    format!("rgb({},{},{})", color.0, color.1, color.2)
+}
+
+// format_number writes n with at most 3 decimal places and without trailing zeros
+fn format_number(n: f64) -> String {
+    let formatted = format!("{:.3}", n);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" { String::from("0") } else { String::from(trimmed) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!("0", format_number(0.));
+        assert_eq!("0", format_number(-0.0001));
+        assert_eq!("30", format_number(30.));
+        assert_eq!("-12.5", format_number(-12.5));
+        assert_eq!("0.866", format_number(3_f64.sqrt() / 2.));
+    }
+
+    #[test]
+    fn test_tiles_to_svg() {
+        let tiles = [
+            Tile::new(vec![Point(0., 0.), Point(1., 0.), Point(1., 1.), Point(0., 1.)]),
+            Tile::new(vec![Point(1., 0.), Point(2., 0.), Point(2., 1.), Point(1., 1.)]),
+        ];
+        let red = RGBColor(255, 0, 0);
+        let config = Config {
+            scale: 10.,
+            background: Some(RGBColor(255, 255, 255)),
+            ..Config::default()
+        };
+
+        let bounds = get_fitted_bounds(tiles.iter()).unwrap();
+        assert_eq!(Point(1., 0.5), bounds.center);
+        assert_eq!(1., bounds.radius);
+
+        assert_eq!(
+            [
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20" viewBox="0 -15 20 20">"#,
+                r#"<rect x="0" y="-15" width="20" height="20" fill="rgb(255,255,255)"/>"#,
+                r#"<g stroke="rgb(0,0,0)" stroke-width="1" stroke-linejoin="round">"#,
+                r#"<polygon points="0,0 10,0 10,-10 0,-10" fill="rgb(255,0,0)"/>"#,
+                r#"<polygon points="10,0 20,0 20,-10 10,-10" fill="rgb(0,0,0)"/>"#,
+                "</g>",
+                "</svg>",
+                "",
+            ].join("\n"),
+            tiles_to_svg(vec![(&tiles[0], red), (&tiles[1], BLACK)], &bounds, &config),
+        );
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let tree_config = pmr_quad_tree::Config { initial_radius: 1000., max_depth: 50, splitting_threshold: 25 };
+        let configuration = atlas::VertexConfiguration::parse("4^4").unwrap();
+        let atlas = atlas::Atlas::from_vertex_posts(&configuration.tiles(), &configuration.to_vertex_posts().unwrap()).unwrap();
+        let mut patch: Patch<()> = Patch::new(atlas, tree_config.clone(), tree_config).unwrap();
+        let config = Config { stroke: None, ..Config::default() };
+        assert!(to_svg(&patch, &config, |_| BLACK).is_err());
+
+        // colors are computed per tile, here from each tile's centroid
+        patch.insert_tile_by_point(Point(0.5, 0.5), Some(())).unwrap();
+        patch.insert_tile_by_point(Point(1.5, 0.5), Some(())).unwrap();
+        let svg = to_svg(&patch, &config, |patch_tile| RGBColor((patch_tile.tile.centroid.0 * 100.) as u8, 0, 0)).unwrap();
+        let fills = svg.lines().filter(|line| line.starts_with("<polygon")).map(|line| line.split("fill=").nth(1).unwrap()).collect::<Vec<&str>>();
+        assert_eq!(vec![r#""rgb(50,0,0)"/>"#, r#""rgb(150,0,0)"/>"#], fills);
+    }
+}
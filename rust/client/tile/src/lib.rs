@@ -3,6 +3,9 @@ use geometry::{reduce_transforms, Bounds, Edge, Euclid, Generator, Point, Spatia
 use itertools::{interleave, Itertools, izip};
 use std::{f64::consts::{PI, TAU}, hash::Hash, iter};
 
+// fraction of the distance to a Tile's centroid by which its points are nudged inwards in Tile::overlaps
+const NUDGE_RATIO: f64 = 0.000_1;
+
 #[derive(Clone, Debug)]
 pub struct Tile {
     pub points: Vec<Point>,
@@ -154,6 +157,23 @@ impl Tile {
         Point::edges(&self.points)
     }
 
+    // overlaps determines whether or not the interiors of this tile and other intersect. Tiles which
+    // only share boundary points, like neighbors in a tiling, don't overlap. Each point is nudged
+    // towards its tile's centroid before testing containment so that shared points are disregarded.
+    pub fn overlaps(&self, other: &Tile) -> bool {
+        if self.contains(&other.centroid) || other.contains(&self.centroid) {
+            return true
+        }
+        if self.points.iter().any(|point| other.contains(&Tile::nudge(point, &self.centroid))) {
+            return true
+        }
+        if other.points.iter().any(|point| self.contains(&Tile::nudge(point, &other.centroid))) {
+            return true
+        }
+        let other_edges = other.edges();
+        self.edges().iter().any(|edge| other_edges.iter().any(|other_edge| edge.crosses(other_edge)))
+    }
+
     // reorient_about_origin shifts the underlying points of a Tile so that the first
     // point is the closest to the origin
     pub fn reorient(&mut self, origin: &Point) {
@@ -174,6 +194,11 @@ impl Tile {
         self.points.len()
     }
 
+    // nudge moves point a small step towards centroid
+    fn nudge(point: &Point, centroid: &Point) -> Point {
+        point + &(centroid - point).mul(NUDGE_RATIO)
+    }
+
     // centroid computes the centroid of the provided points
     fn centroid(points: &Vec<Point>) -> Point {
         // calc area
@@ -358,6 +383,30 @@ mod tests {
         assert_eq!(&Point(0., 0.), edge.1);
     }
 
+    #[test]
+    fn test_tile_overlaps() {
+        let square = regular_polygon(1., 4);
+        let translate = |x: f64, y: f64| square.transform(&Euclid::Translate((x, y)));
+
+        assert!(square.overlaps(&square));
+        assert!(square.overlaps(&translate(0.5, 0.5)));
+        assert!(square.overlaps(&translate(0.9, 0.)));
+        assert!(!square.overlaps(&translate(1., 0.)));
+        assert!(!square.overlaps(&translate(1., 1.)));
+        assert!(!square.overlaps(&translate(1.5, 0.5)));
+
+        // neither contains the other's centroid or points but their edges cross
+        let wide = Tile::new(vec![Point(0., 0.), Point(4., 0.), Point(4., 1.), Point(0., 1.)]);
+        let tall = Tile::new(vec![Point(0.5, -5.), Point(1., -5.), Point(1., 2.), Point(0.5, 2.)]);
+        assert!(wide.overlaps(&tall));
+        assert!(tall.overlaps(&wide));
+
+        // a tile entirely within another
+        let small = Tile::new(vec![Point(0.4, 0.4), Point(0.6, 0.4), Point(0.6, 0.6), Point(0.4, 0.6)]);
+        assert!(square.overlaps(&small));
+        assert!(small.overlaps(&square));
+    }
+
     #[test]
     fn test_regular_polygon() {
         for num_sides in 3..100 {
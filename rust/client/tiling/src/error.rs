@@ -0,0 +1,49 @@
+use atlas::AtlasError;
+use patch::PatchError;
+use serde::Serialize;
+use wasm_bindgen::JsValue;
+
+// TilingError serializes as the underlying error so that its kind is visible to js callers
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TilingError {
+    Atlas(AtlasError),
+    Patch(PatchError),
+    Canvas { message: String },
+}
+
+impl std::fmt::Display for TilingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TilingError::Atlas(err) => write!(f, "{}", err),
+            TilingError::Patch(err) => write!(f, "{}", err),
+            TilingError::Canvas { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for TilingError {}
+
+impl From<AtlasError> for TilingError {
+    fn from(err: AtlasError) -> TilingError {
+        TilingError::Atlas(err)
+    }
+}
+
+impl From<PatchError> for TilingError {
+    fn from(err: PatchError) -> TilingError {
+        TilingError::Patch(err)
+    }
+}
+
+impl From<String> for TilingError {
+    fn from(message: String) -> TilingError {
+        TilingError::Canvas { message }
+    }
+}
+
+impl From<TilingError> for JsValue {
+    fn from(err: TilingError) -> JsValue {
+        JsValue::from_serde(&err).unwrap_or_else(|_| JsValue::from_str(&err.to_string()))
+    }
+}
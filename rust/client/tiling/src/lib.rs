@@ -1,18 +1,23 @@
+mod error;
+
+pub use self::error::*;
+
 use atlas::Atlas;
 use canvas::{Canvas, SCALE, TO_CANVAS_AFFINE};
-use console::log;
 use geometry::*;
-use patch::{Patch, PatchTile, TileDiff};
+use patch::{Patch, PatchSnapshot, PatchTile, TileDiff};
 use plotters::style::RGBColor;
 use plotters_canvas::CanvasBackend;
 use pmr_quad_tree::Config as TreeConfig;
 use std::collections::HashMap;
-use web_sys::HtmlCanvasElement;
+use tile::Tile;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
 use wasm_bindgen::{JsCast, prelude::*};
 
 pub struct Tiling<State> {
     pub id: i32,
     pub canvases: HashMap<Point, Canvas<CanvasBackend>>,
+    canvas_elements: HashMap<Point, HtmlCanvasElement>,
     pub canvas_radius: f64,
     pub patch: Patch<State>,
     neighbor_centroid_diffs: Vec<Point>,
@@ -27,8 +32,20 @@ pub struct Config {
 }
 
 impl<State> Tiling<State> {
-    pub fn new(config: Config) -> Result<Tiling<State>, String> {
-        let canvas_diameter = 2. * config.canvas_radius;
+    pub fn new(config: Config) -> Result<Tiling<State>, TilingError> {
+        let patch = Patch::new(config.atlas, config.tile_tree_config, config.vertex_star_tree_config)?;
+        Ok(Tiling::from_patch(config.id, config.canvas_radius, patch))
+    }
+
+    // from_snapshot creates a Tiling whose patch is restored from snapshot.
+    // The restored tiles are drawn on the next call to draw_tile_diffs.
+    pub fn from_snapshot(config: Config, snapshot: PatchSnapshot<State>) -> Result<Tiling<State>, TilingError> {
+        let patch = Patch::from_snapshot(snapshot, config.atlas, config.tile_tree_config, config.vertex_star_tree_config)?;
+        Ok(Tiling::from_patch(config.id, config.canvas_radius, patch))
+    }
+
+    fn from_patch(id: i32, canvas_radius: f64, patch: Patch<State>) -> Tiling<State> {
+        let canvas_diameter = 2. * canvas_radius;
         let neighbor_centroid_diffs = vec![
             Point(canvas_diameter, 0.),
             Point(canvas_diameter, canvas_diameter),
@@ -40,50 +57,131 @@ impl<State> Tiling<State> {
             Point(canvas_diameter, -canvas_diameter),
         ];
 
-        Ok(Tiling {
+        Tiling {
+            id,
+            canvas_radius,
             neighbor_centroid_diffs,
-            id: config.id,
-            canvas_radius: config.canvas_radius,
+            patch,
             canvases: HashMap::default(),
-            patch: Patch::new(config.atlas, config.tile_tree_config, config.vertex_star_tree_config)?,
-        })
+            canvas_elements: HashMap::default(),
+        }
     }
 
-    pub fn insert_tile_by_point(&mut self, point: Point, state: Option<State>, get_color: impl Fn(&PatchTile<State>) -> &RGBColor) -> Result<(), String> {
-        let centroid = Point(self.center(point.0), self.center(point.1));
+    pub fn insert_tile_by_point(&mut self, point: Point, state: Option<State>, get_color: impl Fn(&PatchTile<State>) -> RGBColor) -> Result<(), TilingError> {
+        let result = self.patch.insert_tile_by_point(point, state);
+        self.draw_tile_diffs(get_color)?;
+        Ok(result?)
+    }
 
-        let neighbor_centroids = self.neighbor_centroids(&centroid);
+    pub fn remove_tile_by_point(&mut self, point: Point, get_color: impl Fn(&PatchTile<State>) -> RGBColor) -> Result<(), TilingError> {
+        let result = self.patch.remove_tile_by_point(point);
+        self.draw_tile_diffs(get_color)?;
+        Ok(result?)
+    }
 
-        if let Err(e) = self.patch.insert_tile_by_point(point, state) {
-            log!(e);
-        }
+    // set_tile_state replaces the state of the included tile containing point and redraws it
+    pub fn set_tile_state(&mut self, point: Point, state: State, get_color: impl Fn(&PatchTile<State>) -> RGBColor) -> Result<(), TilingError> {
+        let result = self.patch.set_tile_state(&point, state);
+        self.draw_tile_diffs(get_color)?;
+        Ok(result?)
+    }
+
+    // fill_bounds includes and draws every tile overlapping bounds
+    pub fn fill_bounds(&mut self, bounds: &Bounds, state: State, get_color: impl Fn(&PatchTile<State>) -> RGBColor) -> Result<(), TilingError> where State: Clone {
+        let result = self.patch.fill_bounds_periodic(bounds, state);
+        self.draw_tile_diffs(&get_color)?;
+        Ok(self.draw(result?, get_color)?)
+    }
+
+    // grow_coronas includes and draws the tile containing point along with its first k coronas, returning the
+    // centroids of the tiles in each corona, see Patch::grow_coronas
+    pub fn grow_coronas(&mut self, point: Point, k: usize, state: State, get_color: impl Fn(&PatchTile<State>) -> RGBColor) -> Result<Vec<Vec<Point>>, TilingError> where State: Clone {
+        let result = self.patch.grow_coronas(&point, k, state);
+        self.draw_tile_diffs(get_color)?;
+        Ok(result?)
+    }
+
+    pub fn undo(&mut self, get_color: impl Fn(&PatchTile<State>) -> RGBColor) -> Result<(), TilingError> {
+        let result = self.patch.undo();
+        self.draw_tile_diffs(get_color)?;
+        Ok(result?)
+    }
+
+    pub fn redo(&mut self, get_color: impl Fn(&PatchTile<State>) -> RGBColor) -> Result<(), TilingError> {
+        let result = self.patch.redo();
+        self.draw_tile_diffs(get_color)?;
+        Ok(result?)
+    }
+
+    // draw_tile_diffs drains the patch's tile diffs and draws them onto the canvases around each tile.
+    pub fn draw_tile_diffs(&mut self, get_color: impl Fn(&PatchTile<State>) -> RGBColor) -> Result<(), TilingError> {
         let tile_diffs = self.patch.drain_tile_diffs();
+        Ok(self.draw(tile_diffs, get_color)?)
+    }
+
+    // draw fills in Added and Changed tiles directly, whereas canvases touched by a Removed tile are
+    // cleared and redrawn from the tiles remaining in the patch.
+    fn draw(&mut self, tile_diffs: Vec<(Point, TileDiff<State>)>, get_color: impl Fn(&PatchTile<State>) -> RGBColor) -> Result<(), String> {
+        let mut bounds_to_redraw: Vec<Bounds> = vec![];
 
-        if tile_diffs.len() > 0 {
-            let all_bounds = std::iter::once(centroid)
-                .chain(neighbor_centroids.into_iter())
-                .map(|neighbor_centroid| Bounds { center: neighbor_centroid, radius: self.canvas_radius })
-                .collect::<Vec<Bounds>>();
-
-            for (_, tile_diff) in tile_diffs.into_iter() {
-                match tile_diff {
-                    TileDiff::Added(patch_tile_weak_item) => {
-                        if let Some(patch_tile_rc_item) = patch_tile_weak_item.upgrade() {
-                            let patch_tile = patch_tile_rc_item.value();
-                            let color = get_color(&patch_tile).clone();
-                            let edges = patch_tile.tile.edges();
-                            let tile = patch_tile.tile.clone();
-                            self.canvas_op(&all_bounds, edges.iter().collect(), Box::new(|canvas| canvas.fill_tile(&tile, &color)))?;
+        for (_, tile_diff) in tile_diffs.into_iter() {
+            match tile_diff {
+                TileDiff::Added(patch_tile_weak_item) | TileDiff::Changed(patch_tile_weak_item) => {
+                    if let Some(patch_tile_rc_item) = patch_tile_weak_item.upgrade() {
+                        let patch_tile = patch_tile_rc_item.value();
+                        let all_bounds = self.all_bounds(&patch_tile.tile.centroid);
+                        let color = get_color(&patch_tile);
+                        let edges = patch_tile.tile.edges();
+                        let tile = patch_tile.tile.clone();
+                        self.canvas_op(&all_bounds, edges.iter().collect(), Box::new(|canvas| canvas.fill_tile(&tile, &color)))?;
+                    }
+                },
+                TileDiff::Removed(patch_tile_weak_item) => {
+                    if let Some(patch_tile_rc_item) = patch_tile_weak_item.upgrade() {
+                        let patch_tile = patch_tile_rc_item.value();
+                        for bounds in self.all_bounds(&patch_tile.tile.centroid).into_iter() {
+                            if patch_tile.tile.intersects(&bounds) && !bounds_to_redraw.iter().any(|b| b.center == bounds.center) {
+                                bounds_to_redraw.push(bounds);
+                            }
                         }
-                    },
-                    _ => {},
-                }
+                    }
+                },
             }
         }
 
+        for bounds in bounds_to_redraw.iter() {
+            self.redraw_canvas(bounds, &get_color)?;
+        }
+
         Ok(())
     }
 
+    // redraw_canvas clears the existing canvas at bounds and refills every included tile intersecting it
+    fn redraw_canvas(&mut self, bounds: &Bounds, get_color: &impl Fn(&PatchTile<State>) -> RGBColor) -> Result<(), String> {
+        let html_canvas_element = match self.canvas_elements.get(&bounds.center) { None => return Ok(()), Some(element) => element };
+        clear_canvas(html_canvas_element)?;
+
+        let colored_tiles = self.patch.patch_tiles
+            .intersecting(bounds)
+            .into_iter()
+            .filter_map(|patch_tile_rc_item| {
+                let patch_tile = patch_tile_rc_item.value();
+                match &patch_tile.state {
+                    None => None,
+                    Some(_) => Some((patch_tile.tile.clone(), get_color(&patch_tile))),
+                }
+            })
+            .collect::<Vec<(Tile, RGBColor)>>();
+
+        let op: Box<dyn Fn(&mut Canvas<CanvasBackend>) -> Result<(), String>> = Box::new(move |canvas| {
+            for (tile, color) in colored_tiles.iter() {
+                canvas.fill_tile(tile, color)?;
+            }
+            Ok(())
+        });
+        self.operate_on_canvas(&bounds.center, &op)
+    }
+
     fn center(&self, distance: f64) -> f64 {
         (2. * (distance / (2. * self.canvas_radius)).ceil() - 1.) * self.canvas_radius
     }
@@ -110,6 +208,8 @@ impl<State> Tiling<State> {
             None => return Err(String::from("could not get new HtmlCanvasElement")),
         };
 
+        self.canvas_elements.insert(canvas_centroid.clone(), html_canvas_element.clone());
+
         let canvas = self.canvases
             .entry(canvas_centroid.clone())
             .or_insert_with(|| {
@@ -127,6 +227,15 @@ impl<State> Tiling<State> {
         op(canvas)
     }
 
+    // all_bounds returns the bounds of the canvas containing point along with those of its neighboring canvases
+    fn all_bounds(&self, point: &Point) -> Vec<Bounds> {
+        let centroid = Point(self.center(point.0), self.center(point.1));
+        std::iter::once(centroid)
+            .chain(self.neighbor_centroids(&centroid).into_iter())
+            .map(|neighbor_centroid| Bounds { center: neighbor_centroid, radius: self.canvas_radius })
+            .collect()
+    }
+
     fn neighbor_centroids(&self, centroid: &Point) -> Vec<Point> {
         self.neighbor_centroid_diffs
             .iter()
@@ -146,3 +255,20 @@ fn get_new_canvas(global_id: &i32, canvas_radius: &f64, center: &Point) -> Optio
         .dyn_into::<HtmlCanvasElement>()
         .ok()
 }
+
+// clear_canvas erases everything drawn on html_canvas_element, ignoring the translation
+// applied to its context by getNewCanvas
+fn clear_canvas(html_canvas_element: &HtmlCanvasElement) -> Result<(), String> {
+    let context = html_canvas_element
+        .get_context("2d")
+        .ok()
+        .flatten()
+        .and_then(|context| context.dyn_into::<CanvasRenderingContext2d>().ok())
+        .ok_or_else(|| String::from("could not get 2d context of canvas"))?;
+
+    context.save();
+    context.set_transform(1., 0., 0., 1., 0., 0.).map_err(|_| String::from("could not reset canvas transform"))?;
+    context.clear_rect(0., 0., html_canvas_element.width() as f64, html_canvas_element.height() as f64);
+    context.restore();
+    Ok(())
+}
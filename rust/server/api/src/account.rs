@@ -72,6 +72,11 @@ pub async fn get_account(db: DbConn, auth_account: AuthAccount) -> Result<Json<A
     db.run(move |conn| Account::find(auth_account.id, conn)).await.map(Json)
 }
 
+#[get("/v1/account-patches")]
+pub async fn get_account_patches(db: DbConn, auth_account: AuthAccount) -> Result<Json<Vec<Patch>>> {
+    db.run(move |conn| queries::get_account_patches(auth_account, conn)).await.map(Json)
+}
+
 #[get("/v1/account-tilings")]
 pub async fn get_account_tilings(db: DbConn, auth_account: AuthAccount) -> Result<Json<Vec<FullTiling>>> {
     db.run(move |conn| queries::get_account_tilings(auth_account, conn)).await.map(Json)
@@ -1,11 +1,80 @@
 use crate::common::*;
 use auth::AuthAccount;
 use db_conn::DbConn;
+use diesel::PgConnection;
 use models::*;
-use result::Result;
-use rocket::serde::json::Json;
+use result::{Error, Result};
+use rocket::{http::Status, serde::json::Json};
 
 const BATCH_LIMIT: u32 = 1000;
+const VERTEX_TYPE_LIMIT: u32 = 1000;
+const VERTEX_TYPE_MAX_STEPS: usize = 1_000_000;
+
+// canonical_key identifies an atlas' tiling, see atlas::canonical_key
+fn canonical_key(full_atlas: &FullAtlas) -> Result<String> {
+    let atlas = client::Atlas::new(full_atlas)?;
+    ::atlas::canonical_key(&atlas).map_err(|err| Error::from_kind(Status::BadRequest, &err))
+}
+
+// backfill_canonical_keys keys the atlases stored before their tilings were keyed, returning the ids of those left
+// unkeyed because another atlas of the same tiling already is, or because their tiling can't be keyed. Those are
+// recorded as unkeyed so that they aren't tried again.
+pub fn backfill_canonical_keys(conn: &PgConnection) -> Result<Vec<i32>> {
+    let mut unkeyed_ids = vec![];
+    for id in Atlas::find_unkeyed_ids(conn)? {
+        let keyed = FullAtlas::find(id, conn)
+            .and_then(|full_atlas| canonical_key(&full_atlas))
+            .and_then(|canonical_key| Atlas::set_canonical_key(id, canonical_key, conn));
+        match keyed {
+            Ok(()) => {},
+            Err(Error::Structured(status, body)) if status == Status::Conflict || status == Status::BadRequest => {
+                Atlas::set_unkeyed(id, body.to_string(), conn)?;
+                unkeyed_ids.push(id);
+            },
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(unkeyed_ids)
+}
+
+// backfill_wallpaper_groups stores the wallpaper groups of the tilings of atlases stored before groups were, returning
+// the ids of the atlases whose tilings' groups can't be found. Those tilings are recorded as ungrouped so that they
+// aren't tried again.
+pub fn backfill_wallpaper_groups(conn: &PgConnection) -> Result<Vec<i32>> {
+    let mut ungrouped_ids = vec![];
+    for id in Atlas::find_ungrouped_ids(conn)? {
+        let full_atlas = FullAtlas::find(id, conn)?;
+        let wallpaper_group = match wallpaper_group(&full_atlas) {
+            Ok(wallpaper_group) => Ok(wallpaper_group),
+            Err(Error::Structured(status, body)) if status == Status::BadRequest => {
+                ungrouped_ids.push(id);
+                Err(body.to_string())
+            },
+            Err(err) => return Err(err),
+        };
+        Tiling::set_wallpaper_group(full_atlas.tiling.tiling.id, wallpaper_group, conn)?;
+    }
+    Ok(ungrouped_ids)
+}
+
+// wallpaper_group names the symmetry group of an atlas' tiling, see atlas::symmetry
+fn wallpaper_group(full_atlas: &FullAtlas) -> Result<String> {
+    let atlas = client::Atlas::new(full_atlas)?;
+    ::atlas::symmetry(&atlas)
+        .map(|symmetry| symmetry.group.to_string())
+        .map_err(|err| Error::from_kind(Status::BadRequest, &err))
+}
+
+// set_wallpaper_group stores the wallpaper group of an atlas' tiling. An atlas whose group can't be found is still
+// stored, with its tiling left ungrouped and the failure logged, since the group only labels the tiling.
+fn set_wallpaper_group(full_atlas: &mut FullAtlas, conn: &PgConnection) -> Result<()> {
+    let wallpaper_group = wallpaper_group(full_atlas).map_err(|err| {
+        error!("Failed to find the wallpaper group of atlas {}: {}", full_atlas.id, err);
+        err.to_string()
+    });
+    full_atlas.tiling = Tiling::set_wallpaper_group(full_atlas.tiling.tiling.id, wallpaper_group, conn)?;
+    Ok(())
+}
 
 #[get("/v1/atlas/<id>")]
 pub async fn get_atlas(id: i32, db: DbConn) -> Result<Json<FullAtlas>> {
@@ -29,15 +98,40 @@ pub async fn create_atlas(mut full_atlas_post: FullAtlasPost, mut auth_account:
     db.run(move |conn| conn.build_transaction().run(|| {
         auth_account.allowed(&ALLOWED_EDITOR_ROLES, conn)?;
         full_atlas_post.owner_id = Some(auth_account.id);
-        full_atlas_post.insert(conn)
+        let mut full_atlas = full_atlas_post.insert(conn)?;
+        Atlas::set_canonical_key(full_atlas.id, canonical_key(&full_atlas)?, conn)?;
+        set_wallpaper_group(&mut full_atlas, conn)?;
+        Ok(full_atlas)
     })).await.map(Json)
 }
 
+// validate_atlas is a dry run of create_atlas which reports every issue with the atlas
+#[post("/v1/atlas/validate", data = "<full_atlas_post>")]
+pub async fn validate_atlas(full_atlas_post: FullAtlasPost, db: DbConn) -> Result<Json<client::AtlasValidation>> {
+    db.run(move |conn| full_atlas_post.validate(conn)).await.map(Json)
+}
+
+// suggest_vertex_types lists the vertex types which can be made from corners of the given polygons,
+// so that an atlas' vertex stars can be picked from them rather than found to be invalid on save.
+// Listing them is costly, so it's limited to editors and gives up after VERTEX_TYPE_MAX_STEPS steps.
+#[post("/v1/atlas/vertex-types", data = "<vertex_types_post>")]
+pub async fn suggest_vertex_types(vertex_types_post: VertexTypesPost, mut auth_account: AuthAccount, db: DbConn) -> Result<Json<Vec<::atlas::VertexType>>> {
+    db.run(move |conn| {
+        auth_account.allowed(&ALLOWED_EDITOR_ROLES, conn)?;
+        let polygons = vertex_types_post.polygons(conn)?;
+        ::atlas::vertex_types(&polygons, clamp_optional(VERTEX_TYPE_LIMIT, vertex_types_post.limit) as usize, VERTEX_TYPE_MAX_STEPS)
+            .map_err(|err| Error::from_kind(Status::BadRequest, &err))
+    }).await.map(Json)
+}
+
 #[patch("/v1/atlas", data = "<full_atlas_patch>")]
 pub async fn update_atlas(full_atlas_patch: FullAtlasPatch, mut auth_account: AuthAccount, db: DbConn) -> Result<Json<FullAtlas>> {
     db.run(move |conn| conn.build_transaction().run(|| {
         auth_account.can_edit(Owned::Atlas, full_atlas_patch.id, conn)?;
-        full_atlas_patch.update(conn)
+        let mut full_atlas = full_atlas_patch.update(conn)?;
+        Atlas::set_canonical_key(full_atlas.id, canonical_key(&full_atlas)?, conn)?;
+        set_wallpaper_group(&mut full_atlas, conn)?;
+        Ok(full_atlas)
     })).await.map(Json)
 }
 
@@ -0,0 +1,57 @@
+use auth::AuthAccount;
+use db_conn::DbConn;
+use models::*;
+use result::{Error, Result};
+use rocket::{http::Status, serde::json::Json};
+
+// validate_data checks that a color scheme's data is a serialized ColorSchemeData
+fn validate_data(data: std::result::Result<ColorSchemeData, serde_json::Error>) -> Result<()> {
+    data
+        .map(|_| ())
+        .map_err(|err| Error::Custom(Status::BadRequest, format!("invalid color scheme data: {}", err)))
+}
+
+// a color scheme can only be added to a tiling its author can edit
+#[post("/v1/colorscheme", data = "<color_scheme_post>")]
+pub async fn create_color_scheme(mut color_scheme_post: ColorSchemePost, mut auth_account: AuthAccount, db: DbConn) -> Result<Json<ColorScheme>> {
+    db.run(move |conn| conn.build_transaction().run(|| {
+        auth_account.can_edit(Owned::Tiling, color_scheme_post.tiling_id, conn)?;
+        validate_data(color_scheme_post.parse_data())?;
+        color_scheme_post.owner_id = Some(auth_account.id);
+        color_scheme_post.insert(conn)
+    })).await.map(Json)
+}
+
+#[get("/v1/colorscheme/<id>")]
+pub async fn get_color_scheme(id: i32, db: DbConn) -> Result<Json<ColorScheme>> {
+    db.run(move |conn| ColorScheme::find(id, conn)).await.map(Json)
+}
+
+#[get("/v1/colorschemes-by-tiling-id/<tiling_id>")]
+pub async fn get_color_schemes_by_tiling_id(tiling_id: i32, db: DbConn) -> Result<Json<Vec<ColorScheme>>> {
+    db.run(move |conn| queries::get_tiling_color_schemes(tiling_id, conn)).await.map(Json)
+}
+
+// a color scheme can only be moved from a tiling its author can edit to another they can edit
+#[patch("/v1/colorscheme", data = "<color_scheme_patch>")]
+pub async fn update_color_scheme(color_scheme_patch: ColorSchemePatch, mut auth_account: AuthAccount, db: DbConn) -> Result<Json<ColorScheme>> {
+    db.run(move |conn| conn.build_transaction().run(|| {
+        auth_account.can_edit(Owned::ColorScheme, color_scheme_patch.id, conn)?;
+        if let Some(tiling_id) = color_scheme_patch.tiling_id {
+            auth_account.can_edit(Owned::Tiling, ColorScheme::find(color_scheme_patch.id, conn)?.tiling_id, conn)?;
+            auth_account.can_edit(Owned::Tiling, tiling_id, conn)?;
+        }
+        if let Some(data) = color_scheme_patch.parse_data() {
+            validate_data(data)?;
+        }
+        color_scheme_patch.update(conn)
+    })).await.map(Json)
+}
+
+#[delete("/v1/colorscheme/<id>")]
+pub async fn delete_color_scheme(id: i32, mut auth_account: AuthAccount, db: DbConn) -> Result<Json<usize>> {
+    db.run(move |conn| conn.build_transaction().run(|| {
+        auth_account.can_edit(Owned::ColorScheme, id, conn)?;
+        ColorScheme::delete(id, conn)
+    })).await.map(Json)
+}
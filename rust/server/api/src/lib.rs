@@ -2,9 +2,11 @@
 
 mod account;
 mod atlas;
+mod colorscheme;
 mod common;
 mod health_check;
 mod labels;
+mod patch;
 mod polygon;
 mod search;
 mod tiling_type;
@@ -12,9 +14,11 @@ mod tiling;
 
 pub use self::account::*;
 pub use self::atlas::*;
+pub use self::colorscheme::*;
 pub use self::common::*;
 pub use self::health_check::*;
 pub use self::labels::*;
+pub use self::patch::*;
 pub use self::polygon::*;
 pub use self::search::*;
 pub use self::tiling_type::*;
@@ -0,0 +1,39 @@
+use auth::AuthAccount;
+use db_conn::DbConn;
+use models::*;
+use result::{Error, Result};
+use rocket::{http::Status, serde::json::Json};
+
+#[post("/v1/patch", data = "<patch_post>")]
+pub async fn create_patch(mut patch_post: PatchPost, mut auth_account: AuthAccount, db: DbConn) -> Result<Json<Patch>> {
+    db.run(move |conn| conn.build_transaction().run(|| {
+        auth_account.allowed(&ALLOWED_EDITOR_ROLES, conn)?;
+        patch_post.check_data(&FullAtlas::find(patch_post.atlas_id, conn)?).map_err(|err| Error::from_kind(Status::BadRequest, &err))?;
+        patch_post.owner_id = Some(auth_account.id);
+        patch_post.insert(conn)
+    })).await.map(Json)
+}
+
+#[get("/v1/patch/<id>")]
+pub async fn get_patch(id: i32, db: DbConn) -> Result<Json<Patch>> {
+    db.run(move |conn| Patch::find(id, conn)).await.map(Json)
+}
+
+#[patch("/v1/patch", data = "<patch_patch>")]
+pub async fn update_patch(patch_patch: PatchPatch, mut auth_account: AuthAccount, db: DbConn) -> Result<Json<Patch>> {
+    db.run(move |conn| conn.build_transaction().run(|| {
+        auth_account.can_edit(Owned::Patch, patch_patch.id, conn)?;
+        let patch = Patch::find(patch_patch.id, conn)?;
+        let full_atlas = FullAtlas::find(patch_patch.atlas_id.unwrap_or(patch.atlas_id), conn)?;
+        patch_patch.check_data(&patch, &full_atlas).map_err(|err| Error::from_kind(Status::BadRequest, &err))?;
+        patch_patch.update(conn)
+    })).await.map(Json)
+}
+
+#[delete("/v1/patch/<id>")]
+pub async fn delete_patch(id: i32, mut auth_account: AuthAccount, db: DbConn) -> Result<Json<usize>> {
+    db.run(move |conn| conn.build_transaction().run(|| {
+        auth_account.can_edit(Owned::Patch, id, conn)?;
+        Patch::delete(id, conn)
+    })).await.map(Json)
+}
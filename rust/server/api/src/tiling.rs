@@ -19,6 +19,13 @@ pub async fn get_tilings(start_id: Option<i32>, end_id: Option<i32>, limit: Opti
     ).await.map(Json)
 }
 
+#[get("/v1/tilings-by-wallpaper-group/<wallpaper_group>?<limit>")]
+pub async fn get_tilings_by_wallpaper_group(wallpaper_group: String, limit: Option<u32>, db: DbConn) -> Result<Json<Vec<Tiling>>> {
+    db.run(move |conn|
+        Tiling::find_by_wallpaper_group(wallpaper_group, clamp_optional(BATCH_LIMIT, limit), conn)
+    ).await.map(Json)
+}
+
 #[patch("/v1/tiling", data="<full_tiling_patch>")]
 pub async fn update_tiling(full_tiling_patch: FullTilingPatch, mut auth_account: AuthAccount, db: DbConn) -> Result<Json<FullTiling>> {
     db.run(move |conn| conn.build_transaction().run(|| {
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS Patch (
+  id        SERIAL       PRIMARY KEY,
+  title     VARCHAR(80)  NOT NULL,
+  atlas_id  INT          NOT NULL,
+  owner_id  INT,
+  data      TEXT         NOT NULL, -- serialized PatchSnapshot of the drawing
+
+  FOREIGN KEY (atlas_id) REFERENCES Atlas (id),
+  FOREIGN KEY (owner_id) REFERENCES Account (id)
+);
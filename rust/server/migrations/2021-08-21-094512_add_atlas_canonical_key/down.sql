@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS UnkeyedAtlas;
+
+DROP INDEX IF EXISTS atlas_canonical_key_md5_idx;
+
+ALTER TABLE Atlas
+  DROP COLUMN canonical_key
+;
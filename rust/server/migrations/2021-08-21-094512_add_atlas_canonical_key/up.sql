@@ -0,0 +1,18 @@
+-- canonical key of the atlas' tiling, shared by every atlas of the same tiling whatever its polygon, vertex and edge
+-- order or mirror image, see atlas::canonical_key; atlases created before this column are keyed by `server backfill`
+ALTER TABLE Atlas
+  ADD COLUMN canonical_key  TEXT
+;
+
+-- an atlas' tiling can only be stored once, which is enforced on a hash of its key as keys can be longer than a btree
+-- index entry allows
+CREATE UNIQUE INDEX atlas_canonical_key_md5_idx ON Atlas (md5(canonical_key));
+
+-- atlases created before canonical_key which `server backfill` couldn't key, either because another atlas of the same
+-- tiling already is or because their tiling can't be keyed, so that they aren't tried again
+CREATE TABLE IF NOT EXISTS UnkeyedAtlas (
+  atlas_id  INT   PRIMARY KEY,
+  reason    TEXT  NOT NULL,
+
+  FOREIGN KEY (atlas_id) REFERENCES Atlas (id)
+);
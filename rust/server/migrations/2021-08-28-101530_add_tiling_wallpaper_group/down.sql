@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS UngroupedTiling;
+
+DROP INDEX IF EXISTS tiling_wallpaper_group_idx;
+
+ALTER TABLE Tiling
+  DROP COLUMN wallpaper_group
+;
@@ -0,0 +1,15 @@
+-- name of the tiling's wallpaper group, e.g. p4m, see atlas::symmetry; tilings of atlases created before this column
+-- are given theirs by `server backfill`
+ALTER TABLE Tiling
+  ADD COLUMN wallpaper_group  VARCHAR(4)
+;
+
+CREATE INDEX tiling_wallpaper_group_idx ON Tiling (wallpaper_group);
+
+-- tilings whose wallpaper groups couldn't be found, along with why, so that `server backfill` doesn't try them again
+CREATE TABLE IF NOT EXISTS UngroupedTiling (
+  tiling_id  INT   PRIMARY KEY,
+  reason     TEXT  NOT NULL,
+
+  FOREIGN KEY (tiling_id) REFERENCES Tiling (id)
+);
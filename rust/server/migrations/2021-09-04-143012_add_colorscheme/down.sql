@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS colorscheme_tiling_id_idx;
+DROP TABLE IF EXISTS ColorScheme;
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS ColorScheme (
+  id         SERIAL       PRIMARY KEY,
+  title      VARCHAR(80)  NOT NULL,
+  tiling_id  INT          NOT NULL,
+  owner_id   INT,
+  data       TEXT         NOT NULL, -- serialized ColorSchemeData, a tiling's colors by polygon id or tile orbit
+
+  FOREIGN KEY (tiling_id) REFERENCES Tiling (id),
+  FOREIGN KEY (owner_id) REFERENCES Account (id)
+);
+
+CREATE INDEX colorscheme_tiling_id_idx ON ColorScheme (tiling_id);
@@ -75,6 +75,14 @@ pub struct FullAtlasPatch {
     pub vertices: Option<Vec<FullAtlasVertexPost>>,
 }
 
+// VertexTypesPost asks for up to limit of the vertex types which can be made from corners of the polygons with polygon_ids
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VertexTypesPost {
+    #[serde(rename = "polygonIds")]
+    pub polygon_ids: Vec<i32>,
+    pub limit: Option<u32>,
+}
+
 from_data! {
     FullAtlasVertex,
     FullAtlasVertexPost,
@@ -82,20 +90,36 @@ from_data! {
     FullAtlasEdgePost,
     FullAtlas,
     FullAtlasPost,
-    FullAtlasPatch
+    FullAtlasPatch,
+    VertexTypesPost
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 mod internal {
     use super::*;
-    use super::client::Atlas as ClientAtlas;
+    use super::client::{self, Atlas as ClientAtlas, AtlasError, AtlasValidation};
     use crate::tables::default_atlas_tiling_type_id;
-    use diesel::{self, prelude::*};
+    use diesel::{self, prelude::*, result::{DatabaseErrorKind, Error as DieselError}, sql_types::{Nullable, Text}};
     use itertools::Itertools;
     use result::{Error, Result};
     use rocket::http::Status;
     use schema::*;
     use std::collections::HashMap;
+    use tile::Tile;
+
+    // validate_atlas_vertices looks up the polygons with polygon_ids and validates vertices against them,
+    // the polygons themselves must all exist before vertices can be checked
+    fn validate_atlas_vertices(polygon_ids: &[i32], vertices: &[FullAtlasVertexPost], conn: &PgConnection) -> Result<AtlasValidation> {
+        let tiles_by_polygon_id = FullPolygon::find_batch(polygon_ids.to_vec(), conn)?
+            .into_iter()
+            .map(|full_polygon| (full_polygon.polygon.id, full_polygon.into_tile()))
+            .collect::<HashMap<i32, Tile>>();
+
+        match client::find_polygons(polygon_ids, &tiles_by_polygon_id) {
+            Ok(polygons) => Ok(client::validate(&polygons, vertices)),
+            Err(missing_polygon_ids) => Ok(AtlasValidation::from(missing_polygon_ids)),
+        }
+    }
 
     fn insert_atlas_vertices(
         atlas_id: i32,
@@ -287,6 +311,10 @@ mod internal {
                 .execute(conn)?;
             diesel::delete(atlasvertex::table.filter(atlasvertex::atlas_id.eq(id)))
                 .execute(conn)?;
+            diesel::delete(patch::table.filter(patch::atlas_id.eq(id)))
+                .execute(conn)?;
+            diesel::delete(unkeyedatlas::table.filter(unkeyedatlas::atlas_id.eq(id)))
+                .execute(conn)?;
             let atlas = Atlas::find(id, conn)?;
             Atlas::delete(id, conn)?;
             FullTiling::delete(atlas.tiling_id, conn)?;
@@ -307,6 +335,12 @@ mod internal {
 
         fn insert(self, conn: &PgConnection) -> Result<Self::Base> {
             let owner_id = self.owner_id.ok_or(Error::Default)?;
+
+            let validation = self.validate(conn)?;
+            if !validation.valid {
+                return Err(validation.into())
+            }
+
             let full_tiling = self.tiling
                 .as_full_tiling_post(default_atlas_tiling_type_id(), owner_id)
                 .insert(conn)?;
@@ -314,17 +348,14 @@ mod internal {
             let atlas = AtlasPost {
                 tiling_id: full_tiling.tiling.id,
                 tiling_type_id: default_atlas_tiling_type_id(),
+                canonical_key: None,
             }.insert(conn)?;
 
             insert_atlas_vertices(atlas.id, self.polygon_ids, self.vertices, conn)?;
 
             let full_atlas = FullAtlas::find(atlas.id, conn)?;
 
-            ClientAtlas::new(&full_atlas)
-                .map_err(|err_msg| Error::Custom(
-                    Status::BadRequest,
-                    err_msg,
-                ))?;
+            ClientAtlas::new(&full_atlas)?;
 
             Ok(full_atlas)
         }
@@ -345,30 +376,163 @@ mod internal {
             }
 
             if let Some(vertices) = self.vertices {
+                // if updating vertices, must provide polygon ids as well
+                let polygon_ids = self.polygon_ids.ok_or(Error::Status(Status::BadRequest))?;
+
+                let validation = validate_atlas_vertices(&polygon_ids, &vertices, conn)?;
+                if !validation.valid {
+                    return Err(validation.into())
+                }
+
                 diesel::delete(atlasedge::table.filter(atlasedge::atlas_id.eq(self.id)))
                     .execute(conn)?;
 
                 diesel::delete(atlasvertex::table.filter(atlasvertex::atlas_id.eq(self.id)))
                     .execute(conn)?;
 
-                // if updating vertices, must provide polygon ids as well
-                let polygon_ids = self.polygon_ids.ok_or(Error::Status(Status::BadRequest))?;
-
                 insert_atlas_vertices(self.id, polygon_ids, vertices, conn)?;
             }
 
             let full_atlas = FullAtlas::find(self.id, conn)?;
 
-            ClientAtlas::new(&full_atlas)
-                .map_err(|err_msg| Error::Custom(
-                    Status::BadRequest,
-                    err_msg,
-                ))?;
+            ClientAtlas::new(&full_atlas)?;
 
             Ok(full_atlas)
         }
     }
 
+    impl From<AtlasError> for Error {
+        fn from(err: AtlasError) -> Error {
+            Error::from_kind(Status::BadRequest, &err)
+        }
+    }
+
+    // an invalid atlas is reported with each of its issues, along with the indices each is found at
+    impl From<AtlasValidation> for Error {
+        fn from(validation: AtlasValidation) -> Error {
+            Error::Structured(Status::BadRequest, serde_json::json!({
+                "code": "InvalidAtlas",
+                "message": validation.to_string(),
+                "issues": validation.issues,
+            }))
+        }
+    }
+
+    impl FullAtlasPost {
+        // validate reports every issue with this atlas without persisting anything
+        pub fn validate(&self, conn: &PgConnection) -> Result<AtlasValidation> {
+            validate_atlas_vertices(&self.polygon_ids, &self.vertices, conn)
+        }
+    }
+
+    impl VertexTypesPost {
+        // polygons looks up the tiles of the polygons with polygon_ids in order, all of which must exist
+        pub fn polygons(&self, conn: &PgConnection) -> Result<Vec<Tile>> {
+            let tiles_by_polygon_id = FullPolygon::find_batch(self.polygon_ids.clone(), conn)?
+                .into_iter()
+                .map(|full_polygon| (full_polygon.polygon.id, full_polygon.into_tile()))
+                .collect::<HashMap<i32, Tile>>();
+
+            self.polygon_ids
+                .iter()
+                .map(|polygon_id| tiles_by_polygon_id
+                    .get(polygon_id)
+                    .cloned()
+                    .ok_or_else(|| Error::Custom(Status::NotFound, format!("polygon {} doesn't exist", polygon_id)))
+                )
+                .collect()
+        }
+    }
+
+    sql_function!(fn md5(text: Nullable<Text>) -> Nullable<Text>);
+
+    #[derive(Clone, Debug, PartialEq, Serialize)]
+    #[serde(tag = "kind")]
+    pub enum TilingError {
+        // another atlas, if it's known which, is already stored of the same tiling
+        DuplicateTiling {
+            #[serde(rename = "atlasId")]
+            atlas_id: Option<i32>,
+        },
+    }
+
+    impl std::fmt::Display for TilingError {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            match self {
+                TilingError::DuplicateTiling { atlas_id: Some(atlas_id) } =>
+                    write!(f, "atlas has the same tiling as atlas {}", atlas_id),
+                TilingError::DuplicateTiling { atlas_id: None } =>
+                    write!(f, "atlas has the same tiling as another atlas"),
+            }
+        }
+    }
+
+    impl From<TilingError> for Error {
+        fn from(err: TilingError) -> Error {
+            Error::from_kind(Status::Conflict, &err)
+        }
+    }
+
+    impl Atlas {
+        // find_unkeyed_ids lists the ids of atlases stored before their tilings were keyed, other than those which
+        // have already failed to be, see set_unkeyed
+        pub fn find_unkeyed_ids(conn: &PgConnection) -> Result<Vec<i32>> {
+            Ok(atlas::table
+                .left_join(unkeyedatlas::table)
+                .filter(atlas::canonical_key.is_null())
+                .filter(unkeyedatlas::atlas_id.is_null())
+                .select(atlas::id)
+                .order(atlas::id)
+                .load(conn)?)
+        }
+
+        // find_ungrouped_ids lists the ids of atlases whose tilings were stored before their wallpaper groups were,
+        // other than those whose groups have already failed to be found, see Tiling::set_wallpaper_group
+        pub fn find_ungrouped_ids(conn: &PgConnection) -> Result<Vec<i32>> {
+            Ok(atlas::table
+                .inner_join(tiling::table.left_join(ungroupedtiling::table))
+                .filter(tiling::wallpaper_group.is_null())
+                .filter(ungroupedtiling::tiling_id.is_null())
+                .select(atlas::id)
+                .order(atlas::id)
+                .load(conn)?)
+        }
+
+        // set_canonical_key stores the canonical key of an atlas' tiling,
+        // failing if another atlas of the same tiling is already stored
+        pub fn set_canonical_key(id: i32, canonical_key: String, conn: &PgConnection) -> Result<()> {
+            let duplicate_id: Option<i32> = atlas::table
+                .filter(md5(atlas::canonical_key).eq(md5(&canonical_key)))
+                .filter(atlas::id.ne(id))
+                .select(atlas::id)
+                .first(conn)
+                .optional()?;
+            if let Some(duplicate_id) = duplicate_id {
+                return Err(TilingError::DuplicateTiling { atlas_id: Some(duplicate_id) }.into())
+            }
+            // an atlas of the same tiling stored since is only caught by the unique index on the key's hash
+            match diesel::update(atlas::table.find(id)).set(atlas::canonical_key.eq(canonical_key)).execute(conn) {
+                Ok(_) => {
+                    diesel::delete(unkeyedatlas::table.find(id)).execute(conn)?;
+                    Ok(())
+                },
+                Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) =>
+                    Err(TilingError::DuplicateTiling { atlas_id: None }.into()),
+                Err(err) => Err(err.into()),
+            }
+        }
+
+        // set_unkeyed records why an atlas stored before tilings were keyed couldn't be keyed, so that it's no longer
+        // listed by find_unkeyed_ids
+        pub fn set_unkeyed(id: i32, reason: String, conn: &PgConnection) -> Result<()> {
+            diesel::insert_into(unkeyedatlas::table)
+                .values((unkeyedatlas::atlas_id.eq(id), unkeyedatlas::reason.eq(reason)))
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+            Ok(())
+        }
+    }
+
     impl FullAtlas {
         pub fn find_by_tiling_id(tiling_id: i32, conn: &PgConnection) -> Result<Self> {
             let atlas: Atlas = atlas::table.filter(atlas::tiling_id.eq(tiling_id))
@@ -382,14 +546,321 @@ mod internal {
 pub use self::internal::*;
 
 pub mod client {
-    use super::{FullAtlas, FullPolygon};
+    use super::{FullAtlas, FullAtlasEdgePost, FullAtlasVertexPost, FullPolygon};
     use common::{DEFAULT_F64_MARGIN, fmt_float, rad};
     use float_cmp::ApproxEq;
     use geometry::{Euclid, ORIGIN, Transformable};
     use itertools::{Itertools, izip};
-    use std::{collections::HashSet, f64::consts::TAU, iter};
+    use serde::{Deserialize, Serialize};
+    use std::{collections::{HashMap, HashSet, VecDeque}, f64::consts::TAU, iter};
     use tile::Tile;
 
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(tag = "kind")]
+    pub enum AtlasError {
+        // the atlas' polygon id at polygon index doesn't refer to an existing polygon
+        MissingPolygonId {
+            #[serde(rename = "polygonIndex")]
+            polygon_index: usize,
+            #[serde(rename = "polygonId")]
+            polygon_id: i32,
+        },
+        // the edge's polygon index doesn't refer to one of the atlas' polygons
+        MissingPolygon {
+            #[serde(rename = "vertexIndex")]
+            vertex_index: usize,
+            #[serde(rename = "edgeIndex")]
+            edge_index: usize,
+            #[serde(rename = "polygonIndex")]
+            polygon_index: usize,
+        },
+        // the edge's point index, or the one following it, is out of range for its polygon
+        MissingPoint {
+            #[serde(rename = "vertexIndex")]
+            vertex_index: usize,
+            #[serde(rename = "edgeIndex")]
+            edge_index: usize,
+            #[serde(rename = "pointIndex")]
+            point_index: usize,
+        },
+        // the interior angles of the vertex's polygons don't sum to 360°
+        AngleSumMismatch {
+            #[serde(rename = "vertexIndex")]
+            vertex_index: usize,
+            degrees: f64,
+        },
+        // the edge's neighbor index doesn't refer to one of the atlas' vertices
+        InvalidNeighborIndex {
+            #[serde(rename = "vertexIndex")]
+            vertex_index: usize,
+            #[serde(rename = "edgeIndex")]
+            edge_index: usize,
+            #[serde(rename = "neighborIndex")]
+            neighbor_index: usize,
+        },
+        // the edge's neighbor edge index doesn't refer to one of its neighbor vertex's edges
+        InvalidNeighborEdgeIndex {
+            #[serde(rename = "vertexIndex")]
+            vertex_index: usize,
+            #[serde(rename = "edgeIndex")]
+            edge_index: usize,
+            #[serde(rename = "neighborIndex")]
+            neighbor_index: usize,
+            #[serde(rename = "neighborEdgeIndex")]
+            neighbor_edge_index: usize,
+        },
+        // the edge's neighbor edge doesn't point back to the edge
+        NonReciprocalNeighbor {
+            #[serde(rename = "vertexIndex")]
+            vertex_index: usize,
+            #[serde(rename = "edgeIndex")]
+            edge_index: usize,
+            #[serde(rename = "neighborIndex")]
+            neighbor_index: usize,
+            #[serde(rename = "neighborEdgeIndex")]
+            neighbor_edge_index: usize,
+        },
+        // the sides of the two polygons meeting along the edge have different lengths
+        EdgeLengthMismatch {
+            #[serde(rename = "vertexIndex")]
+            vertex_index: usize,
+            #[serde(rename = "edgeIndex")]
+            edge_index: usize,
+            length: f64,
+            #[serde(rename = "adjacentLength")]
+            adjacent_length: f64,
+        },
+        // the edge and its neighbor edge have different lengths
+        NeighborEdgeLengthMismatch {
+            #[serde(rename = "vertexIndex")]
+            vertex_index: usize,
+            #[serde(rename = "edgeIndex")]
+            edge_index: usize,
+            length: f64,
+            #[serde(rename = "neighborLength")]
+            neighbor_length: f64,
+        },
+        // the edge and its neighbor edge disagree on whether the neighbor vertex is reflected
+        ParityMismatch {
+            #[serde(rename = "vertexIndex")]
+            vertex_index: usize,
+            #[serde(rename = "edgeIndex")]
+            edge_index: usize,
+            #[serde(rename = "neighborIndex")]
+            neighbor_index: usize,
+            #[serde(rename = "neighborEdgeIndex")]
+            neighbor_edge_index: usize,
+        },
+        // the vertex can't be reached from the first vertex by following neighbors
+        UnreachableVertex {
+            #[serde(rename = "vertexIndex")]
+            vertex_index: usize,
+        },
+        // the atlas has no vertices
+        NoVertices,
+    }
+
+    impl std::fmt::Display for AtlasError {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            match self {
+                AtlasError::MissingPolygonId { polygon_index, polygon_id } =>
+                    write!(f, "polygon {} has id {} which doesn't exist", polygon_index, polygon_id),
+                AtlasError::MissingPolygon { vertex_index, edge_index, polygon_index } =>
+                    write!(f, "vertex {}, edge {} references missing polygon {}", vertex_index, edge_index, polygon_index),
+                AtlasError::MissingPoint { vertex_index, edge_index, point_index } =>
+                    write!(f, "vertex {}, edge {} has missing point for index {}", vertex_index, edge_index, point_index),
+                AtlasError::AngleSumMismatch { vertex_index, degrees } =>
+                    write!(f, "vertex {} - prototiles don't fit together perfectly - expected 360° fill but received ~{}°", vertex_index, fmt_float(*degrees, 2)),
+                AtlasError::InvalidNeighborIndex { vertex_index, edge_index, neighbor_index } =>
+                    write!(f, "vertex {}, edge {} references missing neighbor vertex {}", vertex_index, edge_index, neighbor_index),
+                AtlasError::InvalidNeighborEdgeIndex { vertex_index, edge_index, neighbor_index, neighbor_edge_index } =>
+                    write!(f, "vertex {}, edge {} references missing edge {} of neighbor vertex {}", vertex_index, edge_index, neighbor_edge_index, neighbor_index),
+                AtlasError::NonReciprocalNeighbor { vertex_index, edge_index, neighbor_index, neighbor_edge_index } =>
+                    write!(f, "vertex {}, edge {} references edge {} of neighbor vertex {} which doesn't reference it back", vertex_index, edge_index, neighbor_edge_index, neighbor_index),
+                AtlasError::EdgeLengthMismatch { vertex_index, edge_index, length, adjacent_length } =>
+                    write!(f, "vertex {}, edge {} has length ~{} but its adjacent polygon's side has length ~{}", vertex_index, edge_index, fmt_float(*length, 2), fmt_float(*adjacent_length, 2)),
+                AtlasError::NeighborEdgeLengthMismatch { vertex_index, edge_index, length, neighbor_length } =>
+                    write!(f, "vertex {}, edge {} has length ~{} but its neighbor edge has length ~{}", vertex_index, edge_index, fmt_float(*length, 2), fmt_float(*neighbor_length, 2)),
+                AtlasError::ParityMismatch { vertex_index, edge_index, neighbor_index, neighbor_edge_index } =>
+                    write!(f, "vertex {}, edge {} has a different parity than edge {} of neighbor vertex {}", vertex_index, edge_index, neighbor_edge_index, neighbor_index),
+                AtlasError::UnreachableVertex { vertex_index } =>
+                    write!(f, "vertex {} can't be reached from vertex 0", vertex_index),
+                AtlasError::NoVertices => write!(f, "atlas has no vertices"),
+            }
+        }
+    }
+
+    impl std::error::Error for AtlasError {}
+
+    // AtlasValidation reports every issue found in an atlas, it's valid only if there are none
+    #[derive(Debug, Deserialize, Serialize)]
+    pub struct AtlasValidation {
+        pub valid: bool,
+        pub issues: Vec<AtlasError>,
+    }
+
+    impl From<Vec<AtlasError>> for AtlasValidation {
+        fn from(issues: Vec<AtlasError>) -> AtlasValidation {
+            AtlasValidation { valid: issues.is_empty(), issues }
+        }
+    }
+
+    impl std::fmt::Display for AtlasValidation {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "{}", self.issues.iter().map(AtlasError::to_string).join("; "))
+        }
+    }
+
+    // find_polygons returns the tiles of the polygons with polygon_ids in order, or an issue for each polygon id
+    // missing from tiles_by_polygon_id
+    pub fn find_polygons(polygon_ids: &[i32], tiles_by_polygon_id: &HashMap<i32, Tile>) -> Result<Vec<Tile>, Vec<AtlasError>> {
+        let missing_polygon_ids = polygon_ids
+            .iter()
+            .enumerate()
+            .filter(|(_, polygon_id)| !tiles_by_polygon_id.contains_key(polygon_id))
+            .map(|(polygon_index, polygon_id)| AtlasError::MissingPolygonId { polygon_index, polygon_id: *polygon_id })
+            .collect::<Vec<AtlasError>>();
+
+        if !missing_polygon_ids.is_empty() {
+            return Err(missing_polygon_ids)
+        }
+
+        Ok(polygon_ids.iter().map(|polygon_id| tiles_by_polygon_id[polygon_id].clone()).collect())
+    }
+
+    // validate checks vertices against polygons, the tiles of an atlas' polygons in order of their
+    // polygon index, and returns every issue found rather than stopping at the first
+    pub fn validate(polygons: &[Tile], vertices: &[FullAtlasVertexPost]) -> AtlasValidation {
+        let mut issues: Vec<AtlasError> = vec![];
+        if vertices.is_empty() {
+            issues.push(AtlasError::NoVertices);
+        }
+
+        // lengths[i][j] is the length of edge j of vertex i, if its polygon and point exist
+        let mut lengths: Vec<Vec<Option<f64>>> = Vec::with_capacity(vertices.len());
+
+        for (i, vertex) in vertices.iter().enumerate() {
+            // sides[j] is the length of the sides of edge j's polygon following and preceding the vertex
+            let mut sides: Vec<Option<(f64, f64)>> = Vec::with_capacity(vertex.edges.len());
+            let mut angle = 0.;
+
+            for (j, edge) in vertex.edges.iter().enumerate() {
+                let polygon = match polygons.get(edge.polygon_index) {
+                    Some(polygon) => polygon,
+                    None => {
+                        issues.push(AtlasError::MissingPolygon { vertex_index: i, edge_index: j, polygon_index: edge.polygon_index });
+                        sides.push(None);
+                        continue
+                    },
+                };
+
+                let size = polygon.size();
+                if edge.point_index >= size {
+                    issues.push(AtlasError::MissingPoint { vertex_index: i, edge_index: j, point_index: edge.point_index });
+                    sides.push(None);
+                    continue
+                }
+
+                let point = &polygon.points[edge.point_index];
+                let next_point = &polygon.points[(edge.point_index + 1) % size];
+                let prev_point = &polygon.points[(edge.point_index + size - 1) % size];
+                sides.push(Some(((next_point - point).norm(), (point - prev_point).norm())));
+                angle += polygon.angle(edge.point_index);
+            }
+
+            if sides.iter().all(Option::is_some) && !angle.approx_eq(TAU, DEFAULT_F64_MARGIN) {
+                issues.push(AtlasError::AngleSumMismatch { vertex_index: i, degrees: angle * 360. / TAU });
+            }
+
+            // edge j runs between edge j's polygon and the preceding edge's polygon
+            for (j, side) in sides.iter().enumerate() {
+                let prev_side = sides[(j + sides.len() - 1) % sides.len()];
+                if let (Some((length, _)), Some((_, adjacent_length))) = (side, prev_side) {
+                    if !length.approx_eq(adjacent_length, DEFAULT_F64_MARGIN) {
+                        issues.push(AtlasError::EdgeLengthMismatch { vertex_index: i, edge_index: j, length: *length, adjacent_length });
+                    }
+                }
+            }
+
+            lengths.push(sides.iter().map(|side| side.map(|(length, _)| length)).collect());
+        }
+
+        for (i, vertex) in vertices.iter().enumerate() {
+            for (j, edge) in vertex.edges.iter().enumerate() {
+                let neighbor = match vertices.get(edge.neighbor_index) {
+                    Some(neighbor) => neighbor,
+                    None => {
+                        issues.push(AtlasError::InvalidNeighborIndex { vertex_index: i, edge_index: j, neighbor_index: edge.neighbor_index });
+                        continue
+                    },
+                };
+
+                let neighbor_edge = match neighbor.edges.get(edge.neighbor_edge_index) {
+                    Some(neighbor_edge) => neighbor_edge,
+                    None => {
+                        issues.push(AtlasError::InvalidNeighborEdgeIndex {
+                            vertex_index: i,
+                            edge_index: j,
+                            neighbor_index: edge.neighbor_index,
+                            neighbor_edge_index: edge.neighbor_edge_index,
+                        });
+                        continue
+                    },
+                };
+
+                if neighbor_edge.neighbor_index != i || neighbor_edge.neighbor_edge_index != j {
+                    issues.push(AtlasError::NonReciprocalNeighbor {
+                        vertex_index: i,
+                        edge_index: j,
+                        neighbor_index: edge.neighbor_index,
+                        neighbor_edge_index: edge.neighbor_edge_index,
+                    });
+                    continue
+                }
+
+                // reciprocal pairs are only compared from their lesser end so that each is reported once
+                if (edge.neighbor_index, edge.neighbor_edge_index) < (i, j) {
+                    continue
+                }
+
+                if neighbor_edge.parity != edge.parity {
+                    issues.push(AtlasError::ParityMismatch {
+                        vertex_index: i,
+                        edge_index: j,
+                        neighbor_index: edge.neighbor_index,
+                        neighbor_edge_index: edge.neighbor_edge_index,
+                    });
+                }
+
+                if let (Some(length), Some(neighbor_length)) = (lengths[i][j], lengths[edge.neighbor_index][edge.neighbor_edge_index]) {
+                    if !length.approx_eq(neighbor_length, DEFAULT_F64_MARGIN) {
+                        issues.push(AtlasError::NeighborEdgeLengthMismatch { vertex_index: i, edge_index: j, length, neighbor_length });
+                    }
+                }
+            }
+        }
+
+        let mut reached = vec![false; vertices.len()];
+        let mut queue: VecDeque<usize> = VecDeque::default();
+        if !vertices.is_empty() {
+            reached[0] = true;
+            queue.push_back(0);
+        }
+        while let Some(i) = queue.pop_front() {
+            for edge in vertices[i].edges.iter() {
+                if let Some(false) = reached.get(edge.neighbor_index) {
+                    reached[edge.neighbor_index] = true;
+                    queue.push_back(edge.neighbor_index);
+                }
+            }
+        }
+        for (i, _) in reached.iter().enumerate().filter(|(_, reached)| !**reached) {
+            issues.push(AtlasError::UnreachableVertex { vertex_index: i });
+        }
+
+        AtlasValidation::from(issues)
+    }
+
     #[derive(Clone, Debug)]
     pub struct ProtoNeighbor {
         pub proto_vertex_star_index: usize,
@@ -399,10 +870,11 @@ pub mod client {
         pub reverse_tile_index: usize,
     }
 
-    #[derive(Debug)]
+    #[derive(Clone, Debug)]
     pub struct ProtoVertexStar {
         pub index: usize,
         pub tiles: Vec<Tile>,
+        pub polygon_indices: Vec<usize>, // polygon_indices[i] is the index in the atlas' polygons of tiles[i]'s polygon
         pub proto_neighbors: Vec<ProtoNeighbor>, // proto_neighbors[i].transform.translate == proto_edges[i].tile.points[i+1]
     }
 
@@ -428,53 +900,75 @@ pub mod client {
         }
     }
 
-    #[derive(Debug)]
+    #[derive(Clone, Debug)]
     pub struct Atlas {
         pub tiles: Vec<Tile>,
         pub proto_vertex_stars: Vec<ProtoVertexStar>,
     }
 
     impl Atlas {
-        pub fn new(config: &FullAtlas) -> Result<Atlas, String> {
+        pub fn new(config: &FullAtlas) -> Result<Atlas, AtlasError> {
+            let polygons = config.polygons.iter().map(FullPolygon::into_tile).collect::<Vec<Tile>>();
+            let vertices = config.vertices
+                .iter()
+                .map(|vertex| FullAtlasVertexPost {
+                    edges: vertex.edges
+                        .iter()
+                        .map(|edge| FullAtlasEdgePost {
+                            polygon_index: edge.polygon_index,
+                            point_index: edge.point_index,
+                            neighbor_index: edge.neighbor_index,
+                            neighbor_edge_index: edge.neighbor_edge_index,
+                            parity: edge.parity,
+                        })
+                        .collect(),
+                })
+                .collect::<Vec<FullAtlasVertexPost>>();
+            Atlas::from_vertex_posts(&polygons, &vertices)
+        }
+
+        // from_vertex_posts builds the atlas of vertices which have not been stored yet, where polygons
+        // are the tiles of the atlas' polygons in order of polygon index
+        pub fn from_vertex_posts(polygons: &[Tile], vertices: &[FullAtlasVertexPost]) -> Result<Atlas, AtlasError> {
+            if vertices.is_empty() {
+                return Err(AtlasError::NoVertices)
+            }
+
             // collect all proto tiles belonging to all vertices prior to
             // building vertices to be able to reference other vertices
             // while building their neighbors
-            let mut all_tiles: Vec<Vec<Tile>> = Vec::with_capacity(config.vertices.len());
-            for (i, vertex) in config.vertices.iter().enumerate() {
+            let mut all_tiles: Vec<Vec<Tile>> = Vec::with_capacity(vertices.len());
+            for (i, vertex) in vertices.iter().enumerate() {
                 let mut tiles: Vec<Tile> = Vec::with_capacity(vertex.edges.len());
                 let mut rotation = 0.;
                 for (j, edge) in vertex.edges.iter().enumerate() {
-                    let base_tile = config.polygons
+                    let base_tile = polygons
                         .get(edge.polygon_index)
-                        .map(FullPolygon::into_tile)
-                        .ok_or(String::from(format!(
-                            "polygon {} is missing in vertex {} spec",
-                            edge.polygon_index,
-                            j,
-                        )))?;
+                        .cloned()
+                        .ok_or(AtlasError::MissingPolygon {
+                            vertex_index: i,
+                            edge_index: j,
+                            polygon_index: edge.polygon_index,
+                        })?;
 
                     let point = match base_tile.points.get(edge.point_index) {
                         Some(point) => point,
-                        None => return Err(String::from(format!(
-                            "vertex {}, edge {} has missing point for index {} - Tile == {:?}",
-                            i,
-                            j,
-                            edge.point_index,
-                            base_tile,
-                        ))),
+                        None => return Err(AtlasError::MissingPoint {
+                            vertex_index: i,
+                            edge_index: j,
+                            point_index: edge.point_index,
+                        }),
                     };
 
                     let mut tile = base_tile.transform(&Euclid::Translate(point.neg().values()));
 
                     let next_point = match tile.points.get((edge.point_index + 1) % base_tile.size()) {
                         Some(point) => point.clone(),
-                        None => return Err(String::from(format!(
-                            "vertex {}, edge {} has missing point for index {} - Tile == {:?}",
-                            i,
-                            j,
-                            (edge.point_index + 1) % base_tile.size(),
-                            base_tile,
-                        ))),
+                        None => return Err(AtlasError::MissingPoint {
+                            vertex_index: i,
+                            edge_index: j,
+                            point_index: (edge.point_index + 1) % base_tile.size(),
+                        }),
                     };
 
                     let angle = tile.angle(edge.point_index);
@@ -486,22 +980,31 @@ pub mod client {
                     rotation += angle;
                 }
                 if !rotation.approx_eq(TAU, DEFAULT_F64_MARGIN) {
-                    return Err(String::from(format!("vertex {} - prototiles don't fit together perfectly - expected 360° fill but received ~{}°\n{:#?}\n{:#?}", i, fmt_float(rotation * 360. / TAU, 2), vertex, tiles)))
+                    return Err(AtlasError::AngleSumMismatch { vertex_index: i, degrees: rotation * 360. / TAU })
                 }
                 all_tiles.extend(iter::once(tiles));
             }
 
-            let mut proto_vertex_stars: Vec<ProtoVertexStar> = Vec::with_capacity(config.vertices.len());
-            for (i, (vertex, tiles)) in izip!(config.vertices.iter(), all_tiles.iter()).enumerate() {
+            let mut proto_vertex_stars: Vec<ProtoVertexStar> = Vec::with_capacity(vertices.len());
+            for (i, (vertex, tiles)) in izip!(vertices.iter(), all_tiles.iter()).enumerate() {
                 let mut proto_neighbors: Vec<ProtoNeighbor> = Vec::with_capacity(vertex.edges.len());
-                for (edge, tile) in izip!(vertex.edges.iter(), tiles.iter()) {
+                for (j, (edge, tile)) in izip!(vertex.edges.iter(), tiles.iter()).enumerate() {
                     let edge_point = tile.points.get(1).unwrap();
 
                     let neighbor_edge_point = all_tiles
                         .get(edge.neighbor_index)
-                        .ok_or(String::from("Invalid neighbor index in edge"))?
-                        .get(edge.point_index)
-                        .ok_or(String::from("Invalid point index in edge"))?
+                        .ok_or(AtlasError::InvalidNeighborIndex {
+                            vertex_index: i,
+                            edge_index: j,
+                            neighbor_index: edge.neighbor_index,
+                        })?
+                        .get(edge.neighbor_edge_index)
+                        .ok_or(AtlasError::InvalidNeighborEdgeIndex {
+                            vertex_index: i,
+                            edge_index: j,
+                            neighbor_index: edge.neighbor_index,
+                            neighbor_edge_index: edge.neighbor_edge_index,
+                        })?
                         .points
                         .get(1)
                         .unwrap();
@@ -521,6 +1024,7 @@ pub mod client {
                 proto_vertex_stars.extend(vec![ProtoVertexStar {
                     index: i,
                     tiles: tiles.clone(),
+                    polygon_indices: vertex.edges.iter().map(|edge| edge.polygon_index).collect(),
                     proto_neighbors,
                 }]);
             }
@@ -540,4 +1044,178 @@ pub mod client {
             })
         }
     }
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use geometry::Point;
+        use std::f64::consts::PI;
+
+        fn square(size: f64) -> Tile {
+            Tile::new(vec![Point(0., 0.), Point(size, 0.), Point(size, size), Point(0., size)])
+        }
+
+        fn edge(polygon_index: usize, neighbor_index: usize, neighbor_edge_index: usize) -> FullAtlasEdgePost {
+            FullAtlasEdgePost { polygon_index, point_index: 0, neighbor_index, neighbor_edge_index, parity: false }
+        }
+
+        // square_vertex is the vertex of the square tiling, each of whose edges neighbors the opposite edge of the
+        // same vertex
+        fn square_vertex(polygon_index: usize) -> FullAtlasVertexPost {
+            FullAtlasVertexPost { edges: (0..4).map(|j| edge(polygon_index, 0, (j + 2) % 4)).collect() }
+        }
+
+        fn issues(polygons: &[Tile], vertices: &[FullAtlasVertexPost]) -> Vec<AtlasError> {
+            validate(polygons, vertices).issues
+        }
+
+        #[test]
+        fn test_validate_valid() {
+            let validation = validate(&[square(1.)], &[square_vertex(0)]);
+            assert!(validation.valid);
+            assert!(validation.issues.is_empty());
+            assert!(Atlas::from_vertex_posts(&[square(1.)], &[square_vertex(0)]).is_ok());
+        }
+
+        #[test]
+        fn test_validate_no_vertices() {
+            assert_eq!(vec![AtlasError::NoVertices], issues(&[square(1.)], &[]));
+            assert!(!validate(&[square(1.)], &[]).valid);
+            assert!(matches!(Atlas::from_vertex_posts(&[square(1.)], &[]), Err(AtlasError::NoVertices)));
+        }
+
+        #[test]
+        fn test_from_vertex_posts_neighbor_rotation() {
+            // each vertex of the hexagonal tiling is its neighbors' vertex turned by π, whichever corners its hexagons
+            // are placed by
+            let hexagon = tile::regular_polygon(1., 6);
+            for point_index in 0..6 {
+                let vertex = FullAtlasVertexPost {
+                    edges: (0..3).map(|j| FullAtlasEdgePost { polygon_index: 0, point_index, neighbor_index: 0, neighbor_edge_index: j, parity: false }).collect(),
+                };
+                let atlas = Atlas::from_vertex_posts(&[hexagon.clone()], &[vertex]).unwrap();
+                for proto_neighbor in atlas.proto_vertex_stars[0].proto_neighbors.iter() {
+                    assert!(proto_neighbor.transform.rotate.approx_eq(PI, DEFAULT_F64_MARGIN), "{}", point_index);
+                }
+            }
+        }
+
+        #[test]
+        fn test_find_polygons() {
+            let tiles_by_polygon_id = vec![(7, square(1.))].into_iter().collect::<HashMap<i32, Tile>>();
+            assert_eq!(vec![square(1.)], find_polygons(&[7], &tiles_by_polygon_id).unwrap());
+            assert_eq!(
+                Err(vec![AtlasError::MissingPolygonId { polygon_index: 1, polygon_id: 8 }]),
+                find_polygons(&[7, 8], &tiles_by_polygon_id),
+            );
+        }
+
+        #[test]
+        fn test_validate_missing_polygon() {
+            let mut vertex = square_vertex(0);
+            vertex.edges[1].polygon_index = 5;
+            assert_eq!(vec![AtlasError::MissingPolygon { vertex_index: 0, edge_index: 1, polygon_index: 5 }], issues(&[square(1.)], &[vertex]));
+        }
+
+        #[test]
+        fn test_validate_missing_point() {
+            let mut vertex = square_vertex(0);
+            vertex.edges[2].point_index = 4;
+            assert_eq!(vec![AtlasError::MissingPoint { vertex_index: 0, edge_index: 2, point_index: 4 }], issues(&[square(1.)], &[vertex]));
+        }
+
+        #[test]
+        fn test_validate_angle_sum_mismatch() {
+            // three squares leave a quarter turn unfilled
+            let vertex = FullAtlasVertexPost { edges: vec![edge(0, 0, 1), edge(0, 0, 0), edge(0, 0, 2)] };
+            assert_eq!(vec![AtlasError::AngleSumMismatch { vertex_index: 0, degrees: 270. }], issues(&[square(1.)], &[vertex]));
+        }
+
+        #[test]
+        fn test_validate_edge_length_mismatch() {
+            // a square of side 2 meets squares of side 1 along the edges on either side of it
+            let mut vertex = square_vertex(0);
+            vertex.edges[1].polygon_index = 1;
+            assert_eq!(
+                vec![
+                    AtlasError::EdgeLengthMismatch { vertex_index: 0, edge_index: 1, length: 2., adjacent_length: 1. },
+                    AtlasError::EdgeLengthMismatch { vertex_index: 0, edge_index: 2, length: 1., adjacent_length: 2. },
+                    AtlasError::NeighborEdgeLengthMismatch { vertex_index: 0, edge_index: 1, length: 2., neighbor_length: 1. },
+                ],
+                issues(&[square(1.), square(2.)], &[vertex]),
+            );
+        }
+
+        #[test]
+        fn test_validate_invalid_neighbor_index() {
+            let mut vertex = square_vertex(0);
+            vertex.edges[0].neighbor_index = 3;
+            assert_eq!(
+                vec![
+                    AtlasError::InvalidNeighborIndex { vertex_index: 0, edge_index: 0, neighbor_index: 3 },
+                    AtlasError::NonReciprocalNeighbor { vertex_index: 0, edge_index: 2, neighbor_index: 0, neighbor_edge_index: 0 },
+                ],
+                issues(&[square(1.)], &[vertex]),
+            );
+        }
+
+        #[test]
+        fn test_validate_invalid_neighbor_edge_index() {
+            let mut vertex = square_vertex(0);
+            vertex.edges[0].neighbor_edge_index = 7;
+            assert_eq!(
+                vec![
+                    AtlasError::InvalidNeighborEdgeIndex { vertex_index: 0, edge_index: 0, neighbor_index: 0, neighbor_edge_index: 7 },
+                    AtlasError::NonReciprocalNeighbor { vertex_index: 0, edge_index: 2, neighbor_index: 0, neighbor_edge_index: 0 },
+                ],
+                issues(&[square(1.)], &[vertex]),
+            );
+        }
+
+        #[test]
+        fn test_validate_non_reciprocal_neighbor() {
+            let mut vertex = square_vertex(0);
+            vertex.edges[0].neighbor_edge_index = 1;
+            assert_eq!(
+                vec![
+                    AtlasError::NonReciprocalNeighbor { vertex_index: 0, edge_index: 0, neighbor_index: 0, neighbor_edge_index: 1 },
+                    AtlasError::NonReciprocalNeighbor { vertex_index: 0, edge_index: 2, neighbor_index: 0, neighbor_edge_index: 0 },
+                ],
+                issues(&[square(1.)], &[vertex]),
+            );
+        }
+
+        #[test]
+        fn test_validate_neighbor_edge_length_mismatch() {
+            // a vertex of unit squares neighbors a vertex of squares of side 2 along edges of different lengths
+            let vertices = [
+                FullAtlasVertexPost { edges: vec![edge(0, 1, 2), edge(0, 0, 3), edge(0, 1, 0), edge(0, 0, 1)] },
+                FullAtlasVertexPost { edges: vec![edge(1, 0, 2), edge(1, 1, 3), edge(1, 0, 0), edge(1, 1, 1)] },
+            ];
+            assert_eq!(
+                vec![
+                    AtlasError::NeighborEdgeLengthMismatch { vertex_index: 0, edge_index: 0, length: 1., neighbor_length: 2. },
+                    AtlasError::NeighborEdgeLengthMismatch { vertex_index: 0, edge_index: 2, length: 1., neighbor_length: 2. },
+                ],
+                issues(&[square(1.), square(2.)], &vertices),
+            );
+        }
+
+        #[test]
+        fn test_validate_parity_mismatch() {
+            let mut vertex = square_vertex(0);
+            vertex.edges[0].parity = true;
+            assert_eq!(
+                vec![AtlasError::ParityMismatch { vertex_index: 0, edge_index: 0, neighbor_index: 0, neighbor_edge_index: 2 }],
+                issues(&[square(1.)], &[vertex]),
+            );
+        }
+
+        #[test]
+        fn test_validate_unreachable_vertex() {
+            assert_eq!(
+                vec![AtlasError::UnreachableVertex { vertex_index: 1 }],
+                issues(&[square(1.)], &[square_vertex(0), FullAtlasVertexPost { edges: (0..4).map(|j| edge(0, 1, (j + 2) % 4)).collect() }]),
+            );
+        }
+    }
 }
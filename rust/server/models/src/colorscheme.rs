@@ -0,0 +1,56 @@
+use crate::tables::*;
+use serde::{Deserialize, Serialize};
+
+// ColorSchemeData is the palette stored in a ColorScheme's data, which colors each tile of the scheme's tiling either by
+// the id of its polygon or by its orbit under the tiling's symmetries
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub enum ColorSchemeData {
+    // Polygon colors tiles by the id of the polygon they're a copy of
+    Polygon { colors: Vec<ColorSchemeColor> },
+    // Orbit colors tiles by their orbit, numbered as by atlas::symmetry's tile_orbits
+    Orbit { colors: Vec<ColorSchemeColor> },
+}
+
+// ColorSchemeColor is the color of the tiles with the given polygon id or orbit
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ColorSchemeColor {
+    pub key: i32,
+    pub rgb: (u8, u8, u8),
+}
+
+impl ColorSchemeData {
+    // colors returns the colors of every polygon id or orbit the scheme colors
+    pub fn colors(&self) -> &Vec<ColorSchemeColor> {
+        match self {
+            ColorSchemeData::Polygon { colors } => colors,
+            ColorSchemeData::Orbit { colors } => colors,
+        }
+    }
+
+    // color returns the color of the tiles with the given polygon id or orbit, if the scheme has one
+    pub fn color(&self, key: i32) -> Option<(u8, u8, u8)> {
+        self.colors().iter().find(|color| color.key == key).map(|color| color.rgb)
+    }
+}
+
+impl ColorScheme {
+    // parse_data parses the ColorSchemeData stored in the scheme
+    pub fn parse_data(&self) -> Result<ColorSchemeData, serde_json::Error> {
+        serde_json::from_str(&self.data)
+    }
+}
+
+impl ColorSchemePost {
+    // parse_data parses the ColorSchemeData to be stored in the scheme
+    pub fn parse_data(&self) -> Result<ColorSchemeData, serde_json::Error> {
+        serde_json::from_str(&self.data)
+    }
+}
+
+impl ColorSchemePatch {
+    // parse_data parses the ColorSchemeData to be stored in the scheme, if it's being replaced
+    pub fn parse_data(&self) -> Option<Result<ColorSchemeData, serde_json::Error>> {
+        self.data.as_ref().map(|data| serde_json::from_str(data))
+    }
+}
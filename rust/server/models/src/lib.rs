@@ -13,6 +13,8 @@ extern crate paste;
 
 mod account;
 mod atlas;
+mod colorscheme;
+mod patch;
 mod polygon;
 mod search;
 mod tables;
@@ -20,6 +22,8 @@ mod tiling;
 
 pub use self::account::*;
 pub use self::atlas::*;
+pub use self::colorscheme::*;
+pub use self::patch::*;
 pub use self::polygon::*;
 pub use self::search::*;
 pub use self::tables::*;
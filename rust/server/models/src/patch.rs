@@ -0,0 +1,187 @@
+use crate::{tables::*, FullAtlas};
+use serde::{Deserialize, Serialize};
+
+// PATCH_DATA_VERSION is the version of PatchSnapshot a Patch's data must be, see patch::PATCH_SNAPSHOT_VERSION
+pub const PATCH_DATA_VERSION: u32 = 1;
+
+// PatchData is the shape of a Patch's data, a serialized PatchSnapshot, whose tiles' states are left as they are
+#[derive(Deserialize)]
+struct PatchData {
+    version: u32,
+    #[serde(rename = "atlasId")]
+    atlas_id: i32,
+    tiles: Vec<PatchTileData>,
+}
+
+// PatchTileData is the shape of a PatchTileSnapshot, whose fields are only deserialized to check that they're there
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct PatchTileData {
+    #[serde(rename = "protoVertexStarIndex")]
+    proto_vertex_star_index: usize,
+    parity: bool,
+    rotation: f64,
+    anchor: (f64, f64),
+    #[serde(rename = "linkIndex")]
+    link_index: usize,
+    state: serde_json::Value,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub enum PatchDataError {
+    // the patch's data isn't a serialized PatchSnapshot
+    InvalidData {
+        reason: String,
+    },
+    // the patch's data is a snapshot of a version other than PATCH_DATA_VERSION
+    UnsupportedVersion {
+        version: u32,
+    },
+    // the patch's data is a snapshot of a patch of another atlas than the patch's own
+    AtlasMismatch {
+        #[serde(rename = "atlasId")]
+        atlas_id: i32,
+        #[serde(rename = "dataAtlasId")]
+        data_atlas_id: i32,
+    },
+    // the tile's proto vertex star index doesn't refer to one of the atlas' vertices
+    MissingProtoVertexStar {
+        #[serde(rename = "tileIndex")]
+        tile_index: usize,
+        #[serde(rename = "protoVertexStarIndex")]
+        proto_vertex_star_index: usize,
+    },
+    // the tile's link index is out of range for the link of its anchor's vertex star
+    LinkIndexOutOfRange {
+        #[serde(rename = "tileIndex")]
+        tile_index: usize,
+        #[serde(rename = "linkIndex")]
+        link_index: usize,
+    },
+}
+
+impl std::fmt::Display for PatchDataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PatchDataError::InvalidData { reason } =>
+                write!(f, "invalid patch data: {}", reason),
+            PatchDataError::UnsupportedVersion { version } =>
+                write!(f, "patch data has version {} but only version {} is supported", version, PATCH_DATA_VERSION),
+            PatchDataError::AtlasMismatch { atlas_id, data_atlas_id } =>
+                write!(f, "patch data is for atlas {} but patch is for atlas {}", data_atlas_id, atlas_id),
+            PatchDataError::MissingProtoVertexStar { tile_index, proto_vertex_star_index } =>
+                write!(f, "tile {} references missing vertex {}", tile_index, proto_vertex_star_index),
+            PatchDataError::LinkIndexOutOfRange { tile_index, link_index } =>
+                write!(f, "tile {} has link index {} out of range for its vertex", tile_index, link_index),
+        }
+    }
+}
+
+// check_data checks that data is a serialized PatchSnapshot of a patch of the atlas with atlas_id, whose vertices
+// have vertex_sizes edges each, in order
+fn check_data(atlas_id: i32, vertex_sizes: &[usize], data: &str) -> Result<(), PatchDataError> {
+    let patch_data = serde_json::from_str::<PatchData>(data)
+        .map_err(|err| PatchDataError::InvalidData { reason: err.to_string() })?;
+    if patch_data.version != PATCH_DATA_VERSION {
+        return Err(PatchDataError::UnsupportedVersion { version: patch_data.version })
+    }
+    if patch_data.atlas_id != atlas_id {
+        return Err(PatchDataError::AtlasMismatch { atlas_id, data_atlas_id: patch_data.atlas_id })
+    }
+    for (tile_index, tile) in patch_data.tiles.iter().enumerate() {
+        let vertex_size = vertex_sizes
+            .get(tile.proto_vertex_star_index)
+            .ok_or(PatchDataError::MissingProtoVertexStar { tile_index, proto_vertex_star_index: tile.proto_vertex_star_index })?;
+        if tile.link_index >= *vertex_size {
+            return Err(PatchDataError::LinkIndexOutOfRange { tile_index, link_index: tile.link_index })
+        }
+    }
+    Ok(())
+}
+
+// vertex_sizes lists the number of edges of each of the atlas' vertices, which is the size of their vertex stars' links
+fn vertex_sizes(atlas: &FullAtlas) -> Vec<usize> {
+    atlas.vertices.iter().map(|vertex| vertex.edges.len()).collect()
+}
+
+impl PatchPost {
+    // check_data checks that the patch's data is a snapshot of a patch of its atlas
+    pub fn check_data(&self, atlas: &FullAtlas) -> Result<(), PatchDataError> {
+        check_data(atlas.id, &vertex_sizes(atlas), &self.data)
+    }
+}
+
+impl PatchPatch {
+    // check_data checks that the patch's data is a snapshot of a patch of its atlas once patched over patch, the
+    // stored Patch, so that neither its atlas nor its data can be changed without the other. atlas is the atlas of
+    // the patched Patch.
+    pub fn check_data(&self, patch: &Patch, atlas: &FullAtlas) -> Result<(), PatchDataError> {
+        if self.atlas_id.is_none() && self.data.is_none() {
+            return Ok(())
+        }
+        check_data(atlas.id, &vertex_sizes(atlas), self.data.as_ref().unwrap_or(&patch.data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(version: u32, atlas_id: i32, tiles: &[(usize, usize)]) -> String {
+        serde_json::json!({
+            "version": version,
+            "atlasId": atlas_id,
+            "tiles": tiles
+                .iter()
+                .map(|(proto_vertex_star_index, link_index)| serde_json::json!({
+                    "protoVertexStarIndex": proto_vertex_star_index,
+                    "parity": false,
+                    "rotation": 0.,
+                    "anchor": [0., 0.],
+                    "linkIndex": link_index,
+                    "state": {"hue": 0},
+                }))
+                .collect::<Vec<serde_json::Value>>(),
+        }).to_string()
+    }
+
+    #[test]
+    fn test_check_data() {
+        let vertex_sizes = [3, 4];
+        assert_eq!(Ok(()), check_data(1, &vertex_sizes, &data(PATCH_DATA_VERSION, 1, &[])));
+        assert_eq!(Ok(()), check_data(1, &vertex_sizes, &data(PATCH_DATA_VERSION, 1, &[(0, 2), (1, 3)])));
+        assert_eq!(
+            Err(PatchDataError::UnsupportedVersion { version: PATCH_DATA_VERSION + 1 }),
+            check_data(1, &vertex_sizes, &data(PATCH_DATA_VERSION + 1, 1, &[])),
+        );
+        assert_eq!(
+            Err(PatchDataError::AtlasMismatch { atlas_id: 1, data_atlas_id: 2 }),
+            check_data(1, &vertex_sizes, &data(PATCH_DATA_VERSION, 2, &[])),
+        );
+        assert_eq!(
+            Err(PatchDataError::MissingProtoVertexStar { tile_index: 1, proto_vertex_star_index: 2 }),
+            check_data(1, &vertex_sizes, &data(PATCH_DATA_VERSION, 1, &[(0, 0), (2, 0)])),
+        );
+        assert_eq!(
+            Err(PatchDataError::LinkIndexOutOfRange { tile_index: 0, link_index: 3 }),
+            check_data(1, &vertex_sizes, &data(PATCH_DATA_VERSION, 1, &[(0, 3)])),
+        );
+    }
+
+    #[test]
+    fn test_check_data_invalid() {
+        for data in [
+            "",
+            "{}",
+            r#"{"atlasId": 1, "tiles": []}"#,
+            r#"{"version": 1, "atlasId": 1}"#,
+            r#"{"version": 1, "atlasId": 1, "tiles": [{"protoVertexStarIndex": 0, "linkIndex": 0}]}"#,
+        ].iter() {
+            match check_data(1, &[3], data) {
+                Err(PatchDataError::InvalidData { .. }) => {},
+                result => panic!("expected invalid data for {:?} but got {:?}", data, result),
+            }
+        }
+    }
+}
@@ -339,6 +339,7 @@ pub fn none_i32() -> Option<i32> { None }
 pub fn none_opt_datetime() -> Option<Option<NaiveDateTime>> { None }
 pub fn none_opt_i32() -> Option<Option<i32>> { None }
 pub fn none_opt_string() -> Option<Option<String>> { None }
+pub fn none_string() -> Option<String> { None }
 
 pub fn default_account_password_reset_code() -> Option<String> { None }
 pub fn default_account_password_reset_code_timestamp() -> Option<NaiveDateTime> { None }
@@ -385,6 +386,8 @@ crud! {
         tiling_id: i32,
         #[serde(rename = "tilingTypeId", skip_deserializing)] { "default_atlas_tiling_type_id", "none_i32" }
         tiling_type_id: i32,
+        #[serde(rename = "canonicalKey", skip_deserializing)] { "none_string", "none_opt_string" }
+        canonical_key: Option<String>,
     },
 
     "atlasedge", atlasedge, Atlas PolygonPoint,
@@ -409,11 +412,31 @@ crud! {
         atlas_id: i32,
     },
 
+    "colorscheme", colorscheme, Tiling,
+    struct ColorScheme {
+        title: String,
+        #[serde(rename = "tilingId")]
+        tiling_id: i32,
+        #[serde(rename = "ownerId", skip_deserializing, skip_serializing)] { "none_i32", "none_opt_i32" }
+        owner_id: Option<i32>,
+        data: String,
+    },
+
     "label", label,,
     struct Label {
         content: String,
     },
 
+    "patch", patch, Atlas,
+    struct Patch {
+        title: String,
+        #[serde(rename = "atlasId")]
+        atlas_id: i32,
+        #[serde(rename = "ownerId", skip_deserializing, skip_serializing)] { "none_i32", "none_opt_i32" }
+        owner_id: Option<i32>,
+        data: String,
+    },
+
     "point", point,,
     struct Point {
         x: f64,
@@ -456,6 +479,8 @@ crud! {
         tiling_type_id: i32,
         #[serde(rename = "ownerId", skip_deserializing, skip_serializing)] { "none_i32", "none_opt_i32" }
         owner_id: Option<i32>,
+        #[serde(rename = "wallpaperGroup", skip_deserializing)] { "none_string", "none_opt_string" }
+        wallpaper_group: Option<String>,
     },
 
     "tilinglabel", tilinglabel, Tiling Label,
@@ -523,6 +548,8 @@ mod impls {
 
     pub enum Owned {
         Atlas,
+        ColorScheme,
+        Patch,
         Polygon,
         Tiling,
     }
@@ -536,6 +563,16 @@ mod impls {
                     .get_result(conn)
                     .map_err(Error::from),
 
+                Owned::ColorScheme => colorscheme::table.filter(colorscheme::id.eq(id))
+                    .select(colorscheme::owner_id)
+                    .get_result(conn)
+                    .map_err(Error::from),
+
+                Owned::Patch => patch::table.filter(patch::id.eq(id))
+                    .select(patch::owner_id)
+                    .get_result(conn)
+                    .map_err(Error::from),
+
                 Owned::Polygon => polygon::table.filter(polygon::id.eq(id))
                     .select(polygon::owner_id)
                     .get_result(conn)
@@ -557,6 +594,22 @@ mod impls {
                     Owned::Tiling.lock(tiling_id, conn)
                 },
 
+                Owned::ColorScheme => ColorSchemePatch {
+                    id,
+                    owner_id: Some(None),
+                    title: None,
+                    tiling_id: None,
+                    data: None,
+                }.update(conn).and(Ok(())),
+
+                Owned::Patch => PatchPatch {
+                    id,
+                    owner_id: Some(None),
+                    title: None,
+                    atlas_id: None,
+                    data: None,
+                }.update(conn).and(Ok(())),
+
                 Owned::Polygon => PolygonPatch {
                     id,
                     owner_id: Some(None),
@@ -568,6 +621,7 @@ mod impls {
                     owner_id: Some(None),
                     title: None,
                     tiling_type_id: None,
+                    wallpaper_group: None,
                 }.update(conn).and(Ok(())),
             }
         }
@@ -53,6 +53,7 @@ impl FullSubTilingPost {
                 title: self.title,
                 owner_id: Some(owner_id),
                 tiling_type_id,
+                wallpaper_group: None,
             },
             label_ids: self.label_ids,
         }
@@ -67,6 +68,7 @@ impl FullSubTilingPatch {
                 title: self.title,
                 owner_id: None,
                 tiling_type_id: None,
+                wallpaper_group: None,
             },
             label_ids: self.label_ids,
         }
@@ -76,7 +78,7 @@ impl FullSubTilingPatch {
 #[cfg(not(target_arch = "wasm32"))]
 mod internal {
     use super::*;
-    use diesel::{self, prelude::*, result::Error as DieselError};
+    use diesel::{self, pg::upsert::excluded, prelude::*, result::Error as DieselError};
     use result::{Error, Result};
     use schema::*;
     use std::collections::HashMap;
@@ -96,6 +98,10 @@ mod internal {
         fn delete(id: i32, conn: &PgConnection) -> Result<usize> {
             diesel::delete(tilinglabel::table.filter(tilinglabel::tiling_id.eq(id)))
                 .execute(conn)?;
+            diesel::delete(colorscheme::table.filter(colorscheme::tiling_id.eq(id)))
+                .execute(conn)?;
+            diesel::delete(ungroupedtiling::table.filter(ungroupedtiling::tiling_id.eq(id)))
+                .execute(conn)?;
             let tiling = Tiling::find(id, conn)?;
             match tiling.tiling_type_id {
                 2 => { // Atlas
@@ -147,11 +153,53 @@ mod internal {
         fn delete_batch(ids: Vec<i32>, conn: &PgConnection) -> Result<usize> {
             diesel::delete(tilinglabel::table.filter(tilinglabel::tiling_id.eq_any(ids.clone())))
                 .execute(conn)?;
+            diesel::delete(colorscheme::table.filter(colorscheme::tiling_id.eq_any(ids.clone())))
+                .execute(conn)?;
+            diesel::delete(ungroupedtiling::table.filter(ungroupedtiling::tiling_id.eq_any(ids.clone())))
+                .execute(conn)?;
 
             Tiling::delete_batch(ids, conn)
         }
     }
 
+    impl Tiling {
+        // find_by_wallpaper_group lists the tilings whose symmetry group is named wallpaper_group, e.g. p4m
+        pub fn find_by_wallpaper_group(wallpaper_group: String, limit: u32, conn: &PgConnection) -> Result<Vec<Tiling>> {
+            Ok(tiling::table
+                .filter(tiling::wallpaper_group.eq(wallpaper_group))
+                .order(tiling::id)
+                .limit(limit as i64)
+                .load(conn)?)
+        }
+
+        // set_wallpaper_group stores the name of a tiling's wallpaper group, which is kept apart from the tiling's
+        // labels so that those are only ever the ones its owner gave it. A tiling whose group couldn't be found is
+        // left ungrouped, with the reason recorded so that it isn't looked for again by Atlas::find_ungrouped_ids.
+        pub fn set_wallpaper_group(id: i32, wallpaper_group: std::result::Result<String, String>, conn: &PgConnection) -> Result<FullTiling> {
+            match wallpaper_group {
+                Ok(wallpaper_group) => {
+                    diesel::update(tiling::table.find(id))
+                        .set(tiling::wallpaper_group.eq(wallpaper_group))
+                        .execute(conn)?;
+                    diesel::delete(ungroupedtiling::table.find(id))
+                        .execute(conn)?;
+                },
+                Err(reason) => {
+                    diesel::update(tiling::table.find(id))
+                        .set(tiling::wallpaper_group.eq(None::<String>))
+                        .execute(conn)?;
+                    diesel::insert_into(ungroupedtiling::table)
+                        .values((ungroupedtiling::tiling_id.eq(id), ungroupedtiling::reason.eq(reason)))
+                        .on_conflict(ungroupedtiling::tiling_id)
+                        .do_update()
+                        .set(ungroupedtiling::reason.eq(excluded(ungroupedtiling::reason)))
+                        .execute(conn)?;
+                },
+            }
+            FullTiling::find(id, conn)
+        }
+    }
+
     impl FullInsertable for FullTilingPost {
         type Base = FullTiling;
 
@@ -86,6 +86,13 @@ pub fn get_account_tilings(auth_account: AuthAccount, conn: &PgConnection) -> Re
     FullTiling::find_batch(tiling_ids, conn)
 }
 
+pub fn get_account_patches(auth_account: AuthAccount, conn: &PgConnection) -> Result<Vec<Patch>> {
+    patch::table
+        .filter(patch::owner_id.eq(auth_account.id))
+        .get_results(conn)
+        .map_err(Error::from)
+}
+
 pub fn reset_api_key(mut auth_account: AuthAccount, conn: &PgConnection) -> Result<String> {
     diesel::delete(apikey::table.filter(apikey::account_id.eq(auth_account.id)))
         .execute(conn)?;
@@ -0,0 +1,12 @@
+use diesel::{PgConnection, prelude::*};
+use models::*;
+use result::{Error, Result};
+use schema::*;
+
+pub fn get_tiling_color_schemes(tiling_id: i32, conn: &PgConnection) -> Result<Vec<ColorScheme>> {
+    colorscheme::table
+        .filter(colorscheme::tiling_id.eq(tiling_id))
+        .order(colorscheme::id)
+        .get_results(conn)
+        .map_err(Error::from)
+}
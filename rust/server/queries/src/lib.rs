@@ -1,9 +1,11 @@
 mod account;
+mod colorscheme;
 mod labels;
 mod polygon;
 mod search;
 
 pub use self::account::*;
+pub use self::colorscheme::*;
 pub use self::labels::*;
 pub use self::polygon::*;
 pub use self::search::*;
@@ -11,6 +11,8 @@ mod lib {
         request::Outcome,
         serde::json::Json,
     };
+    use serde::Serialize;
+    use serde_json::{Map, Value};
 
     pub const API_KEY_INVALID_ERR_MSG: &'static str = "API key invalid.";
     pub const API_KEY_MISSING_ERR_MSG: &'static str = "API key missing.";
@@ -48,6 +50,8 @@ mod lib {
         RateLimit,
         Redis(RedisError),
         Status(Status),
+        // Structured is an error whose body is a json object, with a code and whichever fields locate the error
+        Structured(Status, Value),
         Unauthorized,
     }
 
@@ -62,6 +66,7 @@ mod lib {
                 Error::RateLimit => write!(f, "Rate Limit"),
                 Error::Redis(err) => write!(f, "{}", err),
                 Error::Status(status) => write!(f, "{}", status),
+                Error::Structured(_, body) => write!(f, "{}", body),
                 Error::Unauthorized => write!(f, "{}", UNAUTHORIZED_ERR_MSG),
             }
         }
@@ -69,6 +74,21 @@ mod lib {
 
     impl std::error::Error for Error {}
 
+    impl Error {
+        // from_kind builds a Structured error out of err, an enum serialized with its variant under kind, whose
+        // body has err's fields along with its variant as code and its message
+        pub fn from_kind<E: Serialize + std::fmt::Display>(status: Status, err: &E) -> Error {
+            let mut body = match serde_json::to_value(err) {
+                Ok(Value::Object(fields)) => fields,
+                _ => Map::new(),
+            };
+            let code = body.remove("kind").unwrap_or_else(|| Value::String(String::from("Error")));
+            body.insert(String::from("code"), code);
+            body.insert(String::from("message"), Value::String(err.to_string()));
+            Error::Structured(status, Value::Object(body))
+        }
+    }
+
     #[macro_export]
     macro_rules! error_type {
         ($($name:ident),*) => {
@@ -134,9 +154,15 @@ mod lib {
 
     impl<'r> rocket::response::Responder<'r, 'static> for Error {
         fn respond_to(self, req: &'r rocket::request::Request<'_>) -> rocket::response::Result<'static> {
-            let response = Response::from(self);
-            rocket::response::Response::build_from(Json(response.message).respond_to(&req).unwrap())
-                .status(response.status)
+            let (body, status) = match self {
+                Error::Structured(status, body) => (body, status),
+                err => {
+                    let response = Response::from(err);
+                    (Value::String(response.message), response.status)
+                },
+            };
+            rocket::response::Response::build_from(Json(body).respond_to(&req).unwrap())
+                .status(status)
                 .header(ContentType::JSON)
                 .ok()
         }
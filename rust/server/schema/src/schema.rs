@@ -32,6 +32,7 @@ table! {
         id -> Int4,
         tiling_id -> Int4,
         tiling_type_id -> Int4,
+        canonical_key -> Nullable<Text>,
     }
 }
 
@@ -55,6 +56,16 @@ table! {
     }
 }
 
+table! {
+    colorscheme (id) {
+        id -> Int4,
+        title -> Varchar,
+        tiling_id -> Int4,
+        owner_id -> Nullable<Int4>,
+        data -> Text,
+    }
+}
+
 table! {
     label (id) {
         id -> Int4,
@@ -62,6 +73,16 @@ table! {
     }
 }
 
+table! {
+    patch (id) {
+        id -> Int4,
+        title -> Varchar,
+        atlas_id -> Int4,
+        owner_id -> Nullable<Int4>,
+        data -> Text,
+    }
+}
+
 table! {
     point (id) {
         id -> Int4,
@@ -108,6 +129,7 @@ table! {
         title -> Varchar,
         tiling_type_id -> Int4,
         owner_id -> Nullable<Int4>,
+        wallpaper_group -> Nullable<Varchar>,
     }
 }
 
@@ -126,6 +148,20 @@ table! {
     }
 }
 
+table! {
+    ungroupedtiling (tiling_id) {
+        tiling_id -> Int4,
+        reason -> Text,
+    }
+}
+
+table! {
+    unkeyedatlas (atlas_id) {
+        atlas_id -> Int4,
+        reason -> Text,
+    }
+}
+
 joinable!(accountrole -> account (account_id));
 joinable!(accountrole -> role (role_id));
 joinable!(apikey -> account (account_id));
@@ -133,6 +169,10 @@ joinable!(atlas -> tiling (tiling_id));
 joinable!(atlasedge -> atlas (atlas_id));
 joinable!(atlasedge -> polygonpoint (polygon_point_id));
 joinable!(atlasvertex -> atlas (atlas_id));
+joinable!(colorscheme -> account (owner_id));
+joinable!(colorscheme -> tiling (tiling_id));
+joinable!(patch -> account (owner_id));
+joinable!(patch -> atlas (atlas_id));
 joinable!(polygon -> account (owner_id));
 joinable!(polygonlabel -> label (label_id));
 joinable!(polygonlabel -> polygon (polygon_id));
@@ -142,6 +182,8 @@ joinable!(tiling -> account (owner_id));
 joinable!(tiling -> tilingtype (tiling_type_id));
 joinable!(tilinglabel -> label (label_id));
 joinable!(tilinglabel -> tiling (tiling_id));
+joinable!(ungroupedtiling -> tiling (tiling_id));
+joinable!(unkeyedatlas -> atlas (atlas_id));
 
 allow_tables_to_appear_in_same_query!(
     account,
@@ -150,7 +192,9 @@ allow_tables_to_appear_in_same_query!(
     atlas,
     atlasedge,
     atlasvertex,
+    colorscheme,
     label,
+    patch,
     point,
     polygon,
     polygonlabel,
@@ -159,4 +203,6 @@ allow_tables_to_appear_in_same_query!(
     tiling,
     tilinglabel,
     tilingtype,
+    ungroupedtiling,
+    unkeyedatlas,
 );
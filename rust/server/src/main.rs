@@ -7,9 +7,13 @@ use diesel::prelude::*;
 use diesel::pg::PgConnection;
 use r2d2_redis::{r2d2, RedisConnectionManager};
 use rate_limiter::*;
+use rocket::{Build, Rocket};
 
 embed_migrations!();
 
+// BACKFILL_COMMAND is the argument the server is run with to backfill what atlases stored before it need, see backfill
+const BACKFILL_COMMAND: &str = "backfill";
+
 fn format_url(protocol: &str, user: Option<String>, password: Option<String>, hostname: String, port: String, dbname: Option<String>) -> String {
     let account = match user {
         None => String::from(""),
@@ -86,8 +90,7 @@ fn set_env() -> (String, String) {
     (postgres_url, redis_url)
 }
 
-#[launch]
-fn rocket() -> _ {
+fn rocket() -> Rocket<Build> {
     let (postgres_url, redis_url) = set_env();
 
     let postgres_conn = &PgConnection::establish(&postgres_url).expect(&format!("Error connecting to {}", postgres_url));
@@ -107,22 +110,31 @@ fn rocket() -> _ {
             check_email,
             check_password_reset_code,
             create_atlas,
+            create_color_scheme,
+            create_patch,
             create_polygon,
             delete_atlas,
+            delete_color_scheme,
             delete_label,
+            delete_patch,
             delete_polygon,
             delete_tiling,
             fail_rait_limit,
             get_account,
+            get_account_patches,
             get_account_tilings,
             get_atlas,
             get_atlases,
             get_atlas_by_tiling_id,
+            get_color_scheme,
+            get_color_schemes_by_tiling_id,
             get_labels,
+            get_patch,
             get_polygon,
             get_polygons,
             get_tiling,
             get_tilings,
+            get_tilings_by_wallpaper_group,
             get_tiling_type,
             get_tiling_types,
             lock_atlas,
@@ -137,13 +149,70 @@ fn rocket() -> _ {
             sign_in,
             sign_out,
             sign_up,
+            suggest_vertex_types,
             tiling_search,
             update_atlas,
+            update_color_scheme,
+            update_patch,
             update_polygon,
             update_tiling,
             upsert_label,
+            validate_atlas,
             verify,
         ])
         .attach(RateLimiter {})
         .attach(DbConn::fairing())
 }
+
+// backfill keys the tilings of atlases stored before tilings were keyed and finds the wallpaper groups of those stored
+// before groups were, logging the atlases for which either can't be. Those are recorded as such and skipped when run
+// again. It returns whether every atlas could be looked at.
+async fn backfill(rocket: Rocket<Build>) -> Result<bool, rocket::Error> {
+    let rocket = rocket.ignite().await?;
+    let db = match DbConn::get_one(&rocket).await {
+        Some(db) => db,
+        None => {
+            error!("Failed to connect to the database to backfill");
+            return Ok(false)
+        },
+    };
+    Ok(db.run(|conn| {
+        let keyed = match backfill_canonical_keys(conn) {
+            Ok(unkeyed_atlas_ids) => {
+                if !unkeyed_atlas_ids.is_empty() {
+                    warn!("Atlases left unkeyed as duplicates or unkeyable: {:?}", unkeyed_atlas_ids);
+                }
+                true
+            },
+            Err(err) => {
+                error!("Failed to key atlases: {}", err);
+                false
+            },
+        };
+        let grouped = match backfill_wallpaper_groups(conn) {
+            Ok(ungrouped_atlas_ids) => {
+                if !ungrouped_atlas_ids.is_empty() {
+                    warn!("Atlases whose tilings' wallpaper groups can't be found: {:?}", ungrouped_atlas_ids);
+                }
+                true
+            },
+            Err(err) => {
+                error!("Failed to find atlases' wallpaper groups: {}", err);
+                false
+            },
+        };
+        keyed && grouped
+    }).await)
+}
+
+#[rocket::main]
+async fn main() -> Result<(), rocket::Error> {
+    if std::env::args().nth(1).as_deref() == Some(BACKFILL_COMMAND) {
+        if !backfill(rocket()).await? {
+            std::process::exit(1)
+        }
+        return Ok(())
+    }
+    rocket().launch().await?;
+    Ok(())
+}
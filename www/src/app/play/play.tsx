@@ -4,7 +4,7 @@ import { Autocomplete } from "@material-ui/lab";
 import { Canvas, clearCanvas } from "components";
 import { CircularProgress, TextField, Typography } from "@material-ui/core";
 import { debounce, newGlobalId } from "utils";
-import { getTiling, insertTileByPoint, removeTiling, setTiling, tilingSearch } from "client";
+import { getTiling, insertTileByPoint, redo, removeTiling, setTiling, tilingSearch, undo } from "client";
 import { useHistory, useLocation } from "react-router";
 
 type TextSearchItem = {
@@ -74,7 +74,7 @@ export const Play = () => {
     () => {
       clearCanvas(globalId);
       if (tilingId) {
-        setTiling(globalId, tilingId);
+        setTiling(globalId, tilingId).catch((err: ClientError) => console.log(err.code, err.message));
         const search = searchRef.current;
         if (search && !search.value) {
           (async () => {
@@ -109,6 +109,32 @@ export const Play = () => {
   //   [containerRef, searchRef],
   // );
 
+  useEffect(
+    () => {
+      const onKeyDown = (event: KeyboardEvent) => {
+        if (!(event.ctrlKey || event.metaKey) || event.target instanceof HTMLInputElement) {
+          return;
+        }
+        const key = event.key.toLowerCase();
+        try {
+          if (key === "y" || (key === "z" && event.shiftKey)) {
+            event.preventDefault();
+            redo(globalId);
+          } else if (key === "z") {
+            event.preventDefault();
+            undo(globalId);
+          }
+        } catch (e) {
+          const { code, message } = e as ClientError;
+          console.log(code, message);
+        }
+      };
+      window.addEventListener("keydown", onKeyDown);
+      return () => window.removeEventListener("keydown", onKeyDown);
+    },
+    [globalId],
+  );
+
   useEffect(() => {
     const html = Array.from(document.getElementsByTagName("html"))[0];
     html.scrollTop = 0;
@@ -54,7 +54,7 @@ const runTiling = async ({ containerRef, globalId, subContainerRef }: CanvasConf
     const container = containerRef?.current;
     if (container) {
       const tiling = tilings[Math.floor(Math.random() * tilings.length)];
-      setTiling(globalId, tiling.id);
+      setTiling(globalId, tiling.id).catch((err: ClientError) => console.log(err.code, err.message));
       clearCanvas(globalId);
       container.style.opacity = `${maxOpacity}`;
 
@@ -2,7 +2,7 @@ import React, { useEffect, useMemo, useRef } from "react";
 import styles from "./styles.module.scss";
 import { Canvas, canvasXOffset, canvasYOffset } from "components";
 import { newGlobalId } from "utils";
-import { insertTileByPoint, removeTiling, setTiling } from "client";
+import { fillBounds, removeTiling, setTiling } from "client";
 
 type Props = {
   height: number;
@@ -18,16 +18,17 @@ export const Thumbnail = ({ height, tilingId, width }: Props) => {
 
   useEffect(
     () => {
-      setTiling(globalId, tilingId);
+      setTiling(globalId, tilingId).catch((err: ClientError) => console.log(err.code, err.message));
       setTimeout(
         () => {
-          for (let x = -width / 2 - canvasXOffset; x < width / 2 - canvasXOffset + 2 * tileScale; x += tileScale) {
-            for (let y = -canvasYOffset; y < height - canvasYOffset + 2 * tileScale; y += tileScale) {
-              try {
-                insertTileByPoint(globalId, x, y);
-              } catch (e) {}
-            }
-          }
+          try {
+            fillBounds(
+              globalId,
+              tileScale - canvasXOffset,
+              height / 2 - canvasYOffset + tileScale,
+              Math.max(width, height) / 2 + tileScale,
+            );
+          } catch (e) {}
         },
         500,
       );
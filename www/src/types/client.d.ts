@@ -0,0 +1,7 @@
+// types of the values passed to and from the client's exports, as declared by its typescript custom section in
+// rust/client/src/types.rs, made global like the rest of these types
+type Point = import("client").Point;
+type TileState = import("client").TileState;
+type TileDescriptor = import("client").TileDescriptor;
+type ErrorCode = import("client").ErrorCode;
+type ClientError = import("client").ClientError;